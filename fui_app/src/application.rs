@@ -5,6 +5,7 @@ use anyhow::Result;
 use std::cell::RefCell;
 use std::ops::DerefMut;
 use std::rc::Rc;
+use std::time::Instant;
 
 use crate::Dispatcher;
 use crate::DrawingContext;
@@ -62,6 +63,13 @@ impl Application {
         &self.event_loop_iteration
     }
 
+    ///
+    /// Registers application-scoped service.
+    ///
+    pub fn register_service<T: ?Sized + 'static>(&mut self, service: Rc<RefCell<T>>) {
+        self.window_manager.borrow_mut().register_service::<T>(service);
+    }
+
     pub fn add_window<V: ViewModel>(
         &mut self,
         window_builder: winit::window::WindowBuilder,
@@ -82,6 +90,9 @@ impl Application {
         let drawing_context = self.drawing_context.clone();
         let window_manager = self.window_manager.clone();
 
+        // time of the nearest gesture timer in all the windows
+        let mut next_timer: Option<Instant> = None;
+
         event_loop.run(move |event, _, control_flow| {
             event_loop_iteration.borrow_mut().emit(());
             CallbackExecutor::execute_all_in_queue();
@@ -89,7 +100,20 @@ impl Application {
 
             match event {
                 winit::event::Event::MainEventsCleared => {
+                    next_timer = None;
                     for window_entry in window_manager.borrow_mut().get_windows_mut().values_mut() {
+                        Application::handle_timers(
+                            &mut window_entry.window.borrow_mut(),
+                            &mut drawing_context.borrow_mut(),
+                        );
+
+                        let window_next_timer =
+                            window_entry.window.borrow().event_processor.get_next_timer();
+                        next_timer = match (next_timer, window_next_timer) {
+                            (Some(t1), Some(t2)) => Some(t1.min(t2)),
+                            (t1, t2) => t1.or(t2),
+                        };
+
                         if Application::is_dirty(&mut window_entry.window.borrow_mut()) {
                             window_entry
                                 .window
@@ -116,11 +140,20 @@ impl Application {
                         if physical_size.width > 0 && physical_size.height > 0 {
                             let cpu_time = cpu_time::ProcessTime::now();
 
+                            let background_color = window_entry
+                                .services
+                                .borrow()
+                                .get_theme_service()
+                                .map(|theme_service| theme_service.borrow().get_theme())
+                                .unwrap_or_else(Theme::get_default)
+                                .get_color("WindowBackground");
+
                             Application::render(
                                 &mut window_entry.window.borrow_mut(),
                                 &mut drawing_context.borrow_mut(),
                                 physical_size.width as u32,
                                 physical_size.height as u32,
+                                background_color,
                             );
 
                             let cpu_time = cpu_time.elapsed();
@@ -196,30 +229,55 @@ impl Application {
                                 drawing_context.deref_mut(),
                             );
 
-                            // events go only to the top layer
-                            // in future it may change to allow non-modal layers
-                            let root_view = window.get_layers().last().map(|r| r.clone());
-                            if let Some(root_view) = root_view {
-                                window.event_processor.handle_event(
-                                    &root_view,
-                                    &mut fui_drawing_context,
-                                    &input_event,
-                                );
-                            }
+                            // events go to the layers above the top modal one,
+                            // event processor routes them with hit testing
+                            let input_layers = window.get_input_layers();
+                            window.event_processor.handle_event(
+                                &input_layers,
+                                &mut fui_drawing_context,
+                                &input_event,
+                            );
+
+                            let cursor_icon = window.event_processor.get_cursor_icon();
+                            window.set_cursor_icon(cursor_icon);
                         }
                     }
                 }
 
-                _ => *control_flow = winit::event_loop::ControlFlow::Wait,
+                _ => {
+                    *control_flow = match next_timer {
+                        Some(next_timer) => winit::event_loop::ControlFlow::WaitUntil(next_timer),
+                        None => winit::event_loop::ControlFlow::Wait,
+                    }
+                }
             };
         });
     }
 
+    fn handle_timers(window: &mut Window, drawing_context: &mut DrawingContext) {
+        let physical_size = window.drawing_window_target.get_window().inner_size();
+        let mut fui_drawing_context = FuiDrawingContext::new(
+            (physical_size.width as u16, physical_size.height as u16),
+            drawing_context,
+        );
+
+        // the same as for input events, timers affect only the layers
+        // above the top modal one
+        let input_layers = window.get_input_layers();
+        window.event_processor.handle_timers(
+            &input_layers,
+            &mut fui_drawing_context,
+            Instant::now(),
+        );
+    }
+
     fn is_dirty(window: &mut Window) -> bool {
         let mut res = false;
         for root_view in window.get_layers() {
             let root_control = root_view.borrow();
-            if root_control.get_context().is_dirty() {
+            if root_control.get_context().is_dirty()
+                || root_control.get_context().is_redraw_needed()
+            {
                 res = true;
                 break;
             }
@@ -232,7 +290,13 @@ impl Application {
         res
     }
 
-    fn render(window: &mut Window, drawing_context: &mut DrawingContext, width: u32, height: u32) {
+    fn render(
+        window: &mut Window,
+        drawing_context: &mut DrawingContext,
+        width: u32,
+        height: u32,
+        background_color: Color,
+    ) {
         let size = Size::new(width as f32, height as f32);
 
         let mut fui_drawing_context =
@@ -240,11 +304,22 @@ impl Application {
 
         let mut primitives = Vec::new();
 
+        // layout can be skipped when the controls only requested redrawing
+        // (the whole window is drawn also when they requested only some rects
+        // with add_redraw_rect(), the back buffer is not kept between the frames)
+        let is_layout_needed = window.is_dirty
+            || window
+                .get_layers()
+                .iter()
+                .any(|root_view| root_view.borrow().get_context().is_dirty());
+
         for root_view in window.get_layers() {
             let mut root_control = root_view.borrow_mut();
 
-            root_control.measure(&mut fui_drawing_context, size);
-            root_control.set_rect(Rect::new(0f32, 0f32, size.width, size.height));
+            if is_layout_needed {
+                root_control.measure(&mut fui_drawing_context, size);
+                root_control.set_rect(Rect::new(0f32, 0f32, size.width, size.height));
+            }
 
             let (mut primitives1, mut overlay) =
                 root_control.to_primitives(&mut fui_drawing_context);
@@ -252,6 +327,7 @@ impl Application {
             primitives.append(&mut overlay);
 
             root_control.get_context_mut().set_is_dirty(false);
+            root_control.get_context_mut().set_is_redraw_needed(false);
         }
 
         window.is_dirty = false;
@@ -262,7 +338,7 @@ impl Application {
         } else {
             drawing_context.clear(
                 window.drawing_window_target.get_render_target(),
-                &[0.3f32, 0.4f32, 0.3f32, 1.0f32],
+                &background_color,
             );
             let res = drawing_context.draw(
                 window.drawing_window_target.get_render_target(),
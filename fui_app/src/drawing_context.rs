@@ -11,9 +11,7 @@ use drawing::resources::Resources;
 use drawing::TextureFont;
 use drawing_gl::*;
 
-use find_folder;
-use std::fs::File;
-use std::io::Read;
+use crate::FontManager;
 use winit::event_loop::EventLoop;
 use winit::window::WindowBuilder;
 
@@ -26,6 +24,7 @@ pub struct DrawingContext {
     resources: Resources<DrawingDevice, DrawingFont>,
     device: DrawingDevice,
     renderer: Renderer,
+    font_manager: FontManager,
 }
 
 impl DrawingContext {
@@ -34,6 +33,7 @@ impl DrawingContext {
             resources: Resources::new(),
             device: DrawingDevice::new()?,
             renderer: Renderer::new(),
+            font_manager: FontManager::new(),
         })
     }
 
@@ -48,95 +48,60 @@ impl DrawingContext {
             .create_window_target(window_builder, &event_loop, shared_window_target)?)
     }
 
-    pub fn get_font(&mut self, font_name: &'static str) -> Result<&mut DrawingFont> {
-        if let None = self.resources.fonts_mut().get_mut(&font_name.to_string()) {
-            let font_path = find_folder::Search::ParentsThenKids(3, 3)
-                .for_folder("assets")
-                .unwrap()
-                .join(font_name)
-                .into_os_string()
-                .into_string()
-                .unwrap();
-            let mut file = File::open(font_path).unwrap();
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)?;
-
-            let font = DrawingFont::create(&mut self.device, buffer)?;
+    pub fn get_font_manager(&self) -> &FontManager {
+        &self.font_manager
+    }
 
-            self.resources
-                .fonts_mut()
-                .insert(font_name.to_string(), font);
-        }
+    pub fn get_font_manager_mut(&mut self) -> &mut FontManager {
+        &mut self.font_manager
+    }
 
-        Ok(self
-            .resources
+    pub fn get_font(&mut self, font_name: &str) -> Result<&mut DrawingFont> {
+        self.load_font(font_name)?;
+        self.resources
             .fonts_mut()
-            .get_mut(&font_name.to_string())
-            .unwrap())
+            .get_mut(font_name)
+            .ok_or_else(|| format_err!("Font {} not loaded.", font_name))
     }
 
     pub fn get_font_dimensions(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(u16, u16)> {
-        if let None = self.resources.fonts_mut().get_mut(&font_name.to_string()) {
-            let font_path = find_folder::Search::ParentsThenKids(3, 3)
-                .for_folder("assets")
-                .unwrap()
-                .join(font_name)
-                .into_os_string()
-                .into_string()
-                .unwrap();
-            let mut file = File::open(font_path).unwrap();
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)?;
-
-            let font = DrawingFont::create(&mut self.device, buffer)?;
-
-            self.resources
-                .fonts_mut()
-                .insert(font_name.to_string(), font);
-        }
-
-        if let Some(font) = self.resources.fonts_mut().get_mut(&font_name.to_string()) {
-            font.get_dimensions(&mut self.device, FontParams { size: size }, &text)
-        } else {
-            Ok((0, size as u16))
+        self.load_font(font_name)?;
+        match self.resources.fonts_mut().get_mut(font_name) {
+            Some(font) => font.get_dimensions(&mut self.device, FontParams { size: size }, &text),
+            None => Err(format_err!("Font {} not loaded.", font_name)),
         }
     }
 
     pub fn get_font_dimensions_each_char(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(Vec<i16>, u16)> {
-        if let None = self.resources.fonts_mut().get_mut(&font_name.to_string()) {
-            let font_path = find_folder::Search::ParentsThenKids(3, 3)
-                .for_folder("assets")
-                .unwrap()
-                .join(font_name)
-                .into_os_string()
-                .into_string()
-                .unwrap();
-            let mut file = File::open(font_path).unwrap();
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)?;
+        self.load_font(font_name)?;
+        match self.resources.fonts_mut().get_mut(font_name) {
+            Some(font) => {
+                font.get_dimensions_each_char(&mut self.device, FontParams { size: size }, &text)
+            }
+            None => Err(format_err!("Font {} not loaded.", font_name)),
+        }
+    }
 
+    fn load_font(&mut self, font_name: &str) -> Result<()> {
+        if !self.resources.fonts_mut().contains_key(font_name) {
+            let buffer = self.font_manager.get_font_data(font_name)?.to_vec();
             let font = DrawingFont::create(&mut self.device, buffer)?;
 
             self.resources
                 .fonts_mut()
                 .insert(font_name.to_string(), font);
         }
-
-        if let Some(font) = self.resources.fonts_mut().get_mut(&font_name.to_string()) {
-            font.get_dimensions_each_char(&mut self.device, FontParams { size: size }, &text)
-        } else {
-            Ok((Vec::new(), size as u16))
-        }
+        Ok(())
     }
 
     pub fn get_resources(&self) -> &Resources<DrawingDevice, DrawingFont> {
@@ -219,9 +184,31 @@ impl DrawingContext {
 }
 
 impl fui_core::Resources for DrawingContext {
+    fn resolve_font(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: fui_core::FontStyle,
+        text: &str,
+    ) -> Result<String> {
+        self.font_manager
+            .resolve_for_text(family, weight, style, text)
+    }
+
+    fn resolve_font_runs(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: fui_core::FontStyle,
+        text: &str,
+    ) -> Result<Vec<fui_core::FontRun>> {
+        self.font_manager
+            .resolve_runs_for_text(family, weight, style, text)
+    }
+
     fn get_font_dimensions(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(u16, u16)> {
@@ -230,7 +217,7 @@ impl fui_core::Resources for DrawingContext {
 
     fn get_font_dimensions_each_char(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(Vec<i16>, u16)> {
@@ -1,3 +1,6 @@
+// touchpads report the scrolling in pixels
+const PIXELS_PER_LINE: f32 = 20.0f32;
+
 pub fn convert_event(event: &winit::event::WindowEvent) -> Option<fui_core::InputEvent> {
     match event {
         winit::event::WindowEvent::CursorEntered { .. } => {
@@ -28,6 +31,29 @@ pub fn convert_event(event: &winit::event::WindowEvent) -> Option<fui_core::Inpu
             })
         }
 
+        winit::event::WindowEvent::Touch(touch) => Some(fui_core::InputEvent::Touch {
+            phase: match touch.phase {
+                winit::event::TouchPhase::Started => fui_core::TouchPhase::Started,
+                winit::event::TouchPhase::Moved => fui_core::TouchPhase::Moved,
+                winit::event::TouchPhase::Ended => fui_core::TouchPhase::Ended,
+                winit::event::TouchPhase::Cancelled => fui_core::TouchPhase::Cancelled,
+            },
+            id: touch.id,
+            position: fui_core::Point::new(touch.location.x as f32, touch.location.y as f32),
+        }),
+
+        winit::event::WindowEvent::MouseWheel { delta, .. } => {
+            Some(fui_core::InputEvent::MouseWheel {
+                delta: match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => fui_core::Point::new(*x, *y),
+                    winit::event::MouseScrollDelta::PixelDelta(position) => fui_core::Point::new(
+                        position.x as f32 / PIXELS_PER_LINE,
+                        position.y as f32 / PIXELS_PER_LINE,
+                    ),
+                },
+            })
+        }
+
         winit::event::WindowEvent::KeyboardInput { input, .. } => {
             Some(fui_core::InputEvent::KeyboardInput(fui_core::KeyEvent {
                 state: match input.state {
@@ -86,6 +112,32 @@ pub fn convert_event(event: &winit::event::WindowEvent) -> Option<fui_core::Inpu
                     Some(winit::event::VirtualKeyCode::Copy) => Some(fui_core::Keycode::Copy),
                     Some(winit::event::VirtualKeyCode::Paste) => Some(fui_core::Keycode::Paste),
                     Some(winit::event::VirtualKeyCode::Cut) => Some(fui_core::Keycode::Cut),
+                    Some(winit::event::VirtualKeyCode::A) => Some(fui_core::Keycode::A),
+                    Some(winit::event::VirtualKeyCode::B) => Some(fui_core::Keycode::B),
+                    Some(winit::event::VirtualKeyCode::C) => Some(fui_core::Keycode::C),
+                    Some(winit::event::VirtualKeyCode::D) => Some(fui_core::Keycode::D),
+                    Some(winit::event::VirtualKeyCode::E) => Some(fui_core::Keycode::E),
+                    Some(winit::event::VirtualKeyCode::F) => Some(fui_core::Keycode::F),
+                    Some(winit::event::VirtualKeyCode::G) => Some(fui_core::Keycode::G),
+                    Some(winit::event::VirtualKeyCode::H) => Some(fui_core::Keycode::H),
+                    Some(winit::event::VirtualKeyCode::I) => Some(fui_core::Keycode::I),
+                    Some(winit::event::VirtualKeyCode::J) => Some(fui_core::Keycode::J),
+                    Some(winit::event::VirtualKeyCode::K) => Some(fui_core::Keycode::K),
+                    Some(winit::event::VirtualKeyCode::L) => Some(fui_core::Keycode::L),
+                    Some(winit::event::VirtualKeyCode::M) => Some(fui_core::Keycode::M),
+                    Some(winit::event::VirtualKeyCode::N) => Some(fui_core::Keycode::N),
+                    Some(winit::event::VirtualKeyCode::O) => Some(fui_core::Keycode::O),
+                    Some(winit::event::VirtualKeyCode::P) => Some(fui_core::Keycode::P),
+                    Some(winit::event::VirtualKeyCode::Q) => Some(fui_core::Keycode::Q),
+                    Some(winit::event::VirtualKeyCode::R) => Some(fui_core::Keycode::R),
+                    Some(winit::event::VirtualKeyCode::S) => Some(fui_core::Keycode::S),
+                    Some(winit::event::VirtualKeyCode::T) => Some(fui_core::Keycode::T),
+                    Some(winit::event::VirtualKeyCode::U) => Some(fui_core::Keycode::U),
+                    Some(winit::event::VirtualKeyCode::V) => Some(fui_core::Keycode::V),
+                    Some(winit::event::VirtualKeyCode::W) => Some(fui_core::Keycode::W),
+                    Some(winit::event::VirtualKeyCode::X) => Some(fui_core::Keycode::X),
+                    Some(winit::event::VirtualKeyCode::Y) => Some(fui_core::Keycode::Y),
+                    Some(winit::event::VirtualKeyCode::Z) => Some(fui_core::Keycode::Z),
                     _ => None,
                 },
 
@@ -100,6 +152,15 @@ pub fn convert_event(event: &winit::event::WindowEvent) -> Option<fui_core::Inpu
             }))
         }
 
+        winit::event::WindowEvent::ModifiersChanged(modifiers) => Some(
+            fui_core::InputEvent::ModifiersChanged(fui_core::KeyModifiers {
+                shift: modifiers.shift(),
+                ctrl: modifiers.ctrl(),
+                alt: modifiers.alt(),
+                win: modifiers.logo(),
+            }),
+        ),
+
         winit::event::WindowEvent::ReceivedCharacter(character) => {
             // filter out characters matching existing keycodes
             match character {
@@ -113,6 +174,8 @@ pub fn convert_event(event: &winit::event::WindowEvent) -> Option<fui_core::Inpu
                 '\t' |
                 // enter
                 '\r' => return None,
+                // other control characters (like Ctrl+C)
+                c if c.is_control() => return None,
                 _ => (),
             }
 
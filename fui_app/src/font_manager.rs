@@ -0,0 +1,543 @@
+use anyhow::{format_err, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use fui_core::{FontRun, FontStyle};
+
+const FONT_EXTENSIONS: [&str; 3] = ["ttf", "otf", "ttc"];
+const MAX_DIRECTORY_DEPTH: usize = 4;
+
+struct RegisteredFont {
+    family: String,
+    weight: u16,
+    style: FontStyle,
+    key: String,
+}
+
+struct LoadedFont {
+    data: Vec<u8>,
+    font: rusttype::Font<'static>,
+}
+
+///
+/// Finds and loads fonts.
+///
+/// Fonts can be registered from bytes or files under family name,
+/// weight and style. Other fonts are searched by file name
+/// in the font directories (assets folder and system font folders by default).
+///
+/// Resolved fonts are identified by keys, which are used as font names
+/// by the drawing resources.
+///
+/// The font directories are scanned once, when they are added,
+/// so resolving fonts during the layout doesn't touch the file system.
+///
+pub struct FontManager {
+    directories: Vec<PathBuf>,
+    directory_index: HashMap<String, PathBuf>,
+    registered_fonts: Vec<RegisteredFont>,
+    fallback_families: Vec<String>,
+    loaded_fonts: HashMap<String, LoadedFont>,
+}
+
+impl FontManager {
+    pub fn new() -> Self {
+        let mut directories = Vec::new();
+        if let Ok(assets) = find_folder::Search::ParentsThenKids(3, 3).for_folder("assets") {
+            directories.push(assets);
+        }
+        directories.append(&mut FontManager::get_system_font_directories());
+
+        let mut directory_index = HashMap::new();
+        for directory in directories.iter().rev() {
+            FontManager::index_directory(directory, 0, &mut directory_index);
+        }
+
+        FontManager {
+            directories,
+            directory_index,
+            registered_fonts: Vec::new(),
+            fallback_families: Vec::new(),
+            loaded_fonts: HashMap::new(),
+        }
+    }
+
+    pub fn get_directories(&self) -> &[PathBuf] {
+        &self.directories
+    }
+
+    ///
+    /// Adds directory searched for font files
+    /// (before the default ones).
+    ///
+    pub fn add_directory<P: Into<PathBuf>>(&mut self, path: P) {
+        let path = path.into();
+        FontManager::index_directory(&path, 0, &mut self.directory_index);
+        self.directories.insert(0, path);
+    }
+
+    pub fn clear_directories(&mut self) {
+        self.directories.clear();
+        self.directory_index.clear();
+    }
+
+    ///
+    /// Sets the families used for the texts with characters
+    /// not available in the requested font.
+    ///
+    pub fn set_fallback_families(&mut self, families: Vec<String>) {
+        self.fallback_families = families;
+    }
+
+    pub fn register_font_bytes(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let key = format!("{}:{}:{:?}", family, weight, style);
+        let loaded_font = FontManager::parse_font(&key, data)?;
+        self.loaded_fonts.insert(key.clone(), loaded_font);
+
+        self.registered_fonts.retain(|font| font.key != key);
+        self.registered_fonts.push(RegisteredFont {
+            family: family.to_string(),
+            weight,
+            style,
+            key,
+        });
+        Ok(())
+    }
+
+    pub fn register_font_file<P: AsRef<Path>>(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        path: P,
+    ) -> Result<()> {
+        let data = fs::read(path.as_ref()).map_err(|err| {
+            format_err!("Cannot read font file {}: {}", path.as_ref().display(), err)
+        })?;
+        self.register_font_bytes(family, weight, style, data)
+    }
+
+    ///
+    /// Returns the key of the font best matching the family, weight and style.
+    ///
+    /// The family can also be a font file name (like "OpenSans-Regular.ttf").
+    ///
+    pub fn resolve(&mut self, family: &str, weight: u16, style: FontStyle) -> Result<String> {
+        if FontManager::is_file_name(family) {
+            return self.find_file(family).map(|_| family.to_string()).ok_or_else(|| {
+                format_err!("Font file {} not found.", family)
+            });
+        }
+
+        if let Some(key) = self.find_registered(family, weight, style) {
+            return Ok(key);
+        }
+
+        for file_name in FontManager::get_file_name_candidates(family, weight, style) {
+            if self.find_file(&file_name).is_some() {
+                return Ok(file_name);
+            }
+        }
+
+        Err(format_err!(
+            "Font {} (weight {}, {:?}) not found.",
+            family,
+            weight,
+            style
+        ))
+    }
+
+    ///
+    /// Returns the key of the font that has all the characters of the text.
+    ///
+    /// The requested family is tried first, then the fallback families.
+    /// If none of them has all the characters, the first font found is returned.
+    ///
+    pub fn resolve_for_text(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        text: &str,
+    ) -> Result<String> {
+        let keys = self.resolve_with_fallbacks(family, weight, style)?;
+        for key in &keys {
+            if self.has_all_chars(key, text).unwrap_or(false) {
+                return Ok(key.clone());
+            }
+        }
+        Ok(keys[0].clone())
+    }
+
+    ///
+    /// Splits the text into runs displayed with the requested font
+    /// and the fallback fonts for the characters missing in it.
+    ///
+    /// Every character gets the first font (the requested one, then the fallbacks)
+    /// that has it. Characters missing in all the fonts stay with the requested font.
+    ///
+    pub fn resolve_runs_for_text(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        text: &str,
+    ) -> Result<Vec<FontRun>> {
+        let keys = self.resolve_with_fallbacks(family, weight, style)?;
+        Ok(FontManager::split_runs(text, &keys, |key, c| {
+            self.has_char(key, c).unwrap_or(false)
+        }))
+    }
+
+    ///
+    /// Returns font data for the key (loading it when needed).
+    ///
+    pub fn get_font_data(&mut self, key: &str) -> Result<&[u8]> {
+        self.load(key).map(|font| &font.data[..])
+    }
+
+    pub fn has_char(&mut self, key: &str, c: char) -> Result<bool> {
+        let font = self.load(key)?;
+        Ok(font.font.glyph(c).id().0 != 0)
+    }
+
+    pub fn has_all_chars(&mut self, key: &str, text: &str) -> Result<bool> {
+        let font = self.load(key)?;
+        Ok(text
+            .chars()
+            .filter(|c| !c.is_control())
+            .all(|c| font.font.glyph(c).id().0 != 0))
+    }
+
+    ///
+    /// Returns the keys of the requested font and the fallback fonts found.
+    ///
+    fn resolve_with_fallbacks(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+    ) -> Result<Vec<String>> {
+        let mut families = vec![family.to_string()];
+        families.extend(self.fallback_families.iter().cloned());
+
+        let keys: Vec<String> = families
+            .iter()
+            .filter_map(|family| self.resolve(family, weight, style).ok())
+            .collect();
+        if keys.is_empty() {
+            return Err(format_err!("Font {} not found.", family));
+        }
+        Ok(keys)
+    }
+
+    fn split_runs<F: FnMut(&str, char) -> bool>(
+        text: &str,
+        keys: &[String],
+        mut has_char: F,
+    ) -> Vec<FontRun> {
+        let mut runs: Vec<FontRun> = Vec::new();
+        for c in text.chars() {
+            // control characters (like new lines) don't need glyphs,
+            // so they stay in the current run
+            if c.is_control() {
+                if let Some(run) = runs.last_mut() {
+                    run.text.push(c);
+                    continue;
+                }
+            }
+
+            let key = keys.iter().find(|key| has_char(key, c)).unwrap_or(&keys[0]);
+
+            match runs.last_mut() {
+                Some(run) if run.font_name == *key => run.text.push(c),
+                _ => runs.push(FontRun {
+                    font_name: key.clone(),
+                    text: c.to_string(),
+                }),
+            }
+        }
+        runs
+    }
+
+    fn load(&mut self, key: &str) -> Result<&LoadedFont> {
+        if !self.loaded_fonts.contains_key(key) {
+            let path = self
+                .find_file(key)
+                .ok_or_else(|| format_err!("Font file {} not found.", key))?;
+            let data = fs::read(&path).map_err(|err| {
+                format_err!("Cannot read font file {}: {}", path.display(), err)
+            })?;
+            let loaded_font = FontManager::parse_font(key, data)?;
+            self.loaded_fonts.insert(key.to_string(), loaded_font);
+        }
+
+        Ok(&self.loaded_fonts[key])
+    }
+
+    fn parse_font(key: &str, data: Vec<u8>) -> Result<LoadedFont> {
+        if !FontManager::is_font_data_valid(&data) {
+            return Err(format_err!("Cannot parse font {}: invalid font data.", key));
+        }
+
+        // font parser panics on some malformed data
+        let font_data = data.clone();
+        let font = std::panic::catch_unwind(move || rusttype::Font::from_bytes(font_data))
+            .map_err(|_| format_err!("Cannot parse font {}: invalid font data.", key))?
+            .map_err(|err| format_err!("Cannot parse font {}: {}", key, err))?;
+        Ok(LoadedFont { data, font })
+    }
+
+    ///
+    /// Checks the header and the table directory of the font file.
+    ///
+    fn is_font_data_valid(data: &[u8]) -> bool {
+        let read_u16 = |pos: usize| u16::from_be_bytes([data[pos], data[pos + 1]]) as usize;
+        let read_u32 = |pos: usize| {
+            u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize
+        };
+
+        if data.len() < 12 {
+            return false;
+        }
+
+        match &data[0..4] {
+            b"ttcf" => true,
+            [0, 1, 0, 0] | b"OTTO" | b"true" => {
+                let num_tables = read_u16(4);
+                if data.len() < 12 + num_tables * 16 {
+                    return false;
+                }
+                (0..num_tables).all(|i| {
+                    let record = 12 + i * 16;
+                    let offset = read_u32(record + 8);
+                    let length = read_u32(record + 12);
+                    offset
+                        .checked_add(length)
+                        .map(|end| end <= data.len())
+                        .unwrap_or(false)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    fn find_registered(&self, family: &str, weight: u16, style: FontStyle) -> Option<String> {
+        self.registered_fonts
+            .iter()
+            .filter(|font| font.family.eq_ignore_ascii_case(family))
+            .min_by_key(|font| {
+                let style_distance = if font.style == style { 0 } else { 1000 };
+                style_distance + (font.weight as i32 - weight as i32).abs()
+            })
+            .map(|font| font.key.clone())
+    }
+
+    fn find_file(&self, file_name: &str) -> Option<PathBuf> {
+        let path = Path::new(file_name);
+        if path.is_absolute() {
+            return if path.is_file() {
+                Some(path.to_path_buf())
+            } else {
+                None
+            };
+        }
+
+        self.directory_index.get(&file_name.to_lowercase()).cloned()
+    }
+
+    fn index_directory(directory: &Path, depth: usize, index: &mut HashMap<String, PathBuf>) {
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                if depth < MAX_DIRECTORY_DEPTH {
+                    FontManager::index_directory(&path, depth + 1, index);
+                }
+            } else if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
+                if FontManager::is_file_name(file_name) {
+                    // directories are indexed in reverse order of priority,
+                    // so files from the first ones win
+                    index.insert(file_name.to_lowercase(), path.clone());
+                }
+            }
+        }
+    }
+
+    fn is_file_name(name: &str) -> bool {
+        Path::new(name)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| {
+                FONT_EXTENSIONS
+                    .iter()
+                    .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+            })
+            .unwrap_or(false)
+    }
+
+    ///
+    /// Returns file names following the usual naming convention
+    /// (like "DejaVuSans-BoldOblique.ttf") for the nearest weights first.
+    ///
+    fn get_file_name_candidates(family: &str, weight: u16, style: FontStyle) -> Vec<String> {
+        let family: String = family.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let mut weights = vec![
+            (100, "Thin"),
+            (200, "ExtraLight"),
+            (300, "Light"),
+            (400, "Regular"),
+            (500, "Medium"),
+            (600, "SemiBold"),
+            (700, "Bold"),
+            (800, "ExtraBold"),
+            (900, "Black"),
+        ];
+        weights.sort_by_key(|(w, _)| (*w - weight as i32).abs());
+
+        let mut candidates = Vec::new();
+        for (w, weight_name) in weights {
+            let mut names = Vec::new();
+            match style {
+                FontStyle::Normal => {
+                    names.push(weight_name.to_string());
+                    if w == 400 {
+                        names.push("".to_string());
+                    }
+                }
+                FontStyle::Italic => {
+                    if w == 400 {
+                        names.push("Italic".to_string());
+                        names.push("Oblique".to_string());
+                    } else {
+                        names.push(format!("{}Italic", weight_name));
+                        names.push(format!("{}Oblique", weight_name));
+                    }
+                }
+            }
+
+            for name in names {
+                for extension in FONT_EXTENSIONS.iter() {
+                    if name.is_empty() {
+                        candidates.push(format!("{}.{}", family, extension));
+                    } else {
+                        candidates.push(format!("{}-{}.{}", family, name, extension));
+                    }
+                }
+            }
+        }
+        candidates
+    }
+
+    #[cfg(target_os = "linux")]
+    fn get_system_font_directories() -> Vec<PathBuf> {
+        let mut directories = Vec::new();
+        if let Some(home) = std::env::var_os("HOME") {
+            let home = PathBuf::from(home);
+            directories.push(home.join(".local/share/fonts"));
+            directories.push(home.join(".fonts"));
+        }
+        directories.push(PathBuf::from("/usr/local/share/fonts"));
+        directories.push(PathBuf::from("/usr/share/fonts"));
+        directories
+    }
+
+    #[cfg(target_os = "windows")]
+    fn get_system_font_directories() -> Vec<PathBuf> {
+        let windows_dir = std::env::var_os("WINDIR").unwrap_or_else(|| "C:\\Windows".into());
+        vec![PathBuf::from(windows_dir).join("Fonts")]
+    }
+
+    #[cfg(target_os = "macos")]
+    fn get_system_font_directories() -> Vec<PathBuf> {
+        let mut directories = Vec::new();
+        if let Some(home) = std::env::var_os("HOME") {
+            directories.push(PathBuf::from(home).join("Library/Fonts"));
+        }
+        directories.push(PathBuf::from("/Library/Fonts"));
+        directories.push(PathBuf::from("/System/Library/Fonts"));
+        directories
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    fn get_system_font_directories() -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+impl Default for FontManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_candidates_start_with_requested_weight() {
+        let candidates = FontManager::get_file_name_candidates("Open Sans", 700, FontStyle::Normal);
+        assert_eq!(candidates[0], "OpenSans-Bold.ttf");
+
+        let candidates = FontManager::get_file_name_candidates("DejaVu Sans", 400, FontStyle::Italic);
+        assert_eq!(candidates[0], "DejaVuSans-Italic.ttf");
+        assert!(candidates.contains(&"DejaVuSans-BoldOblique.ttf".to_string()));
+    }
+
+    #[test]
+    fn test_missing_font_is_error() {
+        let mut font_manager = FontManager::new();
+        font_manager.clear_directories();
+        assert!(font_manager
+            .resolve("Missing Family", 400, FontStyle::Normal)
+            .is_err());
+        assert!(font_manager.resolve("missing-font.ttf", 400, FontStyle::Normal).is_err());
+        assert!(font_manager.get_font_data("missing-font.ttf").is_err());
+        assert!(font_manager
+            .register_font_bytes("Broken", 400, FontStyle::Normal, vec![0, 1, 2])
+            .is_err());
+        assert!(font_manager
+            .register_font_bytes(
+                "Broken",
+                400,
+                FontStyle::Normal,
+                vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_missing_chars_fall_back_per_run() {
+        let keys = vec!["Latin.ttf".to_string(), "Cyrillic.ttf".to_string()];
+        let runs = FontManager::split_runs("ab\nжя c€", &keys, |key, c| match key {
+            "Latin.ttf" => c.is_ascii(),
+            _ => !c.is_ascii() && c != '€',
+        });
+
+        let runs: Vec<(&str, &str)> = runs
+            .iter()
+            .map(|run| (&run.font_name[..], &run.text[..]))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                ("Latin.ttf", "ab\n"),
+                ("Cyrillic.ttf", "жя"),
+                ("Latin.ttf", " c€"),
+            ]
+        );
+    }
+}
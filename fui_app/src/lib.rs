@@ -9,6 +9,9 @@ pub use dispatcher::*;
 
 mod event_converter;
 
+mod font_manager;
+pub use font_manager::*;
+
 mod high_dpi;
 pub use high_dpi::*;
 
@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use fui_core::ControlObject;
-use fui_core::{EventProcessor, WindowService};
+use fui_core::{CursorIcon, CursorService, EventProcessor, LayerOptions, WindowLayers, WindowService};
 
 use crate::DrawingWindowTarget;
 
@@ -10,7 +10,11 @@ pub struct Window {
     pub drawing_window_target: DrawingWindowTarget,
     pub event_processor: EventProcessor,
     pub is_dirty: bool,
-    control_layers: Vec<Rc<RefCell<dyn ControlObject>>>,
+    control_layers: WindowLayers,
+
+    cursor_icon: CursorIcon,
+    override_cursor_icon: Option<CursorIcon>,
+    window_cursor_icon: CursorIcon,
 }
 
 impl Window {
@@ -19,7 +23,11 @@ impl Window {
             drawing_window_target,
             event_processor: EventProcessor::new(),
             is_dirty: false,
-            control_layers: Vec::new(),
+            control_layers: WindowLayers::new(),
+
+            cursor_icon: CursorIcon::Default,
+            override_cursor_icon: None,
+            window_cursor_icon: CursorIcon::Default,
         }
     }
 
@@ -27,26 +35,93 @@ impl Window {
         &self.drawing_window_target
     }
 
-    pub fn get_layers(&self) -> &Vec<Rc<RefCell<dyn ControlObject>>> {
-        &self.control_layers
+    ///
+    /// Returns layers which can get the input, from the top one
+    /// down to the first modal layer.
+    ///
+    pub fn get_input_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>> {
+        self.control_layers.get_input_layers()
+    }
+
+    fn update_cursor_icon(&mut self) {
+        let cursor_icon = self.override_cursor_icon.unwrap_or(self.cursor_icon);
+        if cursor_icon != self.window_cursor_icon {
+            self.window_cursor_icon = cursor_icon;
+            self.drawing_window_target
+                .get_window()
+                .set_cursor_icon(convert_cursor_icon(cursor_icon));
+        }
     }
 }
 
 impl WindowService for Window {
     fn add_layer(&mut self, control: Rc<RefCell<dyn ControlObject>>) {
-        self.control_layers.push(control);
+        self.add_layer_with_options(control, LayerOptions::modal());
+    }
+
+    fn add_layer_with_options(
+        &mut self,
+        control: Rc<RefCell<dyn ControlObject>>,
+        options: LayerOptions,
+    ) {
+        self.control_layers.add(control, options);
         self.is_dirty = true;
     }
 
     fn remove_layer(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
-        let mut i = 0;
-        while i != self.control_layers.len() {
-            if Rc::ptr_eq(&self.control_layers[i], control) {
-                self.control_layers.remove(i);
-            } else {
-                i += 1;
-            }
-        }
+        self.control_layers.remove(control);
+        self.is_dirty = true;
+    }
+
+    fn get_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>> {
+        self.control_layers.get_layers()
+    }
+
+    fn get_layer_options(&self, control: &Rc<RefCell<dyn ControlObject>>) -> Option<LayerOptions> {
+        self.control_layers.get_options(control)
+    }
+
+    fn set_layer_options(&mut self, control: &Rc<RefCell<dyn ControlObject>>, options: LayerOptions) {
+        self.control_layers.set_options(control, options);
         self.is_dirty = true;
     }
+
+    fn bring_layer_to_front(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        self.control_layers.bring_to_front(control);
+        self.is_dirty = true;
+    }
+}
+
+impl CursorService for Window {
+    fn set_cursor_icon(&mut self, cursor_icon: CursorIcon) {
+        self.cursor_icon = cursor_icon;
+        self.update_cursor_icon();
+    }
+
+    fn set_override_cursor_icon(&mut self, cursor_icon: Option<CursorIcon>) {
+        self.override_cursor_icon = cursor_icon;
+        self.update_cursor_icon();
+    }
+}
+
+fn convert_cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Default => winit::window::CursorIcon::Default,
+        CursorIcon::Arrow => winit::window::CursorIcon::Arrow,
+        CursorIcon::Hand => winit::window::CursorIcon::Hand,
+        CursorIcon::Text => winit::window::CursorIcon::Text,
+        CursorIcon::Wait => winit::window::CursorIcon::Wait,
+        CursorIcon::Progress => winit::window::CursorIcon::Progress,
+        CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+        CursorIcon::Move => winit::window::CursorIcon::Move,
+        CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+        CursorIcon::Grab => winit::window::CursorIcon::Grab,
+        CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+        CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+        CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+        CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+        CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+        CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+    }
 }
@@ -11,23 +11,64 @@ use crate::Window;
 pub struct WindowEntry {
     pub window: Rc<RefCell<Window>>,
     pub services: Rc<RefCell<Services>>,
+    _theme_changed_subscription: Option<EventSubscription>,
 }
 
 pub struct WindowManager {
     drawing_context: Rc<RefCell<DrawingContext>>,
     main_window_id: Option<winit::window::WindowId>,
     windows: HashMap<winit::window::WindowId, WindowEntry>,
+    services: Rc<RefCell<Services>>,
 }
 
 impl WindowManager {
     pub fn new(drawing_context: Rc<RefCell<DrawingContext>>) -> Self {
+        let theme_service = Rc::new(RefCell::new(ThemeService::new(Theme::dark())));
+
         WindowManager {
             drawing_context: drawing_context,
             main_window_id: None,
             windows: HashMap::new(),
+            services: Rc::new(RefCell::new(WindowManager::create_application_services(
+                theme_service,
+            ))),
         }
     }
 
+    fn create_application_services(theme_service: Rc<RefCell<ThemeService>>) -> Services {
+        let mut services = Services::new();
+        services.register::<dyn ClipboardService>(Rc::new(RefCell::new(
+            MemoryClipboardService::new(),
+        )));
+        services.register::<StyleRegistry>(Rc::new(RefCell::new(StyleRegistry::new())));
+        services.register::<ThemeService>(theme_service);
+        services
+    }
+
+    ///
+    /// Returns application-scoped services (parent of all window-scoped services).
+    ///
+    pub fn get_services(&self) -> &Rc<RefCell<Services>> {
+        &self.services
+    }
+
+    pub fn register_service<T: ?Sized + 'static>(&mut self, service: Rc<RefCell<T>>) {
+        self.services.borrow_mut().register::<T>(service);
+    }
+
+    pub fn register_window_service<T: ?Sized + 'static>(
+        &mut self,
+        window_id: winit::window::WindowId,
+        service: Rc<RefCell<T>>,
+    ) -> Result<()> {
+        let window_entry = self
+            .windows
+            .get(&window_id)
+            .ok_or_else(|| anyhow::anyhow!("Window not found."))?;
+        window_entry.services.borrow_mut().register::<T>(service);
+        Ok(())
+    }
+
     pub fn add_window(
         &mut self,
         window_builder: winit::window::WindowBuilder,
@@ -64,8 +105,20 @@ impl WindowManager {
 
         let window_rc = Rc::new(RefCell::new(window));
         let window_service_rc: Rc<RefCell<dyn WindowService>> = window_rc.clone();
+        let cursor_service_rc: Rc<RefCell<dyn CursorService>> = window_rc.clone();
+
+        // the window owns the views holding the services
+        let mut services = Services::with_parent(&self.services);
+        services.register_weak::<dyn WindowService>(&window_service_rc);
+        services.register_weak::<dyn CursorService>(&cursor_service_rc);
+        let services = Rc::new(RefCell::new(services));
 
-        let services = Rc::new(RefCell::new(Services::new(&window_service_rc)));
+        // the window is redrawn with the new theme when the theme is switched
+        let theme_changed_subscription = self
+            .services
+            .borrow()
+            .get_theme_service()
+            .map(|theme_service| theme_service.borrow_mut().dirty_watching(&window_service_rc));
 
         view.borrow_mut()
             .get_context_mut()
@@ -75,6 +128,7 @@ impl WindowManager {
         let window_entry = WindowEntry {
             window: window_rc,
             services: services,
+            _theme_changed_subscription: theme_changed_subscription,
         };
         self.windows.insert(window_id, window_entry);
 
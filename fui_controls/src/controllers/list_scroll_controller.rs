@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use fui_core::*;
+use fui_macros::ui;
+use typemap::TypeMap;
+
+use crate::controls::*;
+use crate::layout::*;
+
+const SCROLL_LINE_SIZE: f32 = 20.0f32;
+const DEFAULT_PAGE_SIZE: usize = 10;
+
+///
+/// Scrolling and keyboard navigation of the list controls (ListBox, TreeView).
+///
+/// The panel with the item views is put into the ScrollArea with the vertical ScrollBar.
+/// Item views are given by the controls, so the rows can be kept in any collection.
+///
+pub(crate) struct ListScrollController {
+    offset_y: Property<f32>,
+    viewport_info: Property<ViewportInfo>,
+    panel: Option<Weak<RefCell<dyn ControlObject>>>,
+}
+
+impl ListScrollController {
+    pub fn new() -> Self {
+        ListScrollController {
+            offset_y: Property::new(0.0f32),
+            viewport_info: Property::new(ViewportInfo::default()),
+            panel: None,
+        }
+    }
+
+    ///
+    /// Returns the view with the panel in the ScrollArea and the vertical ScrollBar.
+    ///
+    pub fn create_view(
+        &mut self,
+        panel: Rc<RefCell<dyn ControlObject>>,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let offset_y_prop1 = Property::binded_two_way(&mut self.offset_y);
+        let offset_y_prop2 = Property::binded_two_way(&mut self.offset_y);
+
+        let viewport_info_prop_src = Property::new(ViewportInfo::default());
+        self.viewport_info.bind(&viewport_info_prop_src);
+        let viewport_height_prop =
+            Property::binded_c_from(&viewport_info_prop_src, |info: ViewportInfo| {
+                info.viewport_height
+            });
+        let max_offset_y_prop =
+            Property::binded_c_from(&viewport_info_prop_src, |info: ViewportInfo| {
+                (info.content_height - info.viewport_height).max(0.0f32)
+            });
+
+        self.panel = Some(Rc::downgrade(&panel));
+
+        let scroll_area = ScrollArea::builder()
+            .offset_y(offset_y_prop1)
+            .viewport_info(viewport_info_prop_src)
+            .build()
+            .to_view(
+                None,
+                ViewContext {
+                    attached_values: TypeMap::new(),
+                    children: Box::new(vec![panel]),
+                },
+            );
+
+        ui! {
+            Grid {
+                columns: 2,
+                widths: vec![(0, Length::Fill(1.0f32)), (1, Length::Auto)],
+
+                Border {
+                    @scroll_area,
+                },
+
+                ScrollBar {
+                    orientation: Orientation::Vertical,
+                    value: offset_y_prop2,
+                    max_value: max_offset_y_prop,
+                    viewport_size: viewport_height_prop,
+                },
+            }
+        }
+    }
+
+    ///
+    /// Returns the offset of the view created by create_view()
+    /// (for virtualizing panels which realize only the visible items).
+    ///
+    pub fn get_offset_y(&self) -> Property<f32> {
+        Property::binded_from(&self.offset_y)
+    }
+
+    ///
+    /// Returns the viewport height of the view created by create_view().
+    ///
+    pub fn get_viewport_height(&self) -> Property<f32> {
+        Property::binded_c_from(&self.viewport_info, |info: ViewportInfo| {
+            info.viewport_height
+        })
+    }
+
+    ///
+    /// Returns the index of the item which Up / Down / Home / End / PageUp / PageDown key
+    /// moves to from the current item (None for other keys).
+    /// The page size is based on the height of the current item view.
+    ///
+    pub fn get_navigation_index(
+        &self,
+        keycode: &Keycode,
+        current: Option<usize>,
+        current_view: Option<Rc<RefCell<dyn ControlObject>>>,
+        len: usize,
+    ) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        let index = match keycode {
+            Keycode::Up => current.map_or(0, |index| index.saturating_sub(1)),
+            Keycode::Down => current.map_or(0, |index| (index + 1).min(len - 1)),
+            Keycode::Home => 0,
+            Keycode::End => len - 1,
+            Keycode::PageUp => {
+                let page_size = self.get_page_size(current_view);
+                current.map_or(0, |index| index.saturating_sub(page_size))
+            }
+            Keycode::PageDown => {
+                let page_size = self.get_page_size(current_view);
+                current.map_or(0, |index| (index + page_size).min(len - 1))
+            }
+            _ => return None,
+        };
+        Some(index)
+    }
+
+    pub fn scroll_into_view(&mut self, view: &Rc<RefCell<dyn ControlObject>>) {
+        if let Some(rect) = self.get_view_rect(view) {
+            let viewport_height = self.viewport_info.get().viewport_height;
+            let offset_y = self.offset_y.get();
+            if rect.y < offset_y {
+                self.offset_y.set(rect.y);
+            } else if rect.y + rect.height > offset_y + viewport_height {
+                self.offset_y.set(rect.y + rect.height - viewport_height);
+            }
+        }
+    }
+
+    ///
+    /// Scrolls by the mouse wheel delta.
+    ///
+    pub fn scroll_by_wheel(&mut self, delta_y: f32) {
+        let info = self.viewport_info.get();
+        let max_offset_y = (info.content_height - info.viewport_height).max(0.0f32);
+        let offset_y =
+            (self.offset_y.get() - delta_y * SCROLL_LINE_SIZE).clamp(0.0f32, max_offset_y);
+        self.offset_y.set(offset_y);
+    }
+
+    fn get_page_size(&self, current_view: Option<Rc<RefCell<dyn ControlObject>>>) -> usize {
+        let item_height = current_view
+            .and_then(|view| self.get_view_rect(&view))
+            .map_or(0.0f32, |rect| rect.height);
+        if item_height > 0.0f32 {
+            ((self.viewport_info.get().viewport_height / item_height) as usize).max(1)
+        } else {
+            DEFAULT_PAGE_SIZE
+        }
+    }
+
+    ///
+    /// Returns the rect of the item view relative to the panel.
+    ///
+    fn get_view_rect(&self, view: &Rc<RefCell<dyn ControlObject>>) -> Option<Rect> {
+        let panel = self.panel.as_ref()?.upgrade()?;
+        let panel_rect = panel.borrow().get_rect();
+        let rect = view.borrow().get_rect();
+        Some(Rect::new(
+            rect.x - panel_rect.x,
+            rect.y - panel_rect.y,
+            rect.width,
+            rect.height,
+        ))
+    }
+}
+
+impl Default for ListScrollController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
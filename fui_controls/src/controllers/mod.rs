@@ -1,2 +1,8 @@
+mod list_scroll_controller;
+pub(crate) use list_scroll_controller::*;
+
 mod radio_controller;
 pub use radio_controller::*;
+
+mod text_editor;
+pub(crate) use text_editor::*;
@@ -0,0 +1,377 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use fui_core::*;
+
+const CARET_BLINK_TIME: Duration = Duration::from_millis(530);
+
+///
+/// Text of the control edited with the TextEditor.
+///
+pub(crate) trait EditableText {
+    fn get_text(&self) -> String;
+    fn set_text(&mut self, text: String);
+    fn set_selection(&mut self, selection: TextSelection);
+    fn is_read_only(&self) -> bool;
+
+    ///
+    /// Returns false when the text cannot be copied or cut (passwords).
+    ///
+    fn can_copy(&self) -> bool {
+        true
+    }
+
+    ///
+    /// Returns the accepted part of the text typed or pasted in place of the selection.
+    ///
+    fn accept_input(&self, text: &str, _selection: &TextSelection) -> String {
+        text.to_string()
+    }
+
+    ///
+    /// Returns the clipboard text without the characters the control does not accept.
+    ///
+    fn clean_pasted_text(&self, text: &str) -> String;
+
+    fn find_previous_word_start(&self, text: &str, index: usize) -> usize {
+        find_previous_word_start(text, index)
+    }
+
+    fn find_next_word_start(&self, text: &str, index: usize) -> usize {
+        find_next_word_start(text, index)
+    }
+}
+
+///
+/// Editing core of the text controls (TextBox, TextArea).
+///
+/// It blinks the caret, keeps the undo stack and handles the keys that work
+/// the same way in all the text controls. Selections are passed by the styles,
+/// so they can limit them to the text length the way they like.
+///
+pub(crate) struct TextEditor {
+    is_focused: bool,
+    is_caret_visible: bool,
+    caret_rect: Rect,
+    undo_stack: TextUndoStack,
+}
+
+impl TextEditor {
+    pub fn new() -> Self {
+        TextEditor {
+            is_focused: false,
+            is_caret_visible: false,
+            caret_rect: Rect::new(0.0f32, 0.0f32, 0.0f32, 0.0f32),
+            undo_stack: TextUndoStack::new(),
+        }
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    pub fn is_caret_visible(&self) -> bool {
+        self.is_focused && self.is_caret_visible
+    }
+
+    ///
+    /// Sets the caret rect (in window coordinates) after the layout,
+    /// so only the caret is drawn again when it blinks.
+    ///
+    pub fn set_caret_rect(&mut self, caret_rect: Rect) {
+        self.caret_rect = caret_rect;
+    }
+
+    ///
+    /// Handles the focus changes and the caret blink timer.
+    /// Returns false for other events.
+    ///
+    pub fn handle_focus_event(
+        &mut self,
+        control_context: &mut ControlContext,
+        event_context: &mut dyn EventContext,
+        event: &ControlEvent,
+    ) -> bool {
+        match event {
+            ControlEvent::FocusEnter => {
+                self.is_focused = true;
+                self.restart_caret_blink(control_context, event_context);
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::FocusLeave => {
+                self.is_focused = false;
+                self.is_caret_visible = false;
+                event_context.clear_timer(&control_context.get_self_rc());
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::Timer if self.is_focused => {
+                self.is_caret_visible = !self.is_caret_visible;
+                event_context.set_timer(
+                    control_context.get_self_rc(),
+                    Instant::now() + CARET_BLINK_TIME,
+                );
+                control_context.add_redraw_rect(self.caret_rect);
+            }
+
+            _ => return false,
+        }
+        true
+    }
+
+    ///
+    /// Shows the caret and starts blinking from the beginning,
+    /// so the caret does not blink while the user is typing.
+    ///
+    pub fn restart_caret_blink(
+        &mut self,
+        control_context: &mut ControlContext,
+        event_context: &mut dyn EventContext,
+    ) {
+        if self.is_focused {
+            self.is_caret_visible = true;
+            event_context.set_timer(
+                control_context.get_self_rc(),
+                Instant::now() + CARET_BLINK_TIME,
+            );
+            control_context.add_redraw_rect(self.caret_rect);
+        }
+    }
+
+    ///
+    /// Handles undo / redo, the clipboard keys, Backspace, Delete, Left, Right and Ctrl+A.
+    /// Returns false when the key is not handled.
+    ///
+    pub fn handle_key<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        control_context: &ControlContext,
+        key_code: &Keycode,
+        modifiers: &KeyModifiers,
+    ) -> bool {
+        let extend = modifiers.shift;
+        match key_code {
+            Keycode::Z if modifiers.ctrl && modifiers.shift => self.redo(data, selection),
+            Keycode::Z if modifiers.ctrl => self.undo(data, selection),
+            Keycode::Y if modifiers.ctrl => self.redo(data, selection),
+
+            Keycode::Copy => self.copy(data, selection, control_context),
+            Keycode::C | Keycode::Insert if modifiers.ctrl => {
+                self.copy(data, selection, control_context)
+            }
+
+            Keycode::Cut => self.cut(data, selection, control_context),
+            Keycode::X if modifiers.ctrl => self.cut(data, selection, control_context),
+            Keycode::Delete if modifiers.shift => self.cut(data, selection, control_context),
+
+            Keycode::Paste => self.paste(data, selection, control_context),
+            Keycode::V if modifiers.ctrl => self.paste(data, selection, control_context),
+            Keycode::Insert if modifiers.shift => self.paste(data, selection, control_context),
+
+            Keycode::Backspace => {
+                if selection.is_empty() {
+                    let start = if modifiers.ctrl {
+                        data.find_previous_word_start(&data.get_text(), selection.caret)
+                    } else {
+                        selection.caret.saturating_sub(1)
+                    };
+                    let range = selection.move_caret(start, true);
+                    self.delete(data, selection, range, TextEditKind::Deleting);
+                } else {
+                    self.delete(data, selection, selection, TextEditKind::Other);
+                }
+            }
+            Keycode::Delete => {
+                if selection.is_empty() {
+                    let text = data.get_text();
+                    let end = if modifiers.ctrl {
+                        data.find_next_word_start(&text, selection.caret)
+                    } else {
+                        (selection.caret + 1).min(get_grapheme_count(&text))
+                    };
+                    let range = selection.move_caret(end, true);
+                    self.delete(data, selection, range, TextEditKind::Deleting);
+                } else {
+                    self.delete(data, selection, selection, TextEditKind::Other);
+                }
+            }
+            Keycode::Left => {
+                let caret = if modifiers.ctrl {
+                    data.find_previous_word_start(&data.get_text(), selection.caret)
+                } else if !selection.is_empty() && !extend {
+                    // collapses the selection
+                    selection.get_start()
+                } else {
+                    selection.caret.saturating_sub(1)
+                };
+                data.set_selection(selection.move_caret(caret, extend));
+            }
+            Keycode::Right => {
+                let caret = if modifiers.ctrl {
+                    data.find_next_word_start(&data.get_text(), selection.caret)
+                } else if !selection.is_empty() && !extend {
+                    // collapses the selection
+                    selection.get_end()
+                } else {
+                    let len = get_grapheme_count(&data.get_text());
+                    (selection.caret + 1).min(len)
+                };
+                data.set_selection(selection.move_caret(caret, extend));
+            }
+            Keycode::A if modifiers.ctrl => {
+                let len = get_grapheme_count(&data.get_text());
+                data.set_selection(TextSelection::with_anchor(0, len));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    ///
+    /// Replaces the selection with the typed text (control characters are skipped).
+    /// Returns false when there is nothing to type.
+    ///
+    pub fn type_text<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        text: &str,
+    ) -> bool {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if text.is_empty() {
+            return false;
+        }
+        self.replace_selection(data, selection, &text, TextEditKind::Typing);
+        true
+    }
+
+    pub fn replace_selection<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        text: &str,
+        kind: TextEditKind,
+    ) {
+        if data.is_read_only() {
+            return;
+        }
+        let text = data.accept_input(text, &selection);
+        if text.is_empty() {
+            // everything was rejected, the selection is kept
+            return;
+        }
+        self.apply_edit(data, selection, selection, &text, kind);
+    }
+
+    fn delete<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        range: TextSelection,
+        kind: TextEditKind,
+    ) {
+        if !range.is_empty() && !data.is_read_only() {
+            self.apply_edit(data, selection, range, "", kind);
+        }
+    }
+
+    ///
+    /// Replaces the range with the text and records the edit in the undo stack.
+    ///
+    fn apply_edit<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        range: TextSelection,
+        text: &str,
+        kind: TextEditKind,
+    ) {
+        let old_text = data.get_text();
+        let (new_text, new_selection) = range.replace(&old_text, text);
+        self.undo_stack
+            .record(kind, &old_text, selection, &new_text, new_selection);
+        data.set_text(new_text);
+        data.set_selection(new_selection);
+    }
+
+    fn undo<D: EditableText>(&mut self, data: &mut D, selection: TextSelection) {
+        if data.is_read_only() {
+            return;
+        }
+        if let Some((text, selection)) = self.undo_stack.undo(&data.get_text(), selection) {
+            data.set_text(text);
+            data.set_selection(selection);
+        }
+    }
+
+    fn redo<D: EditableText>(&mut self, data: &mut D, selection: TextSelection) {
+        if data.is_read_only() {
+            return;
+        }
+        if let Some((text, selection)) = self.undo_stack.redo(&data.get_text(), selection) {
+            data.set_text(text);
+            data.set_selection(selection);
+        }
+    }
+
+    fn get_clipboard_service(
+        control_context: &ControlContext,
+    ) -> Option<Rc<RefCell<dyn ClipboardService>>> {
+        control_context
+            .get_services()
+            .and_then(|services| services.upgrade())
+            .and_then(|services| services.borrow().get_clipboard_service())
+    }
+
+    fn copy<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        control_context: &ControlContext,
+    ) {
+        if selection.is_empty() || !data.can_copy() {
+            return;
+        }
+        if let Some(clipboard_service) = Self::get_clipboard_service(control_context) {
+            clipboard_service
+                .borrow_mut()
+                .set_text(&selection.get_selected_text(&data.get_text()));
+        }
+    }
+
+    fn cut<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        control_context: &ControlContext,
+    ) {
+        if !data.can_copy() {
+            return;
+        }
+        self.copy(data, selection, control_context);
+        self.delete(data, selection, selection, TextEditKind::Other);
+    }
+
+    fn paste<D: EditableText>(
+        &mut self,
+        data: &mut D,
+        selection: TextSelection,
+        control_context: &ControlContext,
+    ) {
+        let text = Self::get_clipboard_service(control_context)
+            .and_then(|clipboard_service| clipboard_service.borrow().get_text());
+        if let Some(text) = text {
+            let text = data.clean_pasted_text(&text);
+            self.replace_selection(data, selection, &text, TextEditKind::Other);
+        }
+    }
+}
+
+impl Default for TextEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -20,7 +20,7 @@ impl Bitmap {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultBitmapStyle::new(
+                RegisteredStyle::with_default(DefaultBitmapStyle::new(
                     DefaultBitmapStyleParams::builder().build(),
                 ))
             }),
@@ -33,7 +33,7 @@ impl Bitmap {
 // Default Bitmap Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultBitmapStyleParams {}
 
 pub struct DefaultBitmapStyle {
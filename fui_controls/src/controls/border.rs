@@ -19,7 +19,7 @@ impl Border {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultBorderStyle::new(
+                RegisteredStyle::with_default(DefaultBorderStyle::new(
                     DefaultBorderStyleParams::builder().build(),
                 ))
             }),
@@ -34,7 +34,7 @@ impl Border {
 
 const BORDER_SIZE: f32 = 1.0f32;
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultBorderStyleParams {}
 
 pub struct DefaultBorderStyle {
@@ -164,7 +164,8 @@ impl Style<Border> for DefaultBorderStyle {
         let width = self.rect.width;
         let height = self.rect.height;
 
-        default_theme::border_3d_single(&mut vec, x, y, width, height, true, false, false);
+        let theme = control_context.get_theme();
+        default_theme::border_3d_single(&mut vec, &theme, x, y, width, height, true, false, false);
 
         let children = control_context.get_children();
         if let Some(ref content) = children.into_iter().next() {
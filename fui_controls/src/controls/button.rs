@@ -24,7 +24,7 @@ impl Button {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultButtonStyle::new(
+                RegisteredStyle::with_default(DefaultButtonStyle::new(
                     DefaultButtonStyleParams::builder().build(),
                 ))
             }),
@@ -37,7 +37,7 @@ impl Button {
 // Default Button Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultButtonStyleParams {}
 
 pub struct DefaultButtonStyle {
@@ -67,6 +67,11 @@ impl DefaultButtonStyle {
 
 impl Style<Button> for DefaultButtonStyle {
     fn setup(&mut self, _data: &mut Button, control_context: &mut ControlContext) {
+        control_context
+            .get_attached_values_mut()
+            .entry::<Cursor>()
+            .or_insert(CursorIcon::Hand);
+
         self.event_subscriptions
             .push(self.is_hover.dirty_watching(&control_context.get_self_rc()));
         self.event_subscriptions.push(
@@ -199,6 +204,7 @@ impl Style<Button> for DefaultButtonStyle {
 
         default_theme::button(
             &mut vec,
+            &control_context.get_theme(),
             x,
             y,
             width,
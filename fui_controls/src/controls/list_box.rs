@@ -0,0 +1,887 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use drawing::primitive::Primitive;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use fui_core::*;
+use fui_macros::ui;
+use typed_builder::TypedBuilder;
+use typemap::TypeMap;
+
+use crate::{layout::*, DataHolder, ListScrollController};
+
+const PADDING: f32 = 2.0f32;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectionMode {
+    ///
+    /// At most one item is selected.
+    ///
+    Single,
+
+    ///
+    /// Click toggles the item. Keys move the current item and Space toggles it.
+    ///
+    Multiple,
+
+    ///
+    /// Click selects one item, Ctrl+click toggles it and Shift+click selects the range.
+    ///
+    Extended,
+}
+
+//
+// ListBox.
+//
+
+///
+/// List of items with the selection.
+///
+/// Items are navigated with Up / Down / Home / End / PageUp / PageDown keys
+/// and the current item is kept in view. Double-click or Enter on the item
+/// emits the item_activated callback.
+///
+#[derive(TypedBuilder)]
+pub struct ListBox<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    #[builder(default = Box::new(Vec::<Rc<RefCell<V>>>::new()))]
+    pub items: Box<dyn ObservableCollection<Rc<RefCell<V>>>>,
+
+    ///
+    /// The current item when it is selected (or the first selected item).
+    ///
+    #[builder(default = Property::new(None))]
+    pub selected_item: Property<Option<Rc<RefCell<V>>>>,
+
+    ///
+    /// All the selected items in the order of the items collection.
+    ///
+    #[builder(default = Rc::new(RefCell::new(ObservableVec::new())))]
+    pub selected_items: Rc<RefCell<ObservableVec<Rc<RefCell<V>>>>>,
+
+    #[builder(default = SelectionMode::Single)]
+    pub selection_mode: SelectionMode,
+
+    #[builder(default = Callback::empty())]
+    pub item_activated: Callback<Rc<RefCell<V>>>,
+}
+
+impl<V> ListBox<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    pub fn to_view(
+        mut self,
+        _style: Option<Box<dyn Style<Self>>>,
+        mut context: ViewContext,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let controller_rc = Rc::new(RefCell::new(ListBoxController {
+            items: None,
+            selection: ListSelection::new(self.selection_mode),
+            selected_item: Property::binded_two_way(&mut self.selected_item),
+            selected_items: self.selected_items.clone(),
+            item_activated: self.item_activated,
+            scroll_controller: ListScrollController::new(),
+            event_subscriptions: Vec::new(),
+        }));
+
+        let controller_weak = Rc::downgrade(&controller_rc);
+        let selected_items = self.selected_items.clone();
+        let item_vms = self.items.map(move |v| {
+            let is_selected = selected_items
+                .borrow()
+                .into_iter()
+                .any(|item| Rc::ptr_eq(item, v));
+            ListBoxItemViewModel::new(v.clone(), is_selected, controller_weak.clone())
+        });
+
+        let panel = ui! {
+            Grid {
+                columns: 1,
+                &item_vms,
+            }
+        };
+
+        let content = controller_rc
+            .borrow_mut()
+            .scroll_controller
+            .create_view(panel);
+
+        {
+            let mut controller = controller_rc.borrow_mut();
+
+            let weak = Rc::downgrade(&controller_rc);
+            if let Some(subscription) = item_vms.on_changed(Box::new(move |_| {
+                if let Some(controller) = weak.upgrade() {
+                    controller.borrow_mut().on_items_changed();
+                }
+            })) {
+                controller.event_subscriptions.push(subscription);
+            }
+
+            let weak = Rc::downgrade(&controller_rc);
+            let subscription = controller.selected_item.on_changed(move |item| {
+                if let Some(controller) = weak.upgrade() {
+                    controller.borrow_mut().on_selected_item_changed(item);
+                }
+            });
+            controller.event_subscriptions.push(subscription);
+
+            controller.items = Some(item_vms);
+            controller.on_items_changed();
+            let selected_item = controller.selected_item.get();
+            controller.on_selected_item_changed(selected_item);
+        }
+
+        let controller_clone = controller_rc.clone();
+        let event_filter = EventFilterHandler::new(move |event| match event {
+            ControlEvent::KeyboardInput(key_event) if key_event.state == KeyState::Pressed => {
+                controller_clone.borrow_mut().handle_key(key_event)
+            }
+
+            ControlEvent::MouseWheel { delta, .. } => {
+                controller_clone
+                    .borrow_mut()
+                    .scroll_controller
+                    .scroll_by_wheel(delta.y);
+                true
+            }
+
+            _ => false,
+        });
+        context.attached_values.insert::<EventFilter>(event_filter);
+
+        let data_holder = DataHolder {
+            data: (self.selected_item, self.items, controller_rc),
+        };
+        data_holder.to_view(
+            None,
+            ViewContext {
+                attached_values: context.attached_values,
+                children: Box::new(vec![content as Rc<RefCell<dyn ControlObject>>]),
+            },
+        )
+    }
+}
+
+struct ListBoxController<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    items: Option<ObservableCollectionMap<Rc<RefCell<ListBoxItemViewModel<V>>>>>,
+    selection: ListSelection,
+    selected_item: Property<Option<Rc<RefCell<V>>>>,
+    selected_items: Rc<RefCell<ObservableVec<Rc<RefCell<V>>>>>,
+    item_activated: Callback<Rc<RefCell<V>>>,
+    scroll_controller: ListScrollController,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl<V> ListBoxController<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    fn get_item_vms(&self) -> Vec<Rc<RefCell<ListBoxItemViewModel<V>>>> {
+        match self.items {
+            Some(ref items) => (items as &dyn ObservableCollection<_>)
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn index_of(&self, item_vm: &Rc<RefCell<ListBoxItemViewModel<V>>>) -> Option<usize> {
+        self.get_item_vms()
+            .iter()
+            .position(|vm| Rc::ptr_eq(vm, item_vm))
+    }
+
+    fn on_items_changed(&mut self) {
+        let item_vms = self.get_item_vms();
+        let selected = item_vms
+            .iter()
+            .map(|vm| vm.borrow().is_selected.get())
+            .collect();
+        let current = item_vms.iter().position(|vm| vm.borrow().is_current.get());
+        self.selection.reset(selected, current);
+        self.update();
+    }
+
+    fn on_selected_item_changed(&mut self, item: Option<Rc<RefCell<V>>>) {
+        let item_vms = self.get_item_vms();
+        match item {
+            Some(item) => {
+                if let Some(index) = item_vms
+                    .iter()
+                    .position(|vm| Rc::ptr_eq(&vm.borrow().source_vm, &item))
+                {
+                    if !self.selection.is_selected(index) {
+                        self.selection.select_single(index);
+                        self.update();
+                        self.scroll_into_view(index);
+                    }
+                }
+            }
+            None => {
+                if !self.selection.get_selected_indices().is_empty() {
+                    self.selection.clear();
+                    self.update();
+                }
+            }
+        }
+    }
+
+    fn on_item_clicked(
+        &mut self,
+        item_vm: &Rc<RefCell<ListBoxItemViewModel<V>>>,
+        modifiers: KeyModifiers,
+    ) {
+        if let Some(index) = self.index_of(item_vm) {
+            self.selection.click(index, &modifiers);
+            self.update();
+        }
+    }
+
+    fn on_item_double_clicked(&mut self, item_vm: &Rc<RefCell<ListBoxItemViewModel<V>>>) {
+        self.item_activated.emit(item_vm.borrow().source_vm.clone());
+    }
+
+    fn handle_key(&mut self, key_event: &KeyEvent) -> bool {
+        let len = self.selection.len();
+        if len == 0 {
+            return false;
+        }
+        let current = self.selection.get_current();
+
+        let index = key_event.keycode.as_ref().and_then(|keycode| {
+            self.scroll_controller.get_navigation_index(
+                keycode,
+                current,
+                current.and_then(|index| self.get_item_view(index)),
+                len,
+            )
+        });
+        if let Some(index) = index {
+            self.selection.navigate(index, &key_event.modifiers);
+            self.update();
+            self.scroll_into_view(index);
+            return true;
+        }
+
+        match key_event.keycode {
+            Some(Keycode::Enter) | Some(Keycode::NumpadEnter) => {
+                if let Some(item_vm) = current.map(|index| self.get_item_vms()[index].clone()) {
+                    self.on_item_double_clicked(&item_vm);
+                }
+                true
+            }
+            _ => {
+                if key_event.text.as_deref() == Some(" ") {
+                    self.selection.toggle_current();
+                    self.update();
+                    return true;
+                }
+                false
+            }
+        }
+    }
+
+    ///
+    /// Updates the item view models and the selected items from the selection.
+    ///
+    fn update(&mut self) {
+        let item_vms = self.get_item_vms();
+        let current = self.selection.get_current();
+        for (index, vm) in item_vms.iter().enumerate() {
+            let mut vm = vm.borrow_mut();
+            vm.is_selected.set(self.selection.is_selected(index));
+            vm.is_current.set(current == Some(index));
+        }
+
+        let selected: Vec<Rc<RefCell<V>>> = self
+            .selection
+            .get_selected_indices()
+            .into_iter()
+            .map(|index| item_vms[index].borrow().source_vm.clone())
+            .collect();
+
+        {
+            let mut selected_items = self.selected_items.borrow_mut();
+            selected_items.remove_filter(|item| !selected.iter().any(|s| Rc::ptr_eq(s, item)));
+            for item in selected.iter() {
+                if !(&*selected_items).into_iter().any(|s| Rc::ptr_eq(s, item)) {
+                    selected_items.push(item.clone());
+                }
+            }
+        }
+
+        let selected_item = match current {
+            Some(index) if self.selection.is_selected(index) => {
+                Some(item_vms[index].borrow().source_vm.clone())
+            }
+            _ => selected.first().cloned(),
+        };
+        let is_changed = match (self.selected_item.get(), &selected_item) {
+            (Some(old_item), Some(new_item)) => !Rc::ptr_eq(&old_item, new_item),
+            (old_item, new_item) => old_item.is_some() != new_item.is_some(),
+        };
+        if is_changed {
+            self.selected_item.set(selected_item);
+        }
+    }
+
+    fn get_item_view(&self, index: usize) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        self.get_item_vms()
+            .into_iter()
+            .nth(index)?
+            .borrow()
+            .view
+            .as_ref()?
+            .upgrade()
+    }
+
+    fn scroll_into_view(&mut self, index: usize) {
+        if let Some(view) = self.get_item_view(index) {
+            self.scroll_controller.scroll_into_view(&view);
+        }
+    }
+}
+
+///
+/// Selection state of the list kept by item indices.
+///
+struct ListSelection {
+    mode: SelectionMode,
+    selected: Vec<bool>,
+    anchor: usize,
+    current: Option<usize>,
+}
+
+impl ListSelection {
+    fn new(mode: SelectionMode) -> Self {
+        ListSelection {
+            mode,
+            selected: Vec::new(),
+            anchor: 0,
+            current: None,
+        }
+    }
+
+    fn reset(&mut self, selected: Vec<bool>, current: Option<usize>) {
+        self.selected = selected;
+        self.current = current.filter(|&index| index < self.selected.len());
+        if self.anchor >= self.selected.len() {
+            self.anchor = self.current.unwrap_or(0);
+        }
+        if self.mode == SelectionMode::Single {
+            let first = self.selected.iter().position(|&s| s);
+            for (index, s) in self.selected.iter_mut().enumerate() {
+                *s = Some(index) == first;
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    fn get_current(&self) -> Option<usize> {
+        self.current
+    }
+
+    fn is_selected(&self, index: usize) -> bool {
+        index < self.selected.len() && self.selected[index]
+    }
+
+    fn get_selected_indices(&self) -> Vec<usize> {
+        (0..self.selected.len())
+            .filter(|&index| self.selected[index])
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        for s in self.selected.iter_mut() {
+            *s = false;
+        }
+    }
+
+    fn select_single(&mut self, index: usize) {
+        self.clear();
+        self.selected[index] = true;
+        self.anchor = index;
+        self.current = Some(index);
+    }
+
+    fn toggle(&mut self, index: usize) {
+        self.selected[index] = !self.selected[index];
+        self.anchor = index;
+        self.current = Some(index);
+    }
+
+    fn select_range(&mut self, index: usize, keep_selection: bool) {
+        if !keep_selection {
+            self.clear();
+        }
+        let (start, end) = if self.anchor <= index {
+            (self.anchor, index)
+        } else {
+            (index, self.anchor)
+        };
+        for s in self.selected[start..=end].iter_mut() {
+            *s = true;
+        }
+        self.current = Some(index);
+    }
+
+    ///
+    /// Changes the selection after click on the item.
+    ///
+    fn click(&mut self, index: usize, modifiers: &KeyModifiers) {
+        match self.mode {
+            SelectionMode::Single => self.select_single(index),
+            SelectionMode::Multiple => self.toggle(index),
+            SelectionMode::Extended => {
+                if modifiers.shift {
+                    self.select_range(index, modifiers.ctrl)
+                } else if modifiers.ctrl {
+                    self.toggle(index)
+                } else {
+                    self.select_single(index)
+                }
+            }
+        }
+    }
+
+    ///
+    /// Changes the selection after moving to the item with keys.
+    ///
+    fn navigate(&mut self, index: usize, modifiers: &KeyModifiers) {
+        match self.mode {
+            SelectionMode::Single => self.select_single(index),
+            SelectionMode::Multiple => self.current = Some(index),
+            SelectionMode::Extended => {
+                if modifiers.shift {
+                    self.select_range(index, modifiers.ctrl)
+                } else if modifiers.ctrl {
+                    self.current = Some(index)
+                } else {
+                    self.select_single(index)
+                }
+            }
+        }
+    }
+
+    fn toggle_current(&mut self) {
+        if let Some(index) = self.current {
+            match self.mode {
+                SelectionMode::Single => self.select_single(index),
+                SelectionMode::Multiple | SelectionMode::Extended => self.toggle(index),
+            }
+        }
+    }
+}
+
+struct ListBoxItemViewModel<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    pub source_vm: Rc<RefCell<V>>,
+    pub is_selected: Property<bool>,
+    pub is_current: Property<bool>,
+    pub clicked: Callback<KeyModifiers>,
+    pub double_clicked: Callback<()>,
+    pub view: Option<Weak<RefCell<dyn ControlObject>>>,
+}
+
+impl<V> ListBoxItemViewModel<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    pub fn new(
+        source_vm: Rc<RefCell<V>>,
+        is_selected: bool,
+        controller: Weak<RefCell<ListBoxController<V>>>,
+    ) -> Rc<RefCell<Self>> {
+        let vm_rc = Rc::new(RefCell::new(ListBoxItemViewModel {
+            source_vm,
+            is_selected: Property::new(is_selected),
+            is_current: Property::new(false),
+            clicked: Callback::empty(),
+            double_clicked: Callback::empty(),
+            view: None,
+        }));
+
+        {
+            let mut vm = vm_rc.borrow_mut();
+
+            let weak_vm = Rc::downgrade(&vm_rc);
+            let controller_clone = controller.clone();
+            vm.clicked.set(move |modifiers| {
+                if let (Some(vm), Some(controller)) =
+                    (weak_vm.upgrade(), controller_clone.upgrade())
+                {
+                    controller.borrow_mut().on_item_clicked(&vm, modifiers);
+                }
+            });
+
+            let weak_vm = Rc::downgrade(&vm_rc);
+            vm.double_clicked.set(move |_| {
+                if let (Some(vm), Some(controller)) = (weak_vm.upgrade(), controller.upgrade()) {
+                    controller.borrow_mut().on_item_double_clicked(&vm);
+                }
+            });
+        }
+
+        vm_rc
+    }
+}
+
+impl<V> ViewModel for ListBoxItemViewModel<V>
+where
+    V: ViewModel + PartialEq + 'static,
+{
+    fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+        let mut vm = view_model.borrow_mut();
+        let content = vm.source_vm.create_view();
+        let view: Rc<RefCell<dyn ControlObject>> = ui! {
+            ListBoxItem {
+                is_selected: &vm.is_selected,
+                is_current: &vm.is_current,
+                clicked: vm.clicked.clone(),
+                double_clicked: vm.double_clicked.clone(),
+                @content,
+            }
+        };
+        vm.view = Some(Rc::downgrade(&view));
+        view
+    }
+}
+
+//
+// ListBoxItem.
+//
+
+///
+/// Container of one ListBox item. Draws the selection and the current item mark.
+///
+#[derive(TypedBuilder)]
+pub struct ListBoxItem {
+    #[builder(default = Property::new(false))]
+    pub is_selected: Property<bool>,
+
+    #[builder(default = Property::new(false))]
+    pub is_current: Property<bool>,
+
+    ///
+    /// Emitted on tap with the modifier keys pressed.
+    ///
+    #[builder(default = Callback::empty())]
+    pub clicked: Callback<KeyModifiers>,
+
+    #[builder(default = Callback::empty())]
+    pub double_clicked: Callback<()>,
+}
+
+impl ListBoxItem {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultListBoxItemStyle::new(
+                    DefaultListBoxItemStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+}
+
+//
+// Default ListBoxItem Style
+//
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultListBoxItemStyleParams {}
+
+pub struct DefaultListBoxItemStyle {
+    rect: Rect,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl DefaultListBoxItemStyle {
+    pub fn new(_params: DefaultListBoxItemStyleParams) -> Self {
+        DefaultListBoxItemStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            event_subscriptions: Vec::new(),
+        }
+    }
+}
+
+impl Style<ListBoxItem> for DefaultListBoxItemStyle {
+    fn setup(&mut self, data: &mut ListBoxItem, control_context: &mut ControlContext) {
+        self.event_subscriptions.push(
+            data.is_selected
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_current
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut ListBoxItem,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        match event {
+            ControlEvent::TapDown { .. } => {
+                data.clicked.emit(event_context.get_key_modifiers());
+            }
+
+            ControlEvent::DoubleTap { .. } => {
+                data.double_clicked.emit(());
+            }
+
+            _ => (),
+        }
+    }
+
+    fn measure(
+        &mut self,
+        _data: &mut ListBoxItem,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let children = control_context.get_children();
+
+        let content_size = if let Some(ref content) = children.into_iter().next() {
+            let child_size = Size::new(
+                if size.width.is_finite() {
+                    0f32.max(size.width - PADDING * 2.0f32)
+                } else {
+                    size.width
+                },
+                if size.height.is_finite() {
+                    0f32.max(size.height - PADDING * 2.0f32)
+                } else {
+                    size.height
+                },
+            );
+            content.borrow_mut().measure(drawing_context, child_size);
+            let rect = content.borrow().get_rect();
+            Size::new(rect.width, rect.height)
+        } else {
+            Size::new(0f32, 0f32)
+        };
+
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            content_size.width + PADDING * 2.0f32,
+            content_size.height + PADDING * 2.0f32,
+        )
+    }
+
+    fn set_rect(
+        &mut self,
+        _data: &mut ListBoxItem,
+        control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+
+        let content_rect = Rect::new(
+            rect.x + PADDING,
+            rect.y + PADDING,
+            rect.width - PADDING * 2.0f32,
+            rect.height - PADDING * 2.0f32,
+        );
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().set_rect(content_rect);
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &ListBoxItem,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            HitTestResult::Current
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn to_primitives(
+        &self,
+        data: &ListBoxItem,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let x = self.rect.x;
+        let y = self.rect.y;
+        let width = self.rect.width;
+        let height = self.rect.height;
+
+        let theme = control_context.get_theme();
+
+        if data.is_selected.get() {
+            vec.push(Primitive::Rectangle {
+                color: theme.get_color("SelectionBackground"),
+                rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
+            });
+        }
+
+        if data.is_current.get() {
+            let color = theme.get_color("Foreground");
+            for &(rx, ry, rw, rh) in [
+                (x, y, width, 1.0f32),
+                (x, y + height - 1.0f32, width, 1.0f32),
+                (x, y, 1.0f32, height),
+                (x + width - 1.0f32, y, 1.0f32, height),
+            ]
+            .iter()
+            {
+                vec.push(Primitive::Rectangle {
+                    color,
+                    rect: PixelRect::new(PixelPoint::new(rx, ry), PixelSize::new(rw, rh)),
+                });
+            }
+        }
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            let (mut vec2, mut overlay2) = content.borrow_mut().to_primitives(drawing_context);
+            vec.append(&mut vec2);
+            overlay.append(&mut overlay2);
+        }
+
+        (vec, overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controls::*;
+
+    #[derive(PartialEq)]
+    struct TestItem {
+        name: &'static str,
+    }
+
+    impl ViewModel for TestItem {
+        fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+            ui! {
+                Text { text: view_model.borrow().name }
+            }
+        }
+    }
+
+    fn modifiers(shift: bool, ctrl: bool) -> KeyModifiers {
+        KeyModifiers {
+            shift,
+            ctrl,
+            ..KeyModifiers::default()
+        }
+    }
+
+    #[test]
+    fn test_extended_selection() {
+        let mut selection = ListSelection::new(SelectionMode::Extended);
+        selection.reset(vec![false; 6], None);
+
+        selection.click(1, &modifiers(false, false));
+        selection.click(3, &modifiers(true, false));
+        assert_eq!(selection.get_selected_indices(), vec![1, 2, 3]);
+
+        selection.click(5, &modifiers(false, true));
+        assert_eq!(selection.get_selected_indices(), vec![1, 2, 3, 5]);
+
+        // range from the last ctrl+clicked item
+        selection.click(4, &modifiers(true, false));
+        assert_eq!(selection.get_selected_indices(), vec![4, 5]);
+
+        selection.navigate(0, &modifiers(false, true));
+        assert_eq!(selection.get_current(), Some(0));
+        assert_eq!(selection.get_selected_indices(), vec![4, 5]);
+        selection.toggle_current();
+        assert_eq!(selection.get_selected_indices(), vec![0, 4, 5]);
+    }
+
+    #[test]
+    fn test_single_and_multiple_selection() {
+        let mut single = ListSelection::new(SelectionMode::Single);
+        single.reset(vec![true, true, false], None);
+        assert_eq!(single.get_selected_indices(), vec![0]);
+        single.click(2, &modifiers(true, true));
+        assert_eq!(single.get_selected_indices(), vec![2]);
+
+        let mut multiple = ListSelection::new(SelectionMode::Multiple);
+        multiple.reset(vec![false; 3], None);
+        multiple.click(0, &modifiers(false, false));
+        multiple.click(2, &modifiers(false, false));
+        multiple.click(0, &modifiers(false, false));
+        assert_eq!(multiple.get_selected_indices(), vec![2]);
+        multiple.navigate(1, &modifiers(false, false));
+        assert_eq!(multiple.get_selected_indices(), vec![2]);
+    }
+
+    #[test]
+    fn test_equal_items_are_selected_by_identity() {
+        let first = Rc::new(RefCell::new(TestItem { name: "same" }));
+        let second = Rc::new(RefCell::new(TestItem { name: "same" }));
+        let selected_items = Rc::new(RefCell::new(ObservableVec::new()));
+        selected_items.borrow_mut().push(second.clone());
+        let mut selected_item = Property::new(None);
+
+        let _list_box = ListBox::builder()
+            .items(Box::new(vec![first.clone(), second.clone()]))
+            .selected_item(Property::binded_two_way(&mut selected_item))
+            .selected_items(selected_items.clone())
+            .selection_mode(SelectionMode::Multiple)
+            .build()
+            .to_view(
+                None,
+                ViewContext {
+                    attached_values: TypeMap::new(),
+                    children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                },
+            );
+        CallbackExecutor::execute_all_in_queue();
+
+        let selected: Vec<_> = (&*selected_items.borrow()).into_iter().cloned().collect();
+        assert_eq!(selected.len(), 1);
+        assert!(Rc::ptr_eq(&selected[0], &second));
+        assert!(selected_item
+            .get()
+            .is_some_and(|item| Rc::ptr_eq(&item, &second)));
+    }
+}
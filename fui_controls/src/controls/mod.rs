@@ -10,12 +10,21 @@ pub use self::button::*;
 mod drop_down;
 pub use self::drop_down::*;
 
+mod list_box;
+pub use self::list_box::*;
+
+mod numeric_up_down;
+pub use self::numeric_up_down::*;
+
 mod popup;
 pub use self::popup::*;
 
 mod progress_bar;
 pub use self::progress_bar::*;
 
+mod rich_text;
+pub use self::rich_text::*;
+
 mod scroll_area;
 pub use self::scroll_area::*;
 
@@ -25,14 +34,26 @@ pub use self::scroll_bar::*;
 mod scroll_viewer;
 pub use self::scroll_viewer::*;
 
+mod slider;
+pub use self::slider::*;
+
 mod tab_control;
 pub use self::tab_control::*;
 
 mod text;
 pub use self::text::*;
 
+mod text_area;
+pub use self::text_area::*;
+
 mod text_box;
 pub use self::text_box::*;
 
 mod toggle_button;
 pub use self::toggle_button::*;
+
+mod tree_view;
+pub use self::tree_view::*;
+
+mod virtual_list;
+pub use self::virtual_list::*;
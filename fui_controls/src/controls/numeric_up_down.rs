@@ -0,0 +1,554 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use fui_core::*;
+use fui_macros::ui;
+use typed_builder::TypedBuilder;
+use typemap::TypeMap;
+
+use crate::controls::*;
+use crate::{layout::*, DataHolder};
+
+// pointer distance (in pixels) of one step when dragging
+const DRAG_STEP_SIZE: f32 = 10.0f32;
+const PAGE_STEPS: f64 = 10.0f64;
+
+///
+/// Numeric input composed from the TextBox and the up / down buttons.
+///
+/// The value can be typed or changed with the buttons, Up / Down / PageUp / PageDown keys,
+/// the mouse wheel and by dragging vertically over the control.
+/// Text which is not a valid number (or is out of range) does not change the value
+/// and it is reverted when the control loses focus.
+///
+#[derive(TypedBuilder)]
+pub struct NumericUpDown {
+    pub value: Property<f64>,
+
+    #[builder(default = 0.0f64)]
+    pub min_value: f64,
+
+    #[builder(default = 100.0f64)]
+    pub max_value: f64,
+
+    #[builder(default = 1.0f64)]
+    pub step: f64,
+
+    ///
+    /// The value is rounded to this number of decimal places.
+    ///
+    #[builder(default = 0usize)]
+    pub decimal_places: usize,
+
+    ///
+    /// Text shown before the number (like currency).
+    ///
+    #[builder(default)]
+    pub prefix: String,
+
+    ///
+    /// Text shown after the number (like units or %).
+    ///
+    #[builder(default)]
+    pub suffix: String,
+}
+
+impl NumericUpDown {
+    pub fn to_view(
+        mut self,
+        _style: Option<Box<dyn Style<Self>>>,
+        mut context: ViewContext,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let format = Rc::new(NumberFormat {
+            min_value: self.min_value,
+            max_value: self.max_value,
+            step: self.step,
+            decimal_places: self.decimal_places,
+            prefix: self.prefix,
+            suffix: self.suffix,
+        });
+
+        let value_rc = Rc::new(RefCell::new(Property::binded_two_way(&mut self.value)));
+        let text_rc = Rc::new(RefCell::new(Property::<String>::new(
+            format.format(self.value.get()),
+        )));
+        let text_prop2 = Property::binded_two_way(&mut text_rc.borrow_mut());
+
+        // text is reformatted only when it doesn't already represent the value,
+        // so it is not changed while the user is typing
+        let value_subscription = {
+            let text_weak = Rc::downgrade(&text_rc);
+            let format = format.clone();
+            value_rc.borrow().on_changed(move |value| {
+                if let Some(text_rc) = text_weak.upgrade() {
+                    let mut text = text_rc.borrow_mut();
+                    let text_value = format.parse(&text.get()).map(|v| format.coerce(v));
+                    if text_value != Some(value) {
+                        text.set(format.format(value));
+                    }
+                }
+            })
+        };
+        let text_subscription = {
+            let value_weak = Rc::downgrade(&value_rc);
+            let format = format.clone();
+            text_rc.borrow().on_changed(move |text| {
+                if let (Some(value_rc), Some(value)) = (value_weak.upgrade(), format.parse(&text)) {
+                    if value >= format.min_value && value <= format.max_value {
+                        value_rc.borrow_mut().set(format.coerce(value));
+                    }
+                }
+            })
+        };
+
+        let spin = {
+            let value_weak = Rc::downgrade(&value_rc);
+            let text_weak = Rc::downgrade(&text_rc);
+            let format = format.clone();
+            Rc::new(move |steps: f64| {
+                if let (Some(value_rc), Some(text_rc)) = (value_weak.upgrade(), text_weak.upgrade())
+                {
+                    let value = format.add_steps(value_rc.borrow().get(), steps);
+                    value_rc.borrow_mut().set(value);
+                    text_rc.borrow_mut().set(format.format(value));
+                }
+            })
+        };
+        let revert = {
+            let value_weak = Rc::downgrade(&value_rc);
+            let text_weak = Rc::downgrade(&text_rc);
+            let format = format.clone();
+            move || {
+                if let (Some(value_rc), Some(text_rc)) = (value_weak.upgrade(), text_weak.upgrade())
+                {
+                    let value = value_rc.borrow().get();
+                    text_rc.borrow_mut().set(format.format(value));
+                }
+            }
+        };
+
+        let mut up_callback = Callback::empty();
+        let spin_clone = spin.clone();
+        up_callback.set(move |_| spin_clone(1.0f64));
+        let mut down_callback = Callback::empty();
+        let spin_clone = spin.clone();
+        down_callback.set(move |_| spin_clone(-1.0f64));
+
+        let input_filter = {
+            let format = format.clone();
+            InputFilter::chars(move |c| format.is_char_allowed(c))
+        };
+
+        // keys, wheel and drags of the parts are handled here
+        let is_dragging = Cell::new(false);
+        let drag_distance = Cell::new(0.0f32);
+        let event_filter = EventFilterHandler::new(move |event| match event {
+            ControlEvent::KeyboardInput(key_event) if key_event.state == KeyState::Pressed => {
+                match key_event.keycode {
+                    Some(Keycode::Up) => spin(1.0f64),
+                    Some(Keycode::Down) => spin(-1.0f64),
+                    Some(Keycode::PageUp) => spin(PAGE_STEPS),
+                    Some(Keycode::PageDown) => spin(-PAGE_STEPS),
+                    Some(Keycode::Enter) | Some(Keycode::NumpadEnter) => revert(),
+                    _ => return false,
+                }
+                true
+            }
+
+            ControlEvent::MouseWheel { delta, .. } => {
+                if delta.y != 0.0f32 {
+                    spin(delta.y.signum() as f64);
+                }
+                true
+            }
+
+            ControlEvent::DragStart { .. } => {
+                is_dragging.set(true);
+                drag_distance.set(0.0f32);
+                true
+            }
+
+            ControlEvent::DragMove { delta, .. } => {
+                // moving up increases the value
+                let distance = drag_distance.get() - delta.y;
+                let steps = (distance / DRAG_STEP_SIZE).trunc();
+                if steps != 0.0f32 {
+                    spin(steps as f64);
+                }
+                drag_distance.set(distance - steps * DRAG_STEP_SIZE);
+                true
+            }
+
+            ControlEvent::DragEnd { .. } => {
+                is_dragging.set(false);
+                true
+            }
+
+            // the text is not selected while dragging the value
+            ControlEvent::TapMove { .. } => is_dragging.get(),
+
+            ControlEvent::FocusLeave => {
+                revert();
+                false
+            }
+
+            _ => false,
+        });
+
+        let content = ui! {
+            Grid {
+                columns: 2,
+                widths: vec![(0, Length::Fill(1.0f32)), (1, Length::Auto)],
+
+                TextBox {
+                    text: text_prop2,
+                    input_filter: Some(input_filter),
+                },
+
+                Grid {
+                    columns: 1,
+
+                    Button {
+                        clicked: up_callback,
+                        Text { text: "+" },
+                    },
+
+                    Button {
+                        clicked: down_callback,
+                        Text { text: "-" },
+                    },
+                },
+            }
+        };
+
+        context.attached_values.insert::<EventFilter>(event_filter);
+
+        let data_holder = DataHolder {
+            data: (
+                self.value,
+                value_rc,
+                text_rc,
+                value_subscription,
+                text_subscription,
+            ),
+        };
+        data_holder.to_view(
+            None,
+            ViewContext {
+                attached_values: context.attached_values,
+                children: Box::new(vec![content as Rc<RefCell<dyn ControlObject>>]),
+            },
+        )
+    }
+}
+
+struct NumberFormat {
+    min_value: f64,
+    max_value: f64,
+    step: f64,
+    decimal_places: usize,
+    prefix: String,
+    suffix: String,
+}
+
+impl NumberFormat {
+    fn format(&self, value: f64) -> String {
+        format!(
+            "{}{:.*}{}",
+            self.prefix, self.decimal_places, value, self.suffix
+        )
+    }
+
+    ///
+    /// Parses the number. Prefix and suffix are optional.
+    ///
+    fn parse(&self, text: &str) -> Option<f64> {
+        let mut text = text.trim();
+        if !self.prefix.is_empty() && text.starts_with(&self.prefix) {
+            text = &text[self.prefix.len()..];
+        }
+        if !self.suffix.is_empty() && text.ends_with(&self.suffix) {
+            text = &text[..text.len() - self.suffix.len()];
+        }
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+    }
+
+    ///
+    /// Limits the value to the range and rounds it to the decimal places.
+    ///
+    fn coerce(&self, value: f64) -> f64 {
+        let factor = 10.0f64.powi(self.decimal_places as i32);
+        let value = (value * factor).round() / factor;
+        value.max(self.min_value).min(self.max_value)
+    }
+
+    fn add_steps(&self, value: f64, steps: f64) -> f64 {
+        self.coerce(value + self.step * steps)
+    }
+
+    fn is_char_allowed(&self, c: char) -> bool {
+        c.is_ascii_digit()
+            || c == '.' && self.decimal_places > 0
+            || c == '-' && self.min_value < 0.0f64
+            || self.prefix.contains(c)
+            || self.suffix.contains(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drawing::primitive::Primitive;
+
+    use super::*;
+    use crate::test_helpers::*;
+
+    ///
+    /// NumericUpDown (0 - 100) in the Border with the TextBox below it (on another layer).
+    ///
+    struct Fixture {
+        value: Property<f64>,
+        layers: Vec<Rc<RefCell<dyn ControlObject>>>,
+        processor: EventProcessor,
+        drawing_context: TestDrawingContext,
+    }
+
+    impl Fixture {
+        fn new(value: f64) -> Self {
+            let mut value = Property::new(value);
+            let numeric_up_down = NumericUpDown::builder()
+                .value(Property::binded_two_way(&mut value))
+                .build()
+                .to_view(
+                    None,
+                    ViewContext {
+                        attached_values: TypeMap::new(),
+                        children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                    },
+                );
+            let layer: Rc<RefCell<dyn ControlObject>> = ui! {
+                Border {
+                    @numeric_up_down,
+                }
+            };
+            let other: Rc<RefCell<dyn ControlObject>> = ui! {
+                TextBox {
+                    text: Property::new(String::new()),
+                }
+            };
+
+            let mut fixture = Fixture {
+                value,
+                layers: vec![layer, other],
+                processor: EventProcessor::new(),
+                drawing_context: TestDrawingContext::new(),
+            };
+            fixture.layout();
+            fixture
+        }
+
+        fn layout(&mut self) {
+            for (index, layer) in self.layers.iter().enumerate() {
+                let mut layer = layer.borrow_mut();
+                layer.measure(&mut self.drawing_context, Size::new(800.0, 600.0));
+                layer.set_rect(Rect::new(0.0, index as f32 * 40.0, 200.0, 40.0));
+            }
+        }
+
+        fn handle(&mut self, event: InputEvent) {
+            self.processor
+                .handle_event(&self.layers, &mut self.drawing_context, &event);
+            CallbackExecutor::execute_all_in_queue();
+            self.layout();
+        }
+
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.handle(InputEvent::CursorMoved {
+                position: Point::new(x, y),
+            });
+        }
+
+        fn press_left(&mut self, state: ElementState) {
+            self.handle(InputEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+            });
+        }
+
+        fn tap(&mut self, x: f32, y: f32) {
+            self.move_to(x, y);
+            self.press_left(ElementState::Pressed);
+            self.press_left(ElementState::Released);
+        }
+
+        ///
+        /// Focuses the TextBox of the NumericUpDown and selects all its text.
+        ///
+        fn select_all(&mut self) {
+            self.tap(50.0, 20.0);
+            self.handle(key_pressed_with_modifiers(
+                Keycode::A,
+                KeyModifiers {
+                    ctrl: true,
+                    ..KeyModifiers::default()
+                },
+            ));
+        }
+
+        fn type_text(&mut self, text: &str) {
+            self.handle(InputEvent::KeyboardInput(KeyEvent {
+                state: KeyState::Pressed,
+                keycode: None,
+                text: Some(text.to_string()),
+                modifiers: KeyModifiers::default(),
+            }));
+        }
+
+        fn press(&mut self, keycode: Keycode) {
+            self.handle(key_pressed(keycode));
+        }
+
+        fn wheel(&mut self, delta_y: f32) {
+            self.handle(InputEvent::MouseWheel {
+                delta: Point::new(0.0, delta_y),
+            });
+        }
+
+        ///
+        /// Returns the text drawn by the TextBox (skipping the labels of the buttons).
+        ///
+        fn get_text(&mut self) -> String {
+            let (primitives, _) = self.layers[0]
+                .borrow()
+                .to_primitives(&mut self.drawing_context);
+            primitives
+                .into_iter()
+                .filter_map(|primitive| match primitive {
+                    Primitive::Text { text, .. } if text != "+" && text != "-" => Some(text),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_invalid_text_is_reverted() {
+        let mut fixture = Fixture::new(50.0);
+
+        fixture.select_all();
+        fixture.type_text("75");
+        assert_eq!(fixture.value.get(), 75.0);
+        assert_eq!(fixture.get_text(), "75");
+
+        // empty text is not a number
+        fixture.select_all();
+        fixture.press(Keycode::Backspace);
+        assert_eq!(fixture.get_text(), "");
+        assert_eq!(fixture.value.get(), 75.0);
+        fixture.press(Keycode::Enter);
+        assert_eq!(fixture.get_text(), "75");
+
+        // out of range
+        fixture.select_all();
+        fixture.type_text("500");
+        assert_eq!(fixture.get_text(), "500");
+        assert_eq!(fixture.value.get(), 75.0);
+        fixture.tap(50.0, 60.0);
+        assert_eq!(fixture.get_text(), "75");
+        assert_eq!(fixture.value.get(), 75.0);
+    }
+
+    #[test]
+    fn test_keys_step_and_clamp_value() {
+        let mut fixture = Fixture::new(50.0);
+        fixture.tap(50.0, 20.0);
+
+        fixture.press(Keycode::Up);
+        assert_eq!(fixture.value.get(), 51.0);
+        fixture.press(Keycode::Down);
+        fixture.press(Keycode::Down);
+        assert_eq!(fixture.value.get(), 49.0);
+        fixture.press(Keycode::PageUp);
+        assert_eq!(fixture.value.get(), 59.0);
+        assert_eq!(fixture.get_text(), "59");
+
+        for _ in 0..5 {
+            fixture.press(Keycode::PageUp);
+        }
+        assert_eq!(fixture.value.get(), 100.0);
+        fixture.press(Keycode::Up);
+        assert_eq!(fixture.value.get(), 100.0);
+
+        for _ in 0..11 {
+            fixture.press(Keycode::PageDown);
+        }
+        assert_eq!(fixture.value.get(), 0.0);
+        fixture.press(Keycode::Down);
+        assert_eq!(fixture.value.get(), 0.0);
+        assert_eq!(fixture.get_text(), "0");
+    }
+
+    #[test]
+    fn test_wheel_steps_and_clamps_value() {
+        let mut fixture = Fixture::new(98.0);
+        fixture.move_to(50.0, 20.0);
+
+        // one step for each wheel event
+        fixture.wheel(1.0);
+        assert_eq!(fixture.value.get(), 99.0);
+        fixture.wheel(-3.0);
+        assert_eq!(fixture.value.get(), 98.0);
+        fixture.wheel(2.0);
+        fixture.wheel(2.0);
+        fixture.wheel(2.0);
+        assert_eq!(fixture.value.get(), 100.0);
+        assert_eq!(fixture.get_text(), "100");
+    }
+
+    #[test]
+    fn test_dragging_steps_and_clamps_value() {
+        let mut fixture = Fixture::new(50.0);
+        fixture.move_to(50.0, 20.0);
+        fixture.press_left(ElementState::Pressed);
+
+        // less than one step
+        fixture.move_to(50.0, 15.0);
+        assert_eq!(fixture.value.get(), 50.0);
+
+        // moving up increases the value
+        fixture.move_to(50.0, -15.0);
+        assert_eq!(fixture.value.get(), 53.0);
+        fixture.move_to(50.0, 35.0);
+        assert_eq!(fixture.value.get(), 49.0);
+
+        fixture.move_to(50.0, -1000.0);
+        assert_eq!(fixture.value.get(), 100.0);
+        fixture.press_left(ElementState::Released);
+        assert_eq!(fixture.get_text(), "100");
+    }
+
+    #[test]
+    fn test_number_format() {
+        let format = NumberFormat {
+            min_value: -10.0f64,
+            max_value: 10.0f64,
+            step: 0.25f64,
+            decimal_places: 2,
+            prefix: "$".to_string(),
+            suffix: " %".to_string(),
+        };
+
+        assert_eq!(format.format(1.5f64), "$1.50 %");
+        assert_eq!(format.parse("$1.50 %"), Some(1.5f64));
+        assert_eq!(format.parse(" -2.5 "), Some(-2.5f64));
+        assert_eq!(format.parse("$1.5.0 %"), None);
+        assert_eq!(format.parse(""), None);
+
+        assert_eq!(format.coerce(1.23456f64), 1.23f64);
+        assert_eq!(format.coerce(25.0f64), 10.0f64);
+        assert_eq!(format.add_steps(9.9f64, 1.0f64), 10.0f64);
+        assert_eq!(format.add_steps(0.0f64, -2.0f64), -0.5f64);
+    }
+}
@@ -36,7 +36,7 @@ impl Popup {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultPopupStyle::new(
+                RegisteredStyle::with_default(DefaultPopupStyle::new(
                     DefaultPopupStyleParams::builder().build(),
                 ))
             }),
@@ -49,7 +49,7 @@ impl Popup {
 // Default Popup Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultPopupStyleParams {}
 
 pub struct DefaultPopupStyle {
@@ -32,7 +32,7 @@ impl ProgressBar {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultProgressBarStyle::new(
+                RegisteredStyle::with_default(DefaultProgressBarStyle::new(
                     DefaultProgressBarStyleParams::builder().build(),
                 ))
             }),
@@ -50,7 +50,7 @@ const END_MARGIN: f32 = 1.0f32;
 const SIDE_MARGIN: f32 = 1.0f32;
 const MIN_SIZE: f32 = 22.0f32;
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultProgressBarStyleParams {}
 
 pub struct DefaultProgressBarStyle {
@@ -152,7 +152,7 @@ impl Style<ProgressBar> for DefaultProgressBarStyle {
     fn to_primitives(
         &self,
         data: &ProgressBar,
-        _control_context: &ControlContext,
+        control_context: &ControlContext,
         _drawing_context: &mut dyn DrawingContext,
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let x = self.rect.x;
@@ -170,12 +170,13 @@ impl Style<ProgressBar> for DefaultProgressBarStyle {
             / (data.max_value.get() - data.min_value.get()))
         .round();
 
-        let foreground = [1.0, 1.0, 0.0, 0.7];
-        let background = [0.1, 0.5, 0.0, 0.2];
+        let theme = control_context.get_theme();
+        let foreground = theme.get_color("ProgressForeground");
+        let background = theme.get_color("TrackBackground");
 
         let mut vec = Vec::new();
 
-        default_theme::border_3d_single(&mut vec, x, y, width, height, true, false, false);
+        default_theme::border_3d_single(&mut vec, &theme, x, y, width, height, true, false, false);
 
         match data.orientation {
             Orientation::Horizontal => {
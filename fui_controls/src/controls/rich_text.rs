@@ -0,0 +1,569 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use euclid::Length;
+use fui_core::*;
+use typed_builder::TypedBuilder;
+
+///
+/// Part of the rich text with its own formatting.
+///
+/// Values not set are inherited from the control
+/// (FontFamily, FontSize, FontWeight and Foreground attached values).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    pub font_family: Option<String>,
+    pub font_size: Option<f32>,
+    pub font_weight: Option<u16>,
+    pub color: Option<Color>,
+    pub is_underlined: bool,
+
+    ///
+    /// Target passed to the link_clicked callback when the run is clicked.
+    ///
+    pub link: Option<String>,
+}
+
+impl TextRun {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        TextRun {
+            text: text.into(),
+            font_family: None,
+            font_size: None,
+            font_weight: None,
+            color: None,
+            is_underlined: false,
+            link: None,
+        }
+    }
+
+    pub fn font_family<S: Into<String>>(mut self, font_family: S) -> Self {
+        self.font_family = Some(font_family.into());
+        self
+    }
+
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    pub fn font_weight(mut self, font_weight: u16) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.font_weight(700)
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn underlined(mut self) -> Self {
+        self.is_underlined = true;
+        self
+    }
+
+    pub fn link<S: Into<String>>(mut self, target: S) -> Self {
+        self.link = Some(target.into());
+        self
+    }
+}
+
+///
+/// Content of the RichText.
+///
+/// Example:
+///
+/// Inlines::new()
+///     .text("Field ")
+///     .bold("Name")
+///     .text(" is required. ")
+///     .link("Details", "help/name")
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inlines {
+    pub runs: Vec<TextRun>,
+}
+
+impl Inlines {
+    pub fn new() -> Self {
+        Inlines { runs: Vec::new() }
+    }
+
+    pub fn run(mut self, run: TextRun) -> Self {
+        self.runs.push(run);
+        self
+    }
+
+    pub fn text<S: Into<String>>(self, text: S) -> Self {
+        self.run(TextRun::new(text))
+    }
+
+    pub fn bold<S: Into<String>>(self, text: S) -> Self {
+        self.run(TextRun::new(text).bold())
+    }
+
+    pub fn colored<S: Into<String>>(self, text: S, color: Color) -> Self {
+        self.run(TextRun::new(text).color(color))
+    }
+
+    pub fn underlined<S: Into<String>>(self, text: S) -> Self {
+        self.run(TextRun::new(text).underlined())
+    }
+
+    pub fn link<S: Into<String>, T: Into<String>>(self, text: S, target: T) -> Self {
+        self.run(TextRun::new(text).link(target))
+    }
+
+    pub fn line_break(self) -> Self {
+        self.text("\n")
+    }
+
+    pub fn get_text(&self) -> String {
+        self.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+}
+
+impl Default for Inlines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntoProperty for Inlines {}
+
+impl From<&str> for Inlines {
+    fn from(text: &str) -> Self {
+        Inlines::new().text(text)
+    }
+}
+
+impl From<String> for Inlines {
+    fn from(text: String) -> Self {
+        Inlines::new().text(text)
+    }
+}
+
+#[derive(TypedBuilder)]
+pub struct RichText {
+    pub inlines: Property<Inlines>,
+
+    #[builder(default = TextWrapping::Word)]
+    pub wrapping: TextWrapping,
+
+    #[builder(default = TextAlignment::Left)]
+    pub alignment: TextAlignment,
+
+    #[builder(default = TextTrimming::None)]
+    pub trimming: TextTrimming,
+
+    ///
+    /// Line height as a multiple of the font height.
+    ///
+    #[builder(default = 1.0f32)]
+    pub line_height: f32,
+
+    #[builder(default)]
+    pub max_lines: Option<usize>,
+
+    ///
+    /// Called with the link target of the clicked run.
+    ///
+    #[builder(default = Callback::empty())]
+    pub link_clicked: Callback<String>,
+}
+
+impl RichText {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultRichTextStyle::new(
+                    DefaultRichTextStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+}
+
+//
+// Default RichText Style
+//
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultRichTextStyleParams {
+    ///
+    /// Inherited Foreground (or Foreground color of the theme)
+    /// is used when not set.
+    ///
+    #[builder(default, setter(strip_option))]
+    pub color: Option<Color>,
+
+    ///
+    /// Color of the links without color set.
+    /// Link color of the theme is used when not set.
+    ///
+    #[builder(default, setter(strip_option))]
+    pub link_color: Option<Color>,
+}
+
+struct ResolvedRun {
+    // runs using fallback fonts share the index of the inline run
+    inline_index: usize,
+    font_name: String,
+    font_size: u8,
+    color: Option<Color>,
+    is_underlined: bool,
+    link: Option<String>,
+}
+
+pub struct DefaultRichTextStyle {
+    rect: Rect,
+    params: DefaultRichTextStyleParams,
+    event_subscriptions: Vec<EventSubscription>,
+    runs: Vec<ResolvedRun>,
+    layout: RichTextLayout,
+    pressed_run: Option<usize>,
+}
+
+impl DefaultRichTextStyle {
+    pub fn new(params: DefaultRichTextStyleParams) -> Self {
+        DefaultRichTextStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            params,
+            event_subscriptions: Vec::new(),
+            runs: Vec::new(),
+            layout: RichTextLayout::new(Vec::new(), 0.0f32, TextLayoutParams::default()),
+            pressed_run: None,
+        }
+    }
+
+    fn get_layout_origin(&self) -> Point {
+        // text block is centered vertically
+        Point::new(
+            self.rect.x,
+            self.rect.y + (self.rect.height - self.layout.get_height()) / 2.0f32,
+        )
+    }
+
+    fn get_link_run_at(&self, position: Point) -> Option<usize> {
+        let origin = self.get_layout_origin();
+        self.layout
+            .get_run_at(
+                self.rect.width,
+                Point::new(position.x - origin.x, position.y - origin.y),
+            )
+            .filter(|run_index| self.runs[*run_index].link.is_some())
+    }
+}
+
+impl Style<RichText> for DefaultRichTextStyle {
+    fn setup(&mut self, data: &mut RichText, control_context: &mut ControlContext) {
+        self.event_subscriptions
+            .push(data.inlines.dirty_watching(&control_context.get_self_rc()));
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut RichText,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        match event {
+            ControlEvent::TapDown { ref position } => {
+                self.pressed_run = self.get_link_run_at(*position);
+            }
+
+            ControlEvent::TapUp { ref position } => {
+                if let Some(pressed_run) = self.pressed_run.take() {
+                    let released_run = self.get_link_run_at(*position);
+                    let inline_index = |run: usize| self.runs[run].inline_index;
+                    if released_run.map(inline_index) == Some(inline_index(pressed_run)) {
+                        if let Some(ref link) = self.runs[pressed_run].link {
+                            data.link_clicked.emit(link.clone());
+                        }
+                    }
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut RichText,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let theme = control_context.get_theme();
+        let inherited_properties = control_context.get_inherited_properties();
+        let default_family = inherited_properties
+            .font_family
+            .unwrap_or_else(|| theme.get_font("Default"));
+        let default_size = inherited_properties
+            .font_size
+            .unwrap_or_else(|| theme.get_size("FontSize"));
+        let default_weight = inherited_properties.font_weight.unwrap_or(400);
+
+        let resources = drawing_context.get_resources();
+        let mut runs = Vec::new();
+        let mut measured_runs = Vec::new();
+        for (inline_index, run) in data.inlines.get().runs.into_iter().enumerate() {
+            let family = run.font_family.unwrap_or_else(|| default_family.clone());
+            let font_size = run.font_size.unwrap_or(default_size) as u8;
+
+            // characters missing in the font are displayed with the fallback fonts,
+            // so the run can be split into more runs
+            let text = run.text;
+            let font_runs = resources
+                .resolve_font_runs(
+                    &family,
+                    run.font_weight.unwrap_or(default_weight),
+                    FontStyle::Normal,
+                    &text,
+                )
+                .unwrap_or_else(|_| {
+                    vec![FontRun {
+                        font_name: family.clone(),
+                        text,
+                    }]
+                });
+
+            for font_run in font_runs {
+                let (char_widths, font_height) = resources
+                    .get_font_dimensions_each_char(&font_run.font_name, font_size, &font_run.text)
+                    .unwrap_or((Vec::new(), 0));
+                measured_runs.push(MeasuredRun {
+                    text: font_run.text,
+                    char_widths: char_widths.into_iter().map(|width| width as f32).collect(),
+                    font_height: font_height as f32,
+                });
+
+                runs.push(ResolvedRun {
+                    inline_index,
+                    font_name: font_run.font_name,
+                    font_size,
+                    color: run.color,
+                    is_underlined: run.is_underlined || run.link.is_some(),
+                    link: run.link.clone(),
+                });
+            }
+        }
+
+        // ellipsis is drawn with the font of the last run on the line,
+        // the width of the last run's font is close enough
+        let (ellipsis_width, _) = runs
+            .last()
+            .and_then(|run| {
+                resources
+                    .get_font_dimensions(&run.font_name, run.font_size, ELLIPSIS)
+                    .ok()
+            })
+            .unwrap_or((0, 0));
+
+        self.runs = runs;
+        self.layout = RichTextLayout::new(
+            measured_runs,
+            ellipsis_width as f32,
+            TextLayoutParams {
+                wrapping: data.wrapping,
+                alignment: data.alignment,
+                trimming: data.trimming,
+                line_height: data.line_height,
+                max_lines: data.max_lines,
+            },
+        );
+        self.pressed_run = None;
+
+        // wrapping happens against available width
+        self.layout.layout(size.width);
+
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            self.layout.get_width(),
+            self.layout.get_height(),
+        )
+    }
+
+    fn set_rect(&mut self, _data: &mut RichText, _control_context: &mut ControlContext, rect: Rect) {
+        // the final width can be smaller than the available one during measure
+        self.layout.layout(rect.width);
+        self.rect = rect;
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &RichText,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            HitTestResult::Current
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn get_cursor_icon(
+        &self,
+        _data: &RichText,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> Option<CursorIcon> {
+        self.get_link_run_at(point).map(|_| CursorIcon::Hand)
+    }
+
+    fn to_primitives(
+        &self,
+        _data: &RichText,
+        control_context: &ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+
+        let theme = control_context.get_theme();
+        let default_color = self.params.color.unwrap_or_else(|| {
+            control_context
+                .get_inherited_properties()
+                .foreground
+                .unwrap_or_else(|| theme.get_color("Foreground"))
+        });
+        let link_color = self
+            .params
+            .link_color
+            .unwrap_or_else(|| theme.get_color("Link"));
+
+        let origin = self.get_layout_origin();
+        let clipping_rect = PixelRect::new(
+            PixelPoint::new(self.rect.x, self.rect.y),
+            PixelSize::new(self.rect.width, self.rect.height),
+        );
+
+        for segment in self.layout.get_segments(self.rect.width) {
+            let run = &self.runs[segment.run_index];
+            let color = run.color.unwrap_or_else(|| {
+                if run.link.is_some() {
+                    link_color
+                } else {
+                    default_color
+                }
+            });
+            let x = origin.x + segment.x;
+            let y = origin.y + segment.y;
+
+            vec.push(Primitive::Text {
+                resource_key: run.font_name.clone(),
+                color,
+                position: PixelPoint::new(x, y),
+                clipping_rect,
+                size: Length::new(run.font_size as f32),
+                text: segment.text,
+            });
+
+            if run.is_underlined {
+                let thickness = (run.font_size as f32 / 16.0f32).max(1.0f32).round();
+                vec.push(Primitive::Rectangle {
+                    color,
+                    rect: PixelRect::new(
+                        PixelPoint::new(x, y + segment.height - thickness),
+                        PixelSize::new(segment.width, thickness),
+                    ),
+                });
+            }
+        }
+
+        (vec, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typemap::TypeMap;
+
+    use super::*;
+    use crate::test_helpers::*;
+
+    #[test]
+    fn test_link_shows_hand_cursor_and_is_clicked() {
+        let clicked_links = Rc::new(RefCell::new(Vec::new()));
+        let clicked_links_clone = clicked_links.clone();
+        let mut link_clicked = Callback::empty();
+        link_clicked.set(move |link| clicked_links_clone.borrow_mut().push(link));
+
+        let rich_text: Rc<RefCell<dyn ControlObject>> = RichText::builder()
+            .inlines(Property::new(
+                Inlines::new().text("Go to ").link("site", "home"),
+            ))
+            .link_clicked(link_clicked)
+            .build()
+            .to_view(
+                None,
+                ViewContext {
+                    attached_values: TypeMap::new(),
+                    children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                },
+            );
+
+        let mut drawing_context = TestDrawingContext::new();
+        rich_text
+            .borrow_mut()
+            .measure(&mut drawing_context, Size::new(800.0, 600.0));
+        rich_text
+            .borrow_mut()
+            .set_rect(Rect::new(0.0, 0.0, 200.0, 20.0));
+
+        let layers = vec![rich_text];
+        let mut processor = EventProcessor::new();
+        let mut handle = |event: InputEvent| {
+            processor.handle_event(&layers, &mut drawing_context, &event);
+            CallbackExecutor::execute_all_in_queue();
+            processor.get_cursor_icon()
+        };
+
+        // "Go to " is 60 pixels wide, the link is next to it
+        let cursor_icon = handle(InputEvent::CursorMoved {
+            position: Point::new(30.0, 10.0),
+        });
+        assert_eq!(cursor_icon, CursorIcon::Default);
+        let cursor_icon = handle(InputEvent::CursorMoved {
+            position: Point::new(80.0, 10.0),
+        });
+        assert_eq!(cursor_icon, CursorIcon::Hand);
+
+        handle(InputEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: MouseButton::Left,
+        });
+        handle(InputEvent::MouseInput {
+            state: ElementState::Released,
+            button: MouseButton::Left,
+        });
+        assert_eq!(*clicked_links.borrow(), vec!["home".to_string()]);
+    }
+}
@@ -36,7 +36,7 @@ impl ScrollArea {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultScrollAreaStyle::new(
+                RegisteredStyle::with_default(DefaultScrollAreaStyle::new(
                     DefaultScrollAreaStyleParams::builder().build(),
                 ))
             }),
@@ -49,7 +49,7 @@ impl ScrollArea {
 // Default ScrollArea Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultScrollAreaStyleParams {}
 
 pub struct DefaultScrollAreaStyle {
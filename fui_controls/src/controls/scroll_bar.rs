@@ -35,7 +35,7 @@ impl ScrollBar {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultScrollBarStyle::new(
+                RegisteredStyle::with_default(DefaultScrollBarStyle::new(
                     DefaultScrollBarStyleParams::builder().build(),
                 ))
             }),
@@ -54,7 +54,7 @@ const SIDE_MARGIN: f32 = 1.0f32;
 const MIN_THUMB_SIZE: f32 = 20.0f32;
 const MIN_SIZE: f32 = MIN_THUMB_SIZE * 2.0f32;
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultScrollBarStyleParams {}
 
 pub struct DefaultScrollBarStyle {
@@ -253,7 +253,7 @@ impl Style<ScrollBar> for DefaultScrollBarStyle {
     fn to_primitives(
         &self,
         data: &ScrollBar,
-        _control_context: &ControlContext,
+        control_context: &ControlContext,
         _drawing_context: &mut dyn DrawingContext,
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let x = self.rect.x;
@@ -266,7 +266,8 @@ impl Style<ScrollBar> for DefaultScrollBarStyle {
             Orientation::Vertical => height - START_MARGIN - END_MARGIN,
         };
 
-        let background = [0.1, 0.5, 0.0, 0.2];
+        let theme = control_context.get_theme();
+        let background = theme.get_color("TrackBackground");
 
         let mut vec = Vec::new();
         if self.thumb_pos_px > 0.0f32 {
@@ -288,6 +289,7 @@ impl Style<ScrollBar> for DefaultScrollBarStyle {
         match data.orientation {
             Orientation::Horizontal => default_theme::button(
                 &mut vec,
+                &theme,
                 x + self.thumb_pos_px + START_MARGIN,
                 y + SIDE_MARGIN,
                 self.thumb_size_px,
@@ -298,6 +300,7 @@ impl Style<ScrollBar> for DefaultScrollBarStyle {
             ),
             Orientation::Vertical => default_theme::button(
                 &mut vec,
+                &theme,
                 x + SIDE_MARGIN,
                 y + self.thumb_pos_px + START_MARGIN,
                 width - SIDE_MARGIN - SIDE_MARGIN,
@@ -336,7 +339,7 @@ impl Style<ScrollBar> for DefaultScrollBarStyle {
             });
         }
 
-        default_theme::border_3d_single(&mut vec, x, y, width, height, true, false, false);
+        default_theme::border_3d_single(&mut vec, &theme, x, y, width, height, true, false, false);
 
         (vec, Vec::new())
     }
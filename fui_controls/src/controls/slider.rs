@@ -0,0 +1,673 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use euclid::Length;
+use fui_core::*;
+use typed_builder::TypedBuilder;
+
+use crate::style::*;
+
+#[derive(TypedBuilder)]
+pub struct Slider {
+    #[builder(default = Orientation::Horizontal)]
+    pub orientation: Orientation,
+
+    #[builder(default = Property::new(0.0f32))]
+    pub min_value: Property<f32>,
+
+    #[builder(default = Property::new(1.0f32))]
+    pub max_value: Property<f32>,
+
+    ///
+    /// Value of the thumb. In the range mode it is the end of the range.
+    ///
+    #[builder(default = Property::new(0.0f32))]
+    pub value: Property<f32>,
+
+    ///
+    /// Shows the second thumb, so the slider selects the range
+    /// from lower_value to value.
+    ///
+    #[builder(default = false)]
+    pub is_range: bool,
+
+    ///
+    /// Start of the range (used only in the range mode).
+    ///
+    #[builder(default = Property::new(0.0f32))]
+    pub lower_value: Property<f32>,
+
+    ///
+    /// Distance between the tick marks (no tick marks when not set).
+    ///
+    #[builder(default)]
+    pub tick_frequency: Option<f32>,
+
+    ///
+    /// Values are rounded to the nearest tick.
+    ///
+    #[builder(default = false)]
+    pub is_snap_to_ticks: bool,
+
+    ///
+    /// Change of arrow keys and mouse wheel.
+    /// Defaults to the tick frequency or to 1/100 of the range.
+    ///
+    #[builder(default)]
+    pub small_change: Option<f32>,
+
+    ///
+    /// Change of PageUp / PageDown keys and clicks on the track.
+    /// Defaults to 1/10 of the range.
+    ///
+    #[builder(default)]
+    pub large_change: Option<f32>,
+
+    ///
+    /// Click on the track moves the thumb to the clicked point
+    /// (instead of changing the value by large_change).
+    ///
+    #[builder(default = false)]
+    pub is_move_to_point_enabled: bool,
+
+    ///
+    /// Shows the value in the tool tip while the thumb is dragged.
+    ///
+    #[builder(default = false)]
+    pub is_value_tool_tip_enabled: bool,
+
+    #[builder(default = 0usize)]
+    pub value_tool_tip_decimal_places: usize,
+}
+
+impl Slider {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultSliderStyle::new(
+                    DefaultSliderStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+
+    fn get_small_change(&self) -> f32 {
+        self.at_least_one_tick(
+            self.small_change
+                .or(self.tick_frequency)
+                .unwrap_or_else(|| (self.max_value.get() - self.min_value.get()) / 100.0f32),
+        )
+    }
+
+    fn get_large_change(&self) -> f32 {
+        self.at_least_one_tick(
+            self.large_change
+                .unwrap_or_else(|| (self.max_value.get() - self.min_value.get()) / 10.0f32),
+        )
+    }
+
+    ///
+    /// When snapping, a smaller change would be rounded back to the same tick.
+    ///
+    fn at_least_one_tick(&self, change: f32) -> f32 {
+        match self.tick_frequency {
+            Some(tick_frequency) if self.is_snap_to_ticks => change.max(tick_frequency),
+            _ => change,
+        }
+    }
+
+    ///
+    /// Limits the value to the range and snaps it to the ticks.
+    ///
+    fn coerce_value(&self, value: f32) -> f32 {
+        coerce_value(
+            value,
+            self.min_value.get(),
+            self.max_value.get(),
+            if self.is_snap_to_ticks {
+                self.tick_frequency
+            } else {
+                None
+            },
+        )
+    }
+
+    fn get_thumb_value(&self, thumb: Thumb) -> f32 {
+        match thumb {
+            Thumb::Lower => self.lower_value.get(),
+            Thumb::Upper => self.value.get(),
+        }
+    }
+
+    ///
+    /// Sets the value of the thumb. Thumbs of the range cannot pass each other.
+    ///
+    fn set_thumb_value(&mut self, thumb: Thumb, value: f32) {
+        let value = self.coerce_value(value);
+        match thumb {
+            Thumb::Lower if self.is_range => {
+                self.lower_value.set(value.min(self.value.get()));
+            }
+            Thumb::Upper if self.is_range => {
+                self.value.set(value.max(self.lower_value.get()));
+            }
+            _ => self.value.set(value),
+        }
+    }
+}
+
+fn coerce_value(value: f32, min_value: f32, max_value: f32, tick_frequency: Option<f32>) -> f32 {
+    let value = match tick_frequency {
+        Some(tick_frequency) if tick_frequency > 0.0f32 => {
+            min_value + ((value - min_value) / tick_frequency).round() * tick_frequency
+        }
+        _ => value,
+    };
+    value.max(min_value).min(max_value)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Thumb {
+    Lower,
+    Upper,
+}
+
+//
+// Default Slider Style
+//
+
+const THUMB_LENGTH: f32 = 12.0f32;
+const THUMB_THICKNESS: f32 = 22.0f32;
+const TRACK_THICKNESS: f32 = 4.0f32;
+const TICK_SIZE: f32 = 5.0f32;
+const MIN_LENGTH: f32 = 60.0f32;
+const TOOL_TIP_PADDING: f32 = 4.0f32;
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultSliderStyleParams {}
+
+pub struct DefaultSliderStyle {
+    rect: Rect,
+    is_hover: bool,
+    is_focused: bool,
+
+    pressed_thumb: Option<Thumb>,
+    pressed_offset: f32,
+
+    // thumb changed by the keyboard in the range mode
+    active_thumb: Thumb,
+
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl DefaultSliderStyle {
+    pub fn new(_params: DefaultSliderStyleParams) -> Self {
+        DefaultSliderStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            is_hover: false,
+            is_focused: false,
+            pressed_thumb: None,
+            pressed_offset: 0.0f32,
+            active_thumb: Thumb::Upper,
+            event_subscriptions: Vec::new(),
+        }
+    }
+
+    fn get_track_length(&self, data: &Slider) -> f32 {
+        let length = match data.orientation {
+            Orientation::Horizontal => self.rect.width,
+            Orientation::Vertical => self.rect.height,
+        };
+        (length - THUMB_LENGTH).max(0.0f32)
+    }
+
+    ///
+    /// Returns the position along the track (the minimum is on the left
+    /// or at the bottom).
+    ///
+    fn get_pos(&self, data: &Slider, point: &Point) -> f32 {
+        match data.orientation {
+            Orientation::Horizontal => point.x - self.rect.x,
+            Orientation::Vertical => self.rect.y + self.rect.height - point.y,
+        }
+    }
+
+    ///
+    /// Returns the position of the thumb center along the track.
+    ///
+    fn get_thumb_pos(&self, data: &Slider, value: f32) -> f32 {
+        let min_value = data.min_value.get();
+        let max_value = data.max_value.get();
+        let ratio = if max_value > min_value {
+            ((value - min_value) / (max_value - min_value)).clamp(0.0f32, 1.0f32)
+        } else {
+            0.0f32
+        };
+        THUMB_LENGTH / 2.0f32 + ratio * self.get_track_length(data)
+    }
+
+    fn get_value_at_pos(&self, data: &Slider, pos: f32) -> f32 {
+        let track_length = self.get_track_length(data);
+        if track_length <= 0.0f32 {
+            return data.min_value.get();
+        }
+        let ratio = (pos - THUMB_LENGTH / 2.0f32) / track_length;
+        data.min_value.get() + ratio * (data.max_value.get() - data.min_value.get())
+    }
+
+    ///
+    /// Returns the thumb closest to the position.
+    ///
+    fn get_nearest_thumb(&self, data: &Slider, pos: f32) -> Thumb {
+        if !data.is_range {
+            return Thumb::Upper;
+        }
+        let lower_pos = self.get_thumb_pos(data, data.lower_value.get());
+        let upper_pos = self.get_thumb_pos(data, data.value.get());
+        if (pos - lower_pos).abs() < (pos - upper_pos).abs()
+            || (pos - lower_pos).abs() == (pos - upper_pos).abs() && pos < lower_pos
+        {
+            Thumb::Lower
+        } else {
+            Thumb::Upper
+        }
+    }
+
+    ///
+    /// Returns the rect of the thumb with the center at the position along the track.
+    ///
+    fn get_thumb_rect(&self, data: &Slider, pos: f32) -> Rect {
+        match data.orientation {
+            Orientation::Horizontal => Rect::new(
+                self.rect.x + pos - THUMB_LENGTH / 2.0f32,
+                self.rect.y,
+                THUMB_LENGTH,
+                THUMB_THICKNESS,
+            ),
+            Orientation::Vertical => Rect::new(
+                self.rect.x,
+                self.rect.y + self.rect.height - pos - THUMB_LENGTH / 2.0f32,
+                THUMB_THICKNESS,
+                THUMB_LENGTH,
+            ),
+        }
+    }
+
+    fn handle_key(&mut self, data: &mut Slider, key_code: &Keycode) {
+        let thumb = self.active_thumb;
+        let value = data.get_thumb_value(thumb);
+        let new_value = match key_code {
+            Keycode::Right | Keycode::Up => value + data.get_small_change(),
+            Keycode::Left | Keycode::Down => value - data.get_small_change(),
+            Keycode::PageUp => value + data.get_large_change(),
+            Keycode::PageDown => value - data.get_large_change(),
+            Keycode::Home => data.min_value.get(),
+            Keycode::End => data.max_value.get(),
+            _ => return,
+        };
+        data.set_thumb_value(thumb, new_value);
+    }
+}
+
+impl Style<Slider> for DefaultSliderStyle {
+    fn setup(&mut self, data: &mut Slider, control_context: &mut ControlContext) {
+        self.event_subscriptions.push(
+            data.min_value
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.max_value
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions
+            .push(data.value.dirty_watching(&control_context.get_self_rc()));
+        self.event_subscriptions.push(
+            data.lower_value
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut Slider,
+        control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        match event {
+            ControlEvent::TapDown { ref position } => {
+                let pos = self.get_pos(data, position);
+                let thumb = self.get_nearest_thumb(data, pos);
+                let thumb_pos = self.get_thumb_pos(data, data.get_thumb_value(thumb));
+                self.active_thumb = thumb;
+
+                if (pos - thumb_pos).abs() <= THUMB_LENGTH / 2.0f32 {
+                    self.pressed_thumb = Some(thumb);
+                    self.pressed_offset = pos - thumb_pos;
+                } else if data.is_move_to_point_enabled {
+                    // the thumb jumps to the pointer and can be dragged then
+                    data.set_thumb_value(thumb, self.get_value_at_pos(data, pos));
+                    self.pressed_thumb = Some(thumb);
+                    self.pressed_offset = 0.0f32;
+                } else {
+                    let value = data.get_thumb_value(thumb);
+                    let change = if pos > thumb_pos {
+                        data.get_large_change()
+                    } else {
+                        -data.get_large_change()
+                    };
+                    data.set_thumb_value(thumb, value + change);
+                }
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::TapMove { ref position } => {
+                if let Some(thumb) = self.pressed_thumb {
+                    let pos = self.get_pos(data, position) - self.pressed_offset;
+                    data.set_thumb_value(thumb, self.get_value_at_pos(data, pos));
+                }
+            }
+
+            ControlEvent::TapUp { .. } => {
+                self.pressed_thumb = None;
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::MouseWheel { ref delta, .. } if delta.y != 0.0f32 => {
+                let thumb = self.active_thumb;
+                let value = data.get_thumb_value(thumb);
+                data.set_thumb_value(thumb, value + data.get_small_change() * delta.y.signum());
+            }
+
+            ControlEvent::KeyboardInput(ref key_event) if key_event.state == KeyState::Pressed => {
+                if let Some(ref key_code) = key_event.keycode {
+                    self.handle_key(data, key_code);
+                }
+            }
+
+            ControlEvent::HoverEnter => {
+                self.is_hover = true;
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::HoverLeave => {
+                self.is_hover = false;
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::FocusEnter => {
+                self.is_focused = true;
+                control_context.set_is_dirty(true);
+            }
+
+            ControlEvent::FocusLeave => {
+                self.is_focused = false;
+                control_context.set_is_dirty(true);
+            }
+
+            _ => (),
+        }
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut Slider,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let thickness = if data.tick_frequency.is_some() {
+            THUMB_THICKNESS + TICK_SIZE + 2.0f32
+        } else {
+            THUMB_THICKNESS
+        };
+        match data.orientation {
+            Orientation::Horizontal => {
+                let space = if size.width.is_infinite() {
+                    MIN_LENGTH
+                } else {
+                    size.width
+                };
+                self.rect = Rect::new(0.0f32, 0.0f32, MIN_LENGTH.max(space), thickness);
+            }
+            Orientation::Vertical => {
+                let space = if size.height.is_infinite() {
+                    MIN_LENGTH
+                } else {
+                    size.height
+                };
+                self.rect = Rect::new(0.0f32, 0.0f32, thickness, MIN_LENGTH.max(space));
+            }
+        }
+    }
+
+    fn set_rect(&mut self, _data: &mut Slider, _control_context: &mut ControlContext, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &Slider,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            HitTestResult::Current
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn to_primitives(
+        &self,
+        data: &Slider,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let theme = control_context.get_theme();
+        let track_length = self.get_track_length(data);
+
+        // track (with the selected part filled)
+        let (fill_start, fill_end) = if data.is_range {
+            (
+                self.get_thumb_pos(data, data.lower_value.get()),
+                self.get_thumb_pos(data, data.value.get()),
+            )
+        } else {
+            (
+                THUMB_LENGTH / 2.0f32,
+                self.get_thumb_pos(data, data.value.get()),
+            )
+        };
+        let track_offset = (THUMB_THICKNESS - TRACK_THICKNESS) / 2.0f32;
+        let along_rect =
+            |start: f32, length: f32, offset: f32, thickness: f32| match data.orientation {
+                Orientation::Horizontal => PixelRect::new(
+                    PixelPoint::new(self.rect.x + start, self.rect.y + offset),
+                    PixelSize::new(length, thickness),
+                ),
+                Orientation::Vertical => PixelRect::new(
+                    PixelPoint::new(
+                        self.rect.x + offset,
+                        self.rect.y + self.rect.height - start - length,
+                    ),
+                    PixelSize::new(thickness, length),
+                ),
+            };
+        vec.push(Primitive::Rectangle {
+            color: theme.get_color("TrackBackground"),
+            rect: along_rect(
+                THUMB_LENGTH / 2.0f32,
+                track_length,
+                track_offset,
+                TRACK_THICKNESS,
+            ),
+        });
+        if fill_end > fill_start {
+            vec.push(Primitive::Rectangle {
+                color: theme.get_color("ProgressForeground"),
+                rect: along_rect(
+                    fill_start,
+                    fill_end - fill_start,
+                    track_offset,
+                    TRACK_THICKNESS,
+                ),
+            });
+        }
+
+        // tick marks
+        if let Some(tick_frequency) = data.tick_frequency {
+            let min_value = data.min_value.get();
+            let max_value = data.max_value.get();
+            if tick_frequency > 0.0f32 && max_value > min_value {
+                let tick_count = ((max_value - min_value) / tick_frequency).floor() as usize;
+                for i in 0..=tick_count {
+                    let pos = self.get_thumb_pos(data, min_value + i as f32 * tick_frequency);
+                    vec.push(Primitive::Rectangle {
+                        color: theme.get_color("Foreground"),
+                        rect: along_rect(pos - 0.5f32, 1.0f32, THUMB_THICKNESS + 2.0f32, TICK_SIZE),
+                    });
+                }
+            }
+        }
+
+        // thumbs
+        let thumbs: &[Thumb] = if data.is_range {
+            &[Thumb::Lower, Thumb::Upper]
+        } else {
+            &[Thumb::Upper]
+        };
+        for thumb in thumbs {
+            let rect =
+                self.get_thumb_rect(data, self.get_thumb_pos(data, data.get_thumb_value(*thumb)));
+            default_theme::button(
+                &mut vec,
+                &theme,
+                rect.x,
+                rect.y,
+                rect.width,
+                rect.height,
+                self.pressed_thumb == Some(*thumb),
+                self.is_hover,
+                self.is_focused && self.active_thumb == *thumb,
+            );
+        }
+
+        // value tool tip of the dragged thumb
+        if let (Some(thumb), true) = (self.pressed_thumb, data.is_value_tool_tip_enabled) {
+            let font_name = theme.get_font("ToolTip");
+            let font_size = theme.get_size("ToolTipFontSize") as u8;
+            let text = format!(
+                "{:.*}",
+                data.value_tool_tip_decimal_places,
+                data.get_thumb_value(thumb)
+            );
+            let (text_width, text_height) = drawing_context
+                .get_resources()
+                .get_font_dimensions(&font_name, font_size, &text)
+                .unwrap_or((0, 0));
+            let width = text_width as f32 + TOOL_TIP_PADDING * 2.0f32;
+            let height = text_height as f32 + TOOL_TIP_PADDING * 2.0f32;
+
+            // above the horizontal slider, on the right of the vertical one
+            let thumb_rect =
+                self.get_thumb_rect(data, self.get_thumb_pos(data, data.get_thumb_value(thumb)));
+            let (x, y) = match data.orientation {
+                Orientation::Horizontal => (
+                    thumb_rect.x + (thumb_rect.width - width) / 2.0f32,
+                    thumb_rect.y - height - 2.0f32,
+                ),
+                Orientation::Vertical => (
+                    thumb_rect.x + thumb_rect.width + 2.0f32,
+                    thumb_rect.y + (thumb_rect.height - height) / 2.0f32,
+                ),
+            };
+
+            overlay.push(Primitive::Rectangle {
+                color: theme.get_color("ToolTipBackground"),
+                rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
+            });
+            overlay.push(Primitive::Text {
+                resource_key: font_name,
+                color: theme.get_color("ToolTipForeground"),
+                position: PixelPoint::new(x + TOOL_TIP_PADDING, y + TOOL_TIP_PADDING),
+                clipping_rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
+                size: Length::new(font_size as f32),
+                text,
+            });
+        }
+
+        (vec, overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coerce_value() {
+        assert_eq!(coerce_value(0.37f32, 0.0f32, 1.0f32, None), 0.37f32);
+        assert_eq!(coerce_value(1.5f32, 0.0f32, 1.0f32, None), 1.0f32);
+        assert_eq!(coerce_value(-3.0f32, -2.0f32, 2.0f32, None), -2.0f32);
+
+        // ticks are counted from the minimum
+        assert_eq!(
+            coerce_value(13.0f32, 5.0f32, 50.0f32, Some(10.0f32)),
+            15.0f32
+        );
+        assert_eq!(
+            coerce_value(49.0f32, 5.0f32, 50.0f32, Some(10.0f32)),
+            45.0f32
+        );
+        assert_eq!(
+            coerce_value(51.0f32, 5.0f32, 50.0f32, Some(10.0f32)),
+            50.0f32
+        );
+    }
+
+    #[test]
+    fn test_keys_move_at_least_one_tick_when_snapping() {
+        let mut slider = Slider::builder()
+            .max_value(Property::new(100.0f32))
+            .tick_frequency(Some(10.0f32))
+            .is_snap_to_ticks(true)
+            .small_change(Some(1.0f32))
+            .large_change(Some(5.0f32))
+            .build();
+        let mut style = DefaultSliderStyle::new(DefaultSliderStyleParams::builder().build());
+
+        style.handle_key(&mut slider, &Keycode::Right);
+        assert_eq!(slider.value.get(), 10.0f32);
+        style.handle_key(&mut slider, &Keycode::PageUp);
+        assert_eq!(slider.value.get(), 20.0f32);
+        style.handle_key(&mut slider, &Keycode::Left);
+        assert_eq!(slider.value.get(), 10.0f32);
+
+        // without snapping the small change is used as it is
+        slider.is_snap_to_ticks = false;
+        style.handle_key(&mut slider, &Keycode::Right);
+        assert_eq!(slider.value.get(), 11.0f32);
+    }
+}
@@ -10,6 +10,24 @@ use typed_builder::TypedBuilder;
 #[derive(TypedBuilder)]
 pub struct Text {
     pub text: Property<String>,
+
+    #[builder(default = TextWrapping::NoWrap)]
+    pub wrapping: TextWrapping,
+
+    #[builder(default = TextAlignment::Center)]
+    pub alignment: TextAlignment,
+
+    #[builder(default = TextTrimming::None)]
+    pub trimming: TextTrimming,
+
+    ///
+    /// Line height as a multiple of the font height.
+    ///
+    #[builder(default = 1.0f32)]
+    pub line_height: f32,
+
+    #[builder(default)]
+    pub max_lines: Option<usize>,
 }
 
 impl Text {
@@ -21,7 +39,7 @@ impl Text {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultTextStyle::new(
+                RegisteredStyle::with_default(DefaultTextStyle::new(
                     DefaultTextStyleParams::builder().build(),
                 ))
             }),
@@ -34,18 +52,23 @@ impl Text {
 // Default Text Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultTextStyleParams {
-    #[builder(default = [1.0f32, 1.0f32, 1.0f32, 1.0f32])]
-    pub color: Color,
+    ///
+    /// Inherited Foreground (or Foreground color of the theme)
+    /// is used when not set.
+    ///
+    #[builder(default, setter(strip_option))]
+    pub color: Option<Color>,
 }
 
 pub struct DefaultTextStyle {
     rect: Rect,
     params: DefaultTextStyleParams,
     event_subscriptions: Vec<EventSubscription>,
-    font_name: &'static str,
+    font_names: Vec<String>,
     font_size: u8,
+    layout: RichTextLayout,
 }
 
 impl DefaultTextStyle {
@@ -59,8 +82,9 @@ impl DefaultTextStyle {
             },
             params,
             event_subscriptions: Vec::new(),
-            font_name: "OpenSans-Regular.ttf",
+            font_names: Vec::new(),
             font_size: 20u8,
+            layout: RichTextLayout::new(Vec::new(), 0.0f32, TextLayoutParams::default()),
         }
     }
 }
@@ -84,18 +108,81 @@ impl Style<Text> for DefaultTextStyle {
     fn measure(
         &mut self,
         data: &mut Text,
-        _control_context: &mut ControlContext,
+        control_context: &mut ControlContext,
         drawing_context: &mut dyn DrawingContext,
-        _size: Size,
+        size: Size,
     ) {
-        let (text_width, text_height) = drawing_context
-            .get_resources()
-            .get_font_dimensions(self.font_name, self.font_size, &data.text.get())
+        let theme = control_context.get_theme();
+        let inherited_properties = control_context.get_inherited_properties();
+        let font_family = inherited_properties
+            .font_family
+            .unwrap_or_else(|| theme.get_font("Default"));
+        self.font_size = inherited_properties
+            .font_size
+            .unwrap_or_else(|| theme.get_size("FontSize")) as u8;
+
+        // characters missing in the font are displayed with the fallback fonts
+        let text = data.text.get();
+        let resources = drawing_context.get_resources();
+        let font_runs = resources
+            .resolve_font_runs(
+                &font_family,
+                inherited_properties.font_weight.unwrap_or(400),
+                FontStyle::Normal,
+                &text,
+            )
+            .unwrap_or_else(|_| {
+                vec![FontRun {
+                    font_name: font_family.clone(),
+                    text: text.clone(),
+                }]
+            });
+
+        let mut measured_runs = Vec::new();
+        self.font_names.clear();
+        for font_run in font_runs {
+            let (char_widths, font_height) = resources
+                .get_font_dimensions_each_char(&font_run.font_name, self.font_size, &font_run.text)
+                .unwrap_or((Vec::new(), 0));
+            measured_runs.push(MeasuredRun {
+                text: font_run.text,
+                char_widths: char_widths.into_iter().map(|width| width as f32).collect(),
+                font_height: font_height as f32,
+            });
+            self.font_names.push(font_run.font_name);
+        }
+
+        let ellipsis_font_name = self.font_names.first().unwrap_or(&font_family);
+        let (ellipsis_width, _) = resources
+            .get_font_dimensions(ellipsis_font_name, self.font_size, ELLIPSIS)
             .unwrap_or((0, 0));
-        self.rect = Rect::new(0.0f32, 0.0f32, text_width as f32, text_height as f32)
+
+        self.layout = RichTextLayout::new(
+            measured_runs,
+            ellipsis_width as f32,
+            TextLayoutParams {
+                wrapping: data.wrapping,
+                alignment: data.alignment,
+                trimming: data.trimming,
+                line_height: data.line_height,
+                max_lines: data.max_lines,
+            },
+        );
+
+        // wrapping happens against available width
+        self.layout.layout(size.width);
+
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            self.layout.get_width(),
+            self.layout.get_height(),
+        )
     }
 
     fn set_rect(&mut self, _data: &mut Text, _control_context: &mut ControlContext, rect: Rect) {
+        // the final width can be smaller than the available one during measure
+        self.layout.layout(rect.width);
         self.rect = rect;
     }
 
@@ -118,34 +205,37 @@ impl Style<Text> for DefaultTextStyle {
 
     fn to_primitives(
         &self,
-        data: &Text,
-        _control_ontext: &ControlContext,
-        drawing_context: &mut dyn DrawingContext,
+        _data: &Text,
+        control_context: &ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
 
-        let x = self.rect.x;
-        let y = self.rect.y;
-        let width = self.rect.width;
-        let height = self.rect.height;
-
-        let (text_width, text_height) = drawing_context
-            .get_resources()
-            .get_font_dimensions(self.font_name, self.font_size, &data.text.get())
-            .unwrap_or((0, 0));
-
-        vec.push(Primitive::Text {
-            resource_key: self.font_name.to_string(),
-            color: self.params.color,
-            position: PixelPoint::new(
-                x + (width - text_width as f32) / 2.0,
-                y + (height - text_height as f32) / 2.0,
-            ),
-            clipping_rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
-            size: Length::new(self.font_size as f32),
-            text: data.text.get(),
+        let color = self.params.color.unwrap_or_else(|| {
+            control_context
+                .get_inherited_properties()
+                .foreground
+                .unwrap_or_else(|| control_context.get_theme().get_color("Foreground"))
         });
 
+        // text block is centered vertically
+        let x = self.rect.x;
+        let y = self.rect.y + (self.rect.height - self.layout.get_height()) / 2.0f32;
+
+        for segment in self.layout.get_segments(self.rect.width) {
+            vec.push(Primitive::Text {
+                resource_key: self.font_names[segment.run_index].clone(),
+                color,
+                position: PixelPoint::new(x + segment.x, y + segment.y),
+                clipping_rect: PixelRect::new(
+                    PixelPoint::new(self.rect.x, self.rect.y),
+                    PixelSize::new(self.rect.width, self.rect.height),
+                ),
+                size: Length::new(self.font_size as f32),
+                text: segment.text,
+            });
+        }
+
         (vec, Vec::new())
     }
 }
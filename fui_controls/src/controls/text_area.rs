@@ -0,0 +1,1370 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::clipping::Clipping;
+use drawing::primitive::Primitive;
+use drawing::transformation::Transformation;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use euclid::Length;
+use fui_core::*;
+use typed_builder::TypedBuilder;
+
+use crate::controls::scroll_area::ViewportInfo;
+use crate::style::*;
+use crate::{EditableText, TextEditor};
+
+/// Text measured to get the average character width.
+const AVERAGE_WIDTH_SAMPLE: &str = "abcdefghijklmnopqrstuvwxyz";
+
+///
+/// Multi-line text editor.
+///
+/// It scrolls its content itself. Offsets and viewport info
+/// work the same way as for ScrollArea, so scroll bars can be bound to them.
+///
+#[derive(TypedBuilder)]
+pub struct TextArea {
+    pub text: Property<String>,
+
+    ///
+    /// Long lines are wrapped to the width of the control
+    /// (or scrolled horizontally with NoWrap).
+    ///
+    #[builder(default = TextWrapping::Word)]
+    pub wrapping: TextWrapping,
+
+    #[builder(default = Property::new(false))]
+    pub is_read_only: Property<bool>,
+
+    ///
+    /// Index of the grapheme (user-perceived character) where the selection starts.
+    /// Line breaks count as single graphemes.
+    ///
+    #[builder(default = Property::new(0usize))]
+    pub selection_start: Property<usize>,
+
+    #[builder(default = Property::new(0usize))]
+    pub selection_length: Property<usize>,
+
+    #[builder(default = Property::new(0usize))]
+    pub caret_index: Property<usize>,
+
+    #[builder(default = Property::new(0.0f32))]
+    pub offset_x: Property<f32>,
+
+    #[builder(default = Property::new(0.0f32))]
+    pub offset_y: Property<f32>,
+
+    #[builder(default = Property::new(ViewportInfo::default()))]
+    pub viewport_info: Property<ViewportInfo>,
+}
+
+impl TextArea {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultTextAreaStyle::new(
+                    DefaultTextAreaStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+
+    ///
+    /// Returns the selection (limited to the text length) built from the properties.
+    ///
+    pub fn get_selection(&self) -> TextSelection {
+        self.get_unclamped_selection()
+            .clamp(get_grapheme_count(&self.text.get()))
+    }
+
+    pub fn set_selection(&mut self, selection: TextSelection) {
+        self.selection_start.set(selection.get_start());
+        self.selection_length.set(selection.get_length());
+        self.caret_index.set(selection.caret);
+    }
+
+    ///
+    /// Returns the selection built from the properties (not limited to the text length).
+    ///
+    fn get_unclamped_selection(&self) -> TextSelection {
+        let start = self.selection_start.get();
+        let length = self.selection_length.get();
+        let caret = self.caret_index.get();
+        if length == 0 {
+            TextSelection::new(caret)
+        } else if caret == start {
+            TextSelection::with_anchor(start + length, start)
+        } else {
+            TextSelection::with_anchor(start, start + length)
+        }
+    }
+}
+
+impl EditableText for TextArea {
+    fn get_text(&self) -> String {
+        self.text.get()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text.set(text);
+    }
+
+    fn set_selection(&mut self, selection: TextSelection) {
+        TextArea::set_selection(self, selection);
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.is_read_only.get()
+    }
+
+    fn clean_pasted_text(&self, text: &str) -> String {
+        text.replace("\r\n", "\n")
+            .chars()
+            .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+            .collect()
+    }
+}
+
+fn is_line_break(grapheme: &str) -> bool {
+    grapheme == "\n" || grapheme == "\r\n" || grapheme == "\r"
+}
+
+///
+/// Splits the paragraph into lines. Lines cover the whole paragraph
+/// (spaces at the wrap points stay at the end of the lines).
+///
+fn wrap_paragraph(
+    graphemes: &[&str],
+    widths: &[f32],
+    max_width: f32,
+    wrapping: TextWrapping,
+) -> Vec<(usize, usize)> {
+    if wrapping == TextWrapping::NoWrap || !max_width.is_finite() || graphemes.is_empty() {
+        return vec![(0, graphemes.len())];
+    }
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < graphemes.len() {
+        let mut width = 0.0f32;
+        let mut end = start;
+        let mut last_break = None;
+        while end < graphemes.len() {
+            if graphemes[end] == " " {
+                width += widths[end];
+                end += 1;
+                if wrapping == TextWrapping::Word {
+                    last_break = Some(end);
+                }
+                continue;
+            }
+            if width + widths[end] > max_width && end > start {
+                break;
+            }
+            width += widths[end];
+            end += 1;
+        }
+        if end < graphemes.len() {
+            // words longer than the line are broken anywhere
+            end = last_break.unwrap_or(end);
+        }
+        lines.push((start, end));
+        start = end;
+    }
+    lines
+}
+
+///
+/// Splits the paragraph not measured yet into lines of the same length,
+/// so there are about as many lines as after wrapping.
+///
+fn estimate_lines(
+    length: usize,
+    average_width: f32,
+    max_width: f32,
+    wrapping: TextWrapping,
+) -> Vec<(usize, usize)> {
+    if wrapping == TextWrapping::NoWrap
+        || !max_width.is_finite()
+        || average_width <= 0.0f32
+        || length == 0
+    {
+        return vec![(0, length)];
+    }
+
+    let line_length = ((max_width / average_width).floor() as usize).max(1);
+    (0..length)
+        .step_by(line_length)
+        .map(|start| (start, (start + line_length).min(length)))
+        .collect()
+}
+
+struct Paragraph {
+    text: String,
+
+    /// Byte offset of the paragraph start in the whole text.
+    byte_start: usize,
+
+    /// Grapheme index of the paragraph start in the whole text.
+    start: usize,
+
+    /// Number of graphemes (without the line break).
+    length: usize,
+
+    /// Grapheme widths, measured when needed.
+    widths: Option<Vec<f32>>,
+
+    /// Lines (grapheme ranges relative to the paragraph start),
+    /// estimated until the paragraph is measured.
+    lines: Vec<(usize, usize)>,
+    wrap_width: f32,
+
+    /// Width of the widest line.
+    width: f32,
+
+    /// Index of the first line in the text.
+    first_line: usize,
+}
+
+impl Paragraph {
+    fn new(text: String, byte_start: usize, start: usize, length: usize) -> Self {
+        Paragraph {
+            text,
+            byte_start,
+            start,
+            length,
+            widths: None,
+            lines: vec![(0, length)],
+            wrap_width: f32::NAN,
+            width: 0.0f32,
+            first_line: 0,
+        }
+    }
+}
+
+///
+/// Splits the text into paragraphs (starts are relative to the text).
+///
+fn split_paragraphs(text: &str) -> Vec<Paragraph> {
+    let mut paragraphs = Vec::new();
+    let mut paragraph = String::new();
+    let mut byte_start = 0;
+    let mut start = 0;
+    let mut length = 0;
+    for grapheme in get_graphemes(text) {
+        if is_line_break(grapheme) {
+            let text = std::mem::take(&mut paragraph);
+            let next_byte_start = byte_start + text.len() + grapheme.len();
+            paragraphs.push(Paragraph::new(text, byte_start, start, length));
+            byte_start = next_byte_start;
+            start += length + 1;
+            length = 0;
+        } else {
+            paragraph.push_str(grapheme);
+            length += 1;
+        }
+    }
+    paragraphs.push(Paragraph::new(paragraph, byte_start, start, length));
+    paragraphs
+}
+
+#[derive(Clone, Copy)]
+struct VisualLine {
+    paragraph: usize,
+    start: usize,
+    end: usize,
+}
+
+//
+// Default TextArea Style
+//
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultTextAreaStyleParams {}
+
+///
+/// Paragraphs are measured and wrapped once and cached, so editing splits,
+/// measures and wraps again only the changed paragraphs. Only the paragraphs
+/// with the visible lines (and the caret) are measured, lines of the others
+/// are estimated from the average character width. Only the visible lines are drawn.
+///
+pub struct DefaultTextAreaStyle {
+    rect: Rect,
+    is_selecting: bool,
+    event_subscriptions: Vec<EventSubscription>,
+    font_name: String,
+    font_size: u8,
+    line_height: f32,
+    average_width: f32,
+
+    text: String,
+    paragraphs: Vec<Paragraph>,
+    line_count: usize,
+    content_width: f32,
+
+    offset_x: f32,
+    offset_y: f32,
+    is_caret_scroll_needed: bool,
+
+    /// Caret x position kept when moving between lines.
+    desired_x: Option<f32>,
+
+    editor: TextEditor,
+}
+
+impl DefaultTextAreaStyle {
+    pub fn new(_params: DefaultTextAreaStyleParams) -> Self {
+        DefaultTextAreaStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            is_selecting: false,
+            event_subscriptions: Vec::new(),
+            font_name: "OpenSans-Regular.ttf".to_string(),
+            font_size: 20u8,
+            line_height: 0.0f32,
+            average_width: 0.0f32,
+
+            text: String::new(),
+            paragraphs: Vec::new(),
+            line_count: 0,
+            content_width: 0.0f32,
+
+            offset_x: 0.0f32,
+            offset_y: 0.0f32,
+            is_caret_scroll_needed: false,
+            desired_x: None,
+
+            editor: TextEditor::new(),
+        }
+    }
+
+    fn get_viewport_size(&self) -> Size {
+        Size::new(
+            (self.rect.width - 8.0f32).max(0.0f32),
+            (self.rect.height - 8.0f32).max(0.0f32),
+        )
+    }
+
+    ///
+    /// Splits the text into paragraphs. Only the paragraphs with the changed
+    /// part of the text are split again, the others keep their measurements.
+    ///
+    fn update_paragraphs(&mut self, text: String) {
+        if self.paragraphs.is_empty() {
+            self.paragraphs = split_paragraphs(&text);
+            self.text = text;
+            return;
+        }
+        if text == self.text {
+            return;
+        }
+
+        // bytes not changed at the beginning and at the end of the text
+        let old_len = self.text.len();
+        let prefix = self
+            .text
+            .bytes()
+            .zip(text.bytes())
+            .take_while(|(old, new)| old == new)
+            .count();
+        let suffix = self
+            .text
+            .bytes()
+            .rev()
+            .zip(text.bytes().rev())
+            .take(old_len.min(text.len()) - prefix)
+            .take_while(|(old, new)| old == new)
+            .count();
+
+        // the line break of the previous paragraph may be changed
+        // when the change starts at the paragraph start
+        let mut first = self.get_paragraph_index_at_byte(prefix);
+        if first > 0 && self.paragraphs[first].byte_start == prefix {
+            first -= 1;
+        }
+        let last = self.get_paragraph_index_at_byte(old_len - suffix);
+
+        let old_start = self.paragraphs[first].byte_start;
+        let old_end = match self.paragraphs.get(last + 1) {
+            Some(next) => next.byte_start,
+            None => old_len,
+        };
+        let new_end = old_end + text.len() - old_len;
+
+        let start = self.paragraphs[first].start;
+        let mut paragraphs = split_paragraphs(&text[old_start..new_end]);
+        let old_count = match self.paragraphs.get(last + 1) {
+            Some(next) => next.start - start,
+            None => self.paragraphs[last].start + self.paragraphs[last].length - start,
+        };
+        let new_count = if last + 1 < self.paragraphs.len() {
+            // the changed part ends with the line break before the next paragraph
+            paragraphs.pop();
+            paragraphs
+                .last()
+                .map_or(0, |paragraph| paragraph.start + paragraph.length + 1)
+        } else {
+            paragraphs
+                .last()
+                .map_or(0, |paragraph| paragraph.start + paragraph.length)
+        };
+
+        for paragraph in paragraphs.iter_mut() {
+            paragraph.byte_start += old_start;
+            paragraph.start += start;
+        }
+        for paragraph in self.paragraphs[last + 1..].iter_mut() {
+            paragraph.byte_start = paragraph.byte_start + text.len() - old_len;
+            paragraph.start = paragraph.start + new_count - old_count;
+        }
+        self.paragraphs.splice(first..=last, paragraphs);
+        self.text = text;
+    }
+
+    ///
+    /// Measures the paragraph. Returns false when it is already measured.
+    ///
+    fn measure_paragraph(&mut self, index: usize, resources: &mut dyn Resources) -> bool {
+        let paragraph = &mut self.paragraphs[index];
+        if paragraph.widths.is_some() {
+            return false;
+        }
+        let (char_widths, _) = resources
+            .get_font_dimensions_each_char(&self.font_name, self.font_size, &paragraph.text)
+            .unwrap_or((Vec::new(), 0));
+        let char_widths: Vec<f32> = char_widths.into_iter().map(|width| width as f32).collect();
+        paragraph.widths = Some(get_grapheme_widths(&paragraph.text, &char_widths));
+        paragraph.wrap_width = f32::NAN;
+        true
+    }
+
+    ///
+    /// Wraps the paragraphs measured or resized since the last call
+    /// (or estimates their lines when they are not measured yet) and numbers the lines.
+    ///
+    fn wrap_paragraphs(&mut self, wrapping: TextWrapping, wrap_width: f32) {
+        let mut content_width = 0.0f32;
+        let mut line_count = 0;
+        for paragraph in self.paragraphs.iter_mut() {
+            if paragraph.wrap_width != wrap_width {
+                match paragraph.widths {
+                    Some(ref widths) => {
+                        let graphemes = get_graphemes(&paragraph.text);
+                        paragraph.lines = wrap_paragraph(&graphemes, widths, wrap_width, wrapping);
+                        paragraph.width = paragraph
+                            .lines
+                            .iter()
+                            .map(|(start, end)| widths[*start..*end].iter().sum())
+                            .fold(0.0f32, f32::max);
+                    }
+                    None => {
+                        paragraph.lines = estimate_lines(
+                            paragraph.length,
+                            self.average_width,
+                            wrap_width,
+                            wrapping,
+                        );
+                    }
+                }
+                paragraph.wrap_width = wrap_width;
+            }
+            content_width = content_width.max(paragraph.width);
+            paragraph.first_line = line_count;
+            line_count += paragraph.lines.len();
+        }
+
+        self.line_count = line_count;
+        self.content_width = content_width;
+    }
+
+    ///
+    /// Updates the layout to the current text (it can be changed
+    /// by the previous events before the control is measured again).
+    ///
+    fn update_layout(&mut self, data: &TextArea, resources: &mut dyn Resources, wrap_width: f32) {
+        self.update_paragraphs(data.text.get());
+
+        let caret_paragraph = self.get_paragraph_index(self.get_selection(data).caret);
+        self.measure_paragraph(caret_paragraph, resources);
+        self.wrap_paragraphs(data.wrapping, wrap_width);
+
+        self.measure_visible_lines(data.wrapping, wrap_width, resources);
+    }
+
+    ///
+    /// Measures the paragraphs with the visible lines. Their estimated lines
+    /// are replaced with the wrapped ones, so the lines are numbered again
+    /// and the visible lines are checked once more.
+    ///
+    fn measure_visible_lines(
+        &mut self,
+        wrapping: TextWrapping,
+        wrap_width: f32,
+        resources: &mut dyn Resources,
+    ) {
+        loop {
+            let (first, last) = self.get_visible_lines();
+            if first >= last {
+                return;
+            }
+            let first_paragraph = self.get_line(first).paragraph;
+            let last_paragraph = self.get_line(last - 1).paragraph;
+            let mut is_measured = false;
+            for paragraph in first_paragraph..=last_paragraph {
+                is_measured |= self.measure_paragraph(paragraph, resources);
+            }
+            if !is_measured {
+                return;
+            }
+            self.wrap_paragraphs(wrapping, wrap_width);
+        }
+    }
+
+    ///
+    /// Returns the number of graphemes in the text laid out (line breaks included).
+    ///
+    fn get_text_length(&self) -> usize {
+        self.paragraphs
+            .last()
+            .map_or(0, |paragraph| paragraph.start + paragraph.length)
+    }
+
+    ///
+    /// Returns the selection limited to the length of the text laid out.
+    ///
+    fn get_selection(&self, data: &TextArea) -> TextSelection {
+        data.get_unclamped_selection().clamp(self.get_text_length())
+    }
+
+    fn get_visible_lines(&self) -> (usize, usize) {
+        if self.line_height <= 0.0f32 {
+            return (0, self.line_count);
+        }
+        let viewport_size = self.get_viewport_size();
+        let first = (self.offset_y / self.line_height).floor().max(0.0f32) as usize;
+        let last = ((self.offset_y + viewport_size.height) / self.line_height).ceil() as usize;
+        (first.min(self.line_count), last.min(self.line_count))
+    }
+
+    ///
+    /// Returns the paragraph with the grapheme index
+    /// (the index of its line break included).
+    ///
+    fn get_paragraph_index(&self, index: usize) -> usize {
+        match self
+            .paragraphs
+            .binary_search_by(|paragraph| paragraph.start.cmp(&index))
+        {
+            Ok(paragraph) => paragraph,
+            Err(paragraph) => paragraph.saturating_sub(1),
+        }
+    }
+
+    fn get_paragraph_index_at_byte(&self, byte_index: usize) -> usize {
+        match self
+            .paragraphs
+            .binary_search_by(|paragraph| paragraph.byte_start.cmp(&byte_index))
+        {
+            Ok(paragraph) => paragraph,
+            Err(paragraph) => paragraph.saturating_sub(1),
+        }
+    }
+
+    fn get_line(&self, line_index: usize) -> VisualLine {
+        // every paragraph has at least one line
+        let paragraph_index = match self
+            .paragraphs
+            .binary_search_by(|paragraph| paragraph.first_line.cmp(&line_index))
+        {
+            Ok(paragraph) => paragraph,
+            Err(paragraph) => paragraph.saturating_sub(1),
+        };
+        let paragraph = &self.paragraphs[paragraph_index];
+        let (start, end) = paragraph.lines[line_index - paragraph.first_line];
+        VisualLine {
+            paragraph: paragraph_index,
+            start,
+            end,
+        }
+    }
+
+    ///
+    /// Returns the line with the grapheme index. Index at the wrap point
+    /// belongs to the next line.
+    ///
+    fn get_line_index(&self, index: usize) -> usize {
+        if self.paragraphs.is_empty() {
+            return 0;
+        }
+        let paragraph = &self.paragraphs[self.get_paragraph_index(index)];
+        let relative_index = index.saturating_sub(paragraph.start);
+        let line = paragraph
+            .lines
+            .iter()
+            .position(|(_, end)| relative_index < *end)
+            .unwrap_or(paragraph.lines.len() - 1);
+        paragraph.first_line + line
+    }
+
+    fn get_grapheme_x(&self, line_index: usize, index: usize) -> f32 {
+        let line = self.get_line(line_index);
+        let paragraph = &self.paragraphs[line.paragraph];
+        let relative_index = index.saturating_sub(paragraph.start);
+        match paragraph.widths {
+            Some(ref widths) => widths[line.start..relative_index.max(line.start).min(line.end)]
+                .iter()
+                .sum(),
+            None => 0.0f32,
+        }
+    }
+
+    fn get_index_at_x(&self, line_index: usize, x: f32) -> usize {
+        let line = self.get_line(line_index);
+        let paragraph = &self.paragraphs[line.paragraph];
+
+        // the wrap point belongs to the next line
+        let is_last_line = line.end == paragraph.length;
+        let max_index = if is_last_line || line.end == line.start {
+            line.end
+        } else {
+            line.end - 1
+        };
+
+        let mut index = line.start;
+        if let Some(ref widths) = paragraph.widths {
+            let mut pos = 0.0f32;
+            while index < max_index && x >= pos + widths[index] / 2.0f32 {
+                pos += widths[index];
+                index += 1;
+            }
+        }
+        paragraph.start + index
+    }
+
+    fn get_index_at_point(&self, point: &Point) -> usize {
+        if self.line_count == 0 || self.line_height <= 0.0f32 {
+            return 0;
+        }
+        let y = point.y - self.rect.y - 4.0f32 + self.offset_y;
+        let line_index =
+            ((y / self.line_height).floor().max(0.0f32) as usize).min(self.line_count - 1);
+        let x = point.x - self.rect.x - 4.0f32 + self.offset_x;
+        self.get_index_at_x(line_index, x)
+    }
+
+    fn move_caret_vertically(
+        &mut self,
+        data: &mut TextArea,
+        resources: &mut dyn Resources,
+        line_delta: isize,
+        extend: bool,
+    ) {
+        if self.line_count == 0 {
+            return;
+        }
+        let selection = self.get_selection(data);
+        let desired_x = match self.desired_x {
+            Some(x) => x,
+            None => self.get_grapheme_x(self.get_line_index(selection.caret), selection.caret),
+        };
+
+        let wrap_width = self.get_viewport_size().width;
+        let caret = loop {
+            let line_index = self.get_line_index(selection.caret);
+            let target_line = (line_index as isize + line_delta)
+                .max(0)
+                .min(self.line_count as isize - 1) as usize;
+            if target_line == line_index && line_delta < 0 {
+                break 0;
+            } else if target_line == line_index && line_delta > 0 {
+                break self.get_text_length();
+            }
+
+            // estimated lines are replaced with the wrapped ones,
+            // so the target line is found again
+            let paragraph = self.get_line(target_line).paragraph;
+            if !self.measure_paragraph(paragraph, resources) {
+                break self.get_index_at_x(target_line, desired_x);
+            }
+            self.wrap_paragraphs(data.wrapping, wrap_width);
+        };
+
+        data.set_selection(selection.move_caret(caret, extend));
+        self.desired_x = Some(desired_x);
+    }
+
+    fn handle_key(
+        &mut self,
+        data: &mut TextArea,
+        resources: &mut dyn Resources,
+        key_code: &Keycode,
+        modifiers: &KeyModifiers,
+    ) {
+        let selection = self.get_selection(data);
+        let extend = modifiers.shift;
+
+        match key_code {
+            Keycode::Up => {
+                self.move_caret_vertically(data, resources, -1, extend);
+                return;
+            }
+            Keycode::Down => {
+                self.move_caret_vertically(data, resources, 1, extend);
+                return;
+            }
+            Keycode::PageUp | Keycode::PageDown => {
+                let page_lines = if self.line_height > 0.0f32 {
+                    ((self.get_viewport_size().height / self.line_height).floor() as isize).max(1)
+                } else {
+                    1
+                };
+                let line_delta = if *key_code == Keycode::PageUp {
+                    -page_lines
+                } else {
+                    page_lines
+                };
+                self.move_caret_vertically(data, resources, line_delta, extend);
+                return;
+            }
+            Keycode::Enter | Keycode::NumpadEnter => {
+                self.editor
+                    .replace_selection(data, selection, "\n", TextEditKind::Typing);
+            }
+            Keycode::Home => {
+                let caret = if modifiers.ctrl || self.line_count == 0 {
+                    0
+                } else {
+                    let line = self.get_line(self.get_line_index(selection.caret));
+                    self.paragraphs[line.paragraph].start + line.start
+                };
+                data.set_selection(selection.move_caret(caret, extend));
+            }
+            Keycode::End => {
+                let caret = if modifiers.ctrl || self.line_count == 0 {
+                    self.get_text_length()
+                } else {
+                    let line_index = self.get_line_index(selection.caret);
+                    self.get_index_at_x(line_index, f32::INFINITY)
+                };
+                data.set_selection(selection.move_caret(caret, extend));
+            }
+            _ => return,
+        }
+
+        self.desired_x = None;
+    }
+
+    fn scroll_caret_into_view(&mut self, caret: usize) {
+        if self.line_count == 0 {
+            return;
+        }
+        let viewport_size = self.get_viewport_size();
+        let line_index = self.get_line_index(caret);
+
+        let caret_y = line_index as f32 * self.line_height;
+        if caret_y < self.offset_y {
+            self.offset_y = caret_y;
+        } else if caret_y + self.line_height > self.offset_y + viewport_size.height {
+            self.offset_y = caret_y + self.line_height - viewport_size.height;
+        }
+
+        let caret_x = self.get_grapheme_x(line_index, caret);
+        if caret_x < self.offset_x {
+            self.offset_x = caret_x;
+        } else if caret_x + 2.0f32 > self.offset_x + viewport_size.width {
+            self.offset_x = caret_x + 2.0f32 - viewport_size.width;
+        }
+    }
+
+    ///
+    /// Returns the caret rect in window coordinates.
+    ///
+    fn get_caret_rect(&self, data: &TextArea) -> Rect {
+        let (x, y) = if self.line_count > 0 {
+            let caret = self.get_selection(data).caret;
+            let line_index = self.get_line_index(caret);
+            (
+                self.get_grapheme_x(line_index, caret),
+                line_index as f32 * self.line_height,
+            )
+        } else {
+            (0.0f32, 0.0f32)
+        };
+        Rect::new(
+            self.rect.x + 4.0f32 + x - self.offset_x,
+            self.rect.y + 4.0f32 + y - self.offset_y,
+            2.0f32,
+            self.line_height,
+        )
+    }
+
+    fn clamp_offsets(&mut self) {
+        let viewport_size = self.get_viewport_size();
+        let content_height = self.line_count as f32 * self.line_height;
+        self.offset_x = self
+            .offset_x
+            .min(self.content_width + 2.0f32 - viewport_size.width)
+            .max(0.0f32);
+        self.offset_y = self
+            .offset_y
+            .min(content_height - viewport_size.height)
+            .max(0.0f32);
+    }
+
+    fn update_properties(&self, data: &mut TextArea) {
+        let viewport_size = self.get_viewport_size();
+        let viewport_info = ViewportInfo {
+            content_width: self.content_width + 2.0f32,
+            content_height: self.line_count as f32 * self.line_height,
+            viewport_width: viewport_size.width,
+            viewport_height: viewport_size.height,
+        };
+        if data.viewport_info.get() != viewport_info {
+            data.viewport_info.set(viewport_info);
+        }
+        if data.offset_x.get() != self.offset_x {
+            data.offset_x.set(self.offset_x);
+        }
+        if data.offset_y.get() != self.offset_y {
+            data.offset_y.set(self.offset_y);
+        }
+    }
+}
+
+impl Style<TextArea> for DefaultTextAreaStyle {
+    fn setup(&mut self, data: &mut TextArea, control_context: &mut ControlContext) {
+        control_context
+            .get_attached_values_mut()
+            .entry::<Cursor>()
+            .or_insert(CursorIcon::Text);
+        control_context
+            .get_attached_values_mut()
+            .insert::<AcceptsReturn>(true);
+
+        self.event_subscriptions
+            .push(data.text.dirty_watching(&control_context.get_self_rc()));
+        self.event_subscriptions.push(
+            data.selection_start
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.selection_length
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.caret_index
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_read_only
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions
+            .push(data.offset_x.dirty_watching(&control_context.get_self_rc()));
+        self.event_subscriptions
+            .push(data.offset_y.dirty_watching(&control_context.get_self_rc()));
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut TextArea,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        if self
+            .editor
+            .handle_focus_event(control_context, event_context, &event)
+        {
+            return;
+        }
+
+        match event {
+            ControlEvent::TapDown { ref position } => {
+                let index = self.get_index_at_point(position);
+                data.set_selection(TextSelection::new(index));
+                self.is_selecting = true;
+                self.desired_x = None;
+                self.editor
+                    .restart_caret_blink(control_context, event_context);
+            }
+
+            ControlEvent::TapMove { ref position } if self.is_selecting => {
+                let index = self.get_index_at_point(position);
+                data.set_selection(self.get_selection(data).move_caret(index, true));
+                self.is_caret_scroll_needed = true;
+            }
+
+            ControlEvent::TapUp { .. } => {
+                self.is_selecting = false;
+            }
+
+            ControlEvent::DoubleTap { ref position } => {
+                let index = self.get_index_at_point(position);
+                data.set_selection(find_word_at(&data.text.get(), index));
+                self.is_selecting = false;
+            }
+
+            ControlEvent::KeyboardInput(ref key_event) if key_event.state == KeyState::Pressed => {
+                // the text may be changed by the previous events
+                let wrap_width = self.get_viewport_size().width;
+                self.update_layout(data, drawing_context.get_resources(), wrap_width);
+                self.is_caret_scroll_needed = true;
+                self.editor
+                    .restart_caret_blink(control_context, event_context);
+
+                if let Some(ref key_code) = key_event.keycode {
+                    let selection = self.get_selection(data);
+                    if self.editor.handle_key(
+                        data,
+                        selection,
+                        control_context,
+                        key_code,
+                        &key_event.modifiers,
+                    ) {
+                        self.desired_x = None;
+                    } else {
+                        self.handle_key(
+                            data,
+                            drawing_context.get_resources(),
+                            key_code,
+                            &key_event.modifiers,
+                        );
+                    }
+                }
+
+                if let Some(ref text) = key_event.text {
+                    let selection = self.get_selection(data);
+                    if self.editor.type_text(data, selection, text) {
+                        self.desired_x = None;
+                    }
+                }
+
+                // caret is moved to the visible area
+                control_context.set_is_dirty(true);
+            }
+
+            _ => (),
+        }
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut TextArea,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let theme = control_context.get_theme();
+        let inherited_properties = control_context.get_inherited_properties();
+        let font_family = inherited_properties
+            .font_family
+            .unwrap_or_else(|| theme.get_font("Default"));
+        let font_name = drawing_context
+            .get_resources()
+            .resolve_font(
+                &font_family,
+                inherited_properties.font_weight.unwrap_or(400),
+                FontStyle::Normal,
+                "",
+            )
+            .unwrap_or(font_family);
+        let font_size = inherited_properties
+            .font_size
+            .unwrap_or_else(|| theme.get_size("FontSize")) as u8;
+        if font_name != self.font_name || font_size != self.font_size {
+            // all the measurements are invalid
+            self.font_name = font_name;
+            self.font_size = font_size;
+            self.paragraphs.clear();
+        }
+
+        let (_, line_height) = drawing_context
+            .get_resources()
+            .get_font_dimensions(&self.font_name, self.font_size, "")
+            .unwrap_or((0, 0));
+        self.line_height = line_height as f32;
+        if self.paragraphs.is_empty() {
+            let (sample_width, _) = drawing_context
+                .get_resources()
+                .get_font_dimensions(&self.font_name, self.font_size, AVERAGE_WIDTH_SAMPLE)
+                .unwrap_or((0, 0));
+            self.average_width = sample_width as f32 / AVERAGE_WIDTH_SAMPLE.len() as f32;
+        }
+
+        // offsets may be changed by the bound scroll bars
+        self.offset_x = data.offset_x.get();
+        self.offset_y = data.offset_y.get();
+
+        let wrap_width = if self.rect.width > 0.0f32 {
+            self.get_viewport_size().width
+        } else {
+            size.width - 8.0f32
+        };
+        self.update_layout(data, drawing_context.get_resources(), wrap_width);
+
+        if self.is_caret_scroll_needed {
+            self.scroll_caret_into_view(self.get_selection(data).caret);
+            self.is_caret_scroll_needed = false;
+        }
+        self.clamp_offsets();
+        self.measure_visible_lines(data.wrapping, wrap_width, drawing_context.get_resources());
+        // measured lines may change the content height
+        self.clamp_offsets();
+
+        let content_height = self.line_count.max(1) as f32 * self.line_height;
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            (self.content_width + 2.0f32 + 8.0f32).min(size.width),
+            (content_height + 8.0f32)
+                .min(size.height)
+                .max(self.line_height + 8.0f32),
+        )
+    }
+
+    fn set_rect(&mut self, data: &mut TextArea, _control_context: &mut ControlContext, rect: Rect) {
+        self.rect = rect;
+
+        // lines are wrapped again (without measuring) when the width is changed
+        let wrap_width = self.get_viewport_size().width;
+        self.wrap_paragraphs(data.wrapping, wrap_width);
+        self.clamp_offsets();
+        self.editor.set_caret_rect(self.get_caret_rect(data));
+
+        self.update_properties(data);
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &TextArea,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            HitTestResult::Current
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn to_primitives(
+        &self,
+        data: &TextArea,
+        control_context: &ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+
+        let x = self.rect.x;
+        let y = self.rect.y;
+        let width = self.rect.width;
+        let height = self.rect.height;
+
+        let theme = control_context.get_theme();
+
+        default_theme::border_3d_edit(
+            &mut vec,
+            &theme,
+            x,
+            y,
+            width,
+            height,
+            self.editor.is_focused(),
+        );
+
+        default_theme::brush_rect(
+            &mut vec,
+            x + 3.0f32,
+            y + 3.0f32,
+            width - 6.0f32,
+            height - 6.0f32,
+            if self.editor.is_focused() {
+                theme.get_brush("EditBackgroundFocused")
+            } else {
+                theme.get_brush("EditBackground")
+            },
+        );
+
+        let mut vec2 = Vec::new();
+
+        let selection = self.get_selection(data);
+        let (first_line, last_line) = self.get_visible_lines();
+        for line_index in first_line..last_line {
+            let line = self.get_line(line_index);
+            let paragraph = &self.paragraphs[line.paragraph];
+            let graphemes = get_graphemes(&paragraph.text);
+            let line_y = y + 4.0f32 + line_index as f32 * self.line_height;
+
+            let get_x = |index: usize| -> f32 {
+                match paragraph.widths {
+                    Some(ref widths) => widths[line.start..index].iter().sum(),
+                    None => 0.0f32,
+                }
+            };
+
+            // selection relative to the line
+            let selection_start = selection
+                .get_start()
+                .saturating_sub(paragraph.start)
+                .max(line.start)
+                .min(line.end);
+            let selection_end = selection
+                .get_end()
+                .saturating_sub(paragraph.start)
+                .max(line.start)
+                .min(line.end);
+            let is_line_break_selected = line.end == paragraph.length
+                && selection.get_start() <= paragraph.start + paragraph.length
+                && selection.get_end() > paragraph.start + paragraph.length;
+
+            // selected line break is shown as a space
+            let line_break_width = if is_line_break_selected {
+                self.line_height / 4.0f32
+            } else {
+                0.0f32
+            };
+            if selection_start < selection_end || is_line_break_selected {
+                let start_px = get_x(selection_start);
+                let end_px = get_x(selection_end) + line_break_width;
+                vec2.push(Primitive::Rectangle {
+                    color: theme.get_color("SelectionBackground"),
+                    rect: PixelRect::new(
+                        PixelPoint::new(x + 4.0f32 + start_px, line_y),
+                        PixelSize::new(end_px - start_px, self.line_height),
+                    ),
+                });
+            }
+
+            let parts = [
+                (
+                    line.start,
+                    selection_start,
+                    theme.get_color("EditForeground"),
+                ),
+                (
+                    selection_start,
+                    selection_end,
+                    theme.get_color("SelectionForeground"),
+                ),
+                (selection_end, line.end, theme.get_color("EditForeground")),
+            ];
+            for (start, end, color) in parts.iter() {
+                if start >= end {
+                    continue;
+                }
+                vec2.push(Primitive::Text {
+                    resource_key: self.font_name.clone(),
+                    color: *color,
+                    position: PixelPoint::new(x + 4.0f32 + get_x(*start), line_y),
+                    clipping_rect: PixelRect::new(
+                        PixelPoint::new(x + 4.0f32, line_y),
+                        PixelSize::new(self.content_width + 2.0f32, self.line_height),
+                    ),
+                    size: Length::new(self.font_size as f32),
+                    text: graphemes[*start..*end].concat(),
+                });
+            }
+        }
+
+        vec2.translate(PixelPoint::new(-self.offset_x, -self.offset_y));
+
+        // draw cursor
+        if self.editor.is_caret_visible() && !data.is_read_only.get() && self.line_count > 0 {
+            let line_index = self.get_line_index(selection.caret);
+            if line_index >= first_line && line_index < last_line {
+                let caret_rect = self.get_caret_rect(data);
+                vec2.push(Primitive::Rectangle {
+                    color: theme.get_color("Caret"),
+                    rect: PixelRect::new(
+                        PixelPoint::new(caret_rect.x, caret_rect.y),
+                        PixelSize::new(caret_rect.width, caret_rect.height),
+                    ),
+                });
+            }
+        }
+
+        vec2 = vec2.clip(PixelRect::new(
+            PixelPoint::new(x + 4.0f32, y + 4.0f32),
+            PixelSize::new(width - 8.0f32, height - 8.0f32),
+        ));
+
+        vec.append(&mut vec2);
+
+        (vec, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+
+    fn create_style(data: &TextArea, viewport_height: f32) -> DefaultTextAreaStyle {
+        let mut style = DefaultTextAreaStyle::new(DefaultTextAreaStyleParams::builder().build());
+        style.line_height = 20.0f32;
+        style.average_width = 10.0f32;
+        style.rect = Rect::new(0.0f32, 0.0f32, 808.0f32, viewport_height + 8.0f32);
+        let wrap_width = style.get_viewport_size().width;
+        style.update_layout(data, &mut TestResources, wrap_width);
+        style
+    }
+
+    fn press(style: &mut DefaultTextAreaStyle, data: &mut TextArea, key_code: Keycode) -> usize {
+        style.handle_key(
+            data,
+            &mut TestResources,
+            &key_code,
+            &KeyModifiers::default(),
+        );
+        data.get_selection().caret
+    }
+
+    #[test]
+    fn test_wrap_paragraph() {
+        let graphemes = get_graphemes("one two three");
+        let widths = vec![10.0f32; graphemes.len()];
+
+        assert_eq!(
+            wrap_paragraph(&graphemes, &widths, 80.0f32, TextWrapping::Word),
+            vec![(0, 8), (8, 13)]
+        );
+        assert_eq!(
+            wrap_paragraph(&graphemes, &widths, 45.0f32, TextWrapping::Word),
+            vec![(0, 4), (4, 8), (8, 12), (12, 13)]
+        );
+        assert_eq!(
+            wrap_paragraph(&graphemes, &widths, 50.0f32, TextWrapping::Character),
+            vec![(0, 5), (5, 10), (10, 13)]
+        );
+        assert_eq!(
+            wrap_paragraph(&graphemes, &widths, 50.0f32, TextWrapping::NoWrap),
+            vec![(0, 13)]
+        );
+    }
+
+    #[test]
+    fn test_up_and_down_keep_caret_column() {
+        let mut data = TextArea::builder()
+            .text(Property::new("abcdef\nab\nabcdefgh"))
+            .build();
+        let mut style = create_style(&data, 100.0f32);
+        data.set_selection(TextSelection::new(5));
+
+        // the short line moves the caret to its end
+        assert_eq!(press(&mut style, &mut data, Keycode::Down), 9);
+        assert_eq!(press(&mut style, &mut data, Keycode::Down), 15);
+        assert_eq!(press(&mut style, &mut data, Keycode::Up), 9);
+        assert_eq!(press(&mut style, &mut data, Keycode::Up), 5);
+        assert_eq!(press(&mut style, &mut data, Keycode::Up), 0);
+    }
+
+    #[test]
+    fn test_page_up_and_down_keep_caret_column() {
+        let text = vec!["0123456789"; 10].join("\n");
+        let mut data = TextArea::builder().text(Property::new(text)).build();
+
+        // three lines are visible
+        let mut style = create_style(&data, 60.0f32);
+        data.set_selection(TextSelection::new(4));
+
+        assert_eq!(press(&mut style, &mut data, Keycode::PageDown), 37);
+        assert_eq!(press(&mut style, &mut data, Keycode::PageDown), 70);
+        assert_eq!(press(&mut style, &mut data, Keycode::PageUp), 37);
+        assert_eq!(press(&mut style, &mut data, Keycode::PageUp), 4);
+        assert_eq!(press(&mut style, &mut data, Keycode::PageUp), 0);
+    }
+
+    #[test]
+    fn test_only_visible_paragraphs_are_measured() {
+        // every paragraph is estimated to two lines, but it is wrapped to three
+        let text = vec!["aaaaaa bbbbbb cccccc"; 1000].join("\n");
+        let mut data = TextArea::builder().text(Property::new(text)).build();
+        let mut style = DefaultTextAreaStyle::new(DefaultTextAreaStyleParams::builder().build());
+        style.line_height = 20.0f32;
+        style.average_width = 10.0f32;
+        style.rect = Rect::new(0.0f32, 0.0f32, 108.0f32, 68.0f32);
+        style.update_layout(&data, &mut TestResources, 100.0f32);
+
+        let get_measured = |style: &DefaultTextAreaStyle| -> Vec<usize> {
+            (0..style.paragraphs.len())
+                .filter(|index| style.paragraphs[*index].widths.is_some())
+                .collect()
+        };
+        assert_eq!(get_measured(&style), vec![0]);
+        assert_eq!(style.line_count, 3 + 999 * 2);
+        assert_eq!(style.paragraphs[2].first_line, 5);
+
+        // the paragraph with the caret is measured
+        data.set_selection(TextSelection::new(21 + 16));
+        style.update_layout(&data, &mut TestResources, 100.0f32);
+        assert_eq!(get_measured(&style), vec![0, 1]);
+        assert_eq!(style.get_line_index(21 + 16), 5);
+
+        // the caret is moved to the line of the next paragraph when it is measured
+        assert_eq!(press(&mut style, &mut data, Keycode::Down), 2 * 21 + 2);
+        assert_eq!(get_measured(&style), vec![0, 1, 2]);
+        assert_eq!(style.get_line_index(2 * 21 + 2), 6);
+
+        style.offset_y = 500.0f32 * 20.0f32;
+        style.measure_visible_lines(TextWrapping::Word, 100.0f32, &mut TestResources);
+        assert_eq!(get_measured(&style).len(), 3 + 2);
+    }
+
+    #[test]
+    fn test_only_changed_paragraphs_are_split_again() {
+        let data = TextArea::builder()
+            .text(Property::new("one\ntwo\r\nthree"))
+            .build();
+        let mut style = create_style(&data, 100.0f32);
+
+        style.update_paragraphs("one\ntwo\r\nthree!".to_string());
+        let measured: Vec<bool> = style
+            .paragraphs
+            .iter()
+            .map(|paragraph| paragraph.widths.is_some())
+            .collect();
+        assert_eq!(measured, vec![true, true, false]);
+
+        for text in &[
+            "one\ntw\no\r\nthree!",
+            "one\r\ntw\no\r\nthree!",
+            "onetw\no\r\nthree!",
+            "x\r",
+            "x\r\n",
+            "",
+            "\n\n",
+            "a\n\nb",
+        ] {
+            style.update_paragraphs(text.to_string());
+            let get_values = |paragraphs: &[Paragraph]| -> Vec<(String, usize, usize, usize)> {
+                paragraphs
+                    .iter()
+                    .map(|p| (p.text.clone(), p.byte_start, p.start, p.length))
+                    .collect()
+            };
+            assert_eq!(
+                get_values(&style.paragraphs),
+                get_values(&split_paragraphs(text)),
+                "{:?}",
+                text
+            );
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::style::default_theme::gradient_rect;
 use drawing::clipping::Clipping;
 use drawing::primitive::Primitive;
 use drawing::transformation::Transformation;
@@ -11,10 +10,91 @@ use fui_core::*;
 use typed_builder::TypedBuilder;
 
 use crate::style::*;
+use crate::{EditableText, TextEditor};
+
+///
+/// Filters the text typed or pasted into the TextBox.
+///
+/// The filter gets the inserted text and returns the part of it that is accepted.
+///
+pub struct InputFilter {
+    filter: Box<dyn Fn(&str) -> String>,
+}
+
+impl InputFilter {
+    pub fn new<F: 'static + Fn(&str) -> String>(filter: F) -> Self {
+        InputFilter {
+            filter: Box::new(filter),
+        }
+    }
+
+    ///
+    /// Accepts only the characters matching the predicate.
+    ///
+    pub fn chars<F: 'static + Fn(char) -> bool>(predicate: F) -> Self {
+        InputFilter::new(move |text| text.chars().filter(|c| predicate(*c)).collect())
+    }
+
+    pub fn digits() -> Self {
+        InputFilter::chars(|c| c.is_ascii_digit())
+    }
+
+    pub fn filter(&self, text: &str) -> String {
+        (self.filter)(text)
+    }
+}
 
 #[derive(TypedBuilder)]
 pub struct TextBox {
     pub text: Property<String>,
+
+    ///
+    /// Shown when the text is empty and the TextBox is not focused.
+    ///
+    #[builder(default = Property::new(String::new()))]
+    pub placeholder: Property<String>,
+
+    ///
+    /// Masks the text with password_char. Text cannot be copied then.
+    ///
+    #[builder(default = Property::new(false))]
+    pub is_password: Property<bool>,
+
+    #[builder(default = '\u{2022}')]
+    pub password_char: char,
+
+    ///
+    /// Text can be selected and copied, but not edited.
+    ///
+    #[builder(default = Property::new(false))]
+    pub is_read_only: Property<bool>,
+
+    ///
+    /// Maximum number of graphemes that can be typed or pasted.
+    ///
+    #[builder(default)]
+    pub max_length: Option<usize>,
+
+    #[builder(default)]
+    pub input_filter: Option<InputFilter>,
+
+    ///
+    /// Index of the grapheme (user-perceived character) where the selection starts.
+    ///
+    #[builder(default = Property::new(0usize))]
+    pub selection_start: Property<usize>,
+
+    ///
+    /// Number of selected graphemes.
+    ///
+    #[builder(default = Property::new(0usize))]
+    pub selection_length: Property<usize>,
+
+    ///
+    /// Grapheme index of the caret. It is at the start or at the end of the selection.
+    ///
+    #[builder(default = Property::new(0usize))]
+    pub caret_index: Property<usize>,
 }
 
 impl TextBox {
@@ -26,32 +106,117 @@ impl TextBox {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultTextBoxStyle::new(
+                RegisteredStyle::with_default(DefaultTextBoxStyle::new(
                     DefaultTextBoxStyleParams::builder().build(),
                 ))
             }),
             context,
         )
     }
+
+    ///
+    /// Returns the selection (limited to the text length) built from the properties.
+    ///
+    pub fn get_selection(&self) -> TextSelection {
+        let start = self.selection_start.get();
+        let length = self.selection_length.get();
+        let caret = self.caret_index.get();
+        let selection = if length == 0 {
+            TextSelection::new(caret)
+        } else if caret == start {
+            TextSelection::with_anchor(start + length, start)
+        } else {
+            TextSelection::with_anchor(start, start + length)
+        };
+        selection.clamp(get_grapheme_count(&self.text.get()))
+    }
+
+    pub fn set_selection(&mut self, selection: TextSelection) {
+        self.selection_start.set(selection.get_start());
+        self.selection_length.set(selection.get_length());
+        self.caret_index.set(selection.caret);
+    }
+}
+
+impl EditableText for TextBox {
+    fn get_text(&self) -> String {
+        self.text.get()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text.set(text);
+    }
+
+    fn set_selection(&mut self, selection: TextSelection) {
+        TextBox::set_selection(self, selection);
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.is_read_only.get()
+    }
+
+    fn can_copy(&self) -> bool {
+        !self.is_password.get()
+    }
+
+    fn accept_input(&self, text: &str, selection: &TextSelection) -> String {
+        let text = match self.input_filter {
+            Some(ref input_filter) => input_filter.filter(text),
+            None => text.to_string(),
+        };
+        match self.max_length {
+            Some(max_length) => {
+                let length = get_grapheme_count(&self.text.get()) - selection.get_length();
+                let available = max_length.saturating_sub(length);
+                TextSelection::with_anchor(0, available).get_selected_text(&text)
+            }
+            None => text,
+        }
+    }
+
+    fn clean_pasted_text(&self, text: &str) -> String {
+        // TextBox is single line
+        text.chars().filter(|c| !c.is_control()).collect()
+    }
+
+    // words of the password are not revealed
+    fn find_previous_word_start(&self, text: &str, index: usize) -> usize {
+        if self.is_password.get() {
+            0
+        } else {
+            find_previous_word_start(text, index)
+        }
+    }
+
+    fn find_next_word_start(&self, text: &str, index: usize) -> usize {
+        if self.is_password.get() {
+            get_grapheme_count(text)
+        } else {
+            find_next_word_start(text, index)
+        }
+    }
 }
 
 //
 // Default TextBox Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultTextBoxStyleParams {}
 
 pub struct DefaultTextBoxStyle {
     rect: Rect,
-    is_focused: bool,
+    is_selecting: bool,
+    display_text: String,
     event_subscriptions: Vec<EventSubscription>,
-    font_name: &'static str,
+    font_name: String,
     font_size: u8,
 
-    cursor_pos_char: usize,
-    cursor_pos_px: f32,
+    grapheme_widths: Vec<f32>,
+    text_height: f32,
     offset_x: f32,
+
+    editor: TextEditor,
 }
 
 impl DefaultTextBoxStyle {
@@ -63,202 +228,179 @@ impl DefaultTextBoxStyle {
                 width: 0f32,
                 height: 0f32,
             },
-            is_focused: false,
+            is_selecting: false,
+            display_text: String::new(),
             event_subscriptions: Vec::new(),
-            font_name: "OpenSans-Regular.ttf",
+            font_name: "OpenSans-Regular.ttf".to_string(),
             font_size: 20u8,
 
-            cursor_pos_char: 0,
-            cursor_pos_px: 0.0f32,
+            grapheme_widths: Vec::new(),
+            text_height: 0.0f32,
             offset_x: 0.0f32,
+
+            editor: TextEditor::new(),
         }
     }
 
-    fn calc_cursor_pos(
-        &self,
-        text: &str,
-        pos: &Point,
-        resources: &mut dyn Resources,
-    ) -> (usize, f32) {
-        let (char_widths, _) = resources
-            .get_font_dimensions_each_char(self.font_name, self.font_size, &text)
-            .unwrap_or((Vec::new(), 0));
+    fn get_grapheme_pos_px(&self, grapheme_index: usize) -> f32 {
+        self.grapheme_widths.iter().take(grapheme_index).sum()
+    }
 
-        let pos = (pos.x - self.rect.x - 4.0f32) as i32;
+    fn calc_grapheme_index(&self, pos: &Point) -> usize {
+        let pos = pos.x - self.rect.x - 4.0f32 + self.offset_x;
 
-        let mut cursor_char = 0;
-        let mut cursor_px = 0;
-        while cursor_char < char_widths.len()
-            && pos >= cursor_px + char_widths[cursor_char] as i32 / 2
+        let mut grapheme_index = 0;
+        let mut grapheme_pos_px = 0.0f32;
+        while grapheme_index < self.grapheme_widths.len()
+            && pos >= grapheme_pos_px + self.grapheme_widths[grapheme_index] / 2.0f32
         {
-            cursor_px += char_widths[cursor_char] as i32;
-            cursor_char += 1;
+            grapheme_pos_px += self.grapheme_widths[grapheme_index];
+            grapheme_index += 1;
         }
 
-        (cursor_char, cursor_px as f32)
-    }
-
-    fn calc_cursor_pos_px(
-        &self,
-        text: &str,
-        cursor_pos_char: usize,
-        resources: &mut dyn Resources,
-    ) -> f32 {
-        let subtext: String = text.chars().take(cursor_pos_char).collect();
-        let (text_width, _) = resources
-            .get_font_dimensions(self.font_name, self.font_size, &subtext)
-            .unwrap_or((0, 0));
-        text_width as f32
-    }
-
-    fn move_cursor(&mut self, text: &str, cursor_pos_char: usize, resources: &mut dyn Resources) {
-        let cursor_pos_px = self.calc_cursor_pos_px(&text, cursor_pos_char, resources);
-        self.cursor_pos_char = cursor_pos_char;
-        self.cursor_pos_px = cursor_pos_px;
-
-        self.update_offset_x();
+        grapheme_index
     }
 
-    fn insert_str(&mut self, data: &mut TextBox, text: &str, resources: &mut dyn Resources) {
-        let t = data.text.get();
-        let t: String = t
-            .chars()
-            .take(self.cursor_pos_char)
-            .chain(text.chars())
-            .chain(t.chars().skip(self.cursor_pos_char))
-            .collect();
-
-        let new_cursor_pos_char = self.cursor_pos_char + text.chars().count();
-
-        self.move_cursor(&t, new_cursor_pos_char, resources);
-        data.text.set(t);
+    ///
+    /// Returns the caret rect in window coordinates.
+    ///
+    fn get_caret_rect(&self, data: &TextBox) -> Rect {
+        Rect::new(
+            self.rect.x + 4.0f32 + self.get_grapheme_pos_px(data.get_selection().caret)
+                - self.offset_x,
+            self.rect.y + (self.rect.height - self.text_height) / 2.0,
+            2.0f32,
+            self.text_height,
+        )
     }
 
-    fn remove_char(&mut self, data: &mut TextBox, pos: usize, resources: &mut dyn Resources) {
-        let t = data.text.get();
-        let t: String = t.chars().take(pos).chain(t.chars().skip(pos + 1)).collect();
-
-        if pos < self.cursor_pos_char {
-            self.move_cursor(&t, self.cursor_pos_char - 1, resources);
+    fn update_offset_x(&mut self, caret_index: usize) {
+        if self.editor.is_focused() {
+            let caret_pos_px = self.get_grapheme_pos_px(caret_index);
+            if caret_pos_px < self.offset_x {
+                self.offset_x = caret_pos_px;
+            } else if caret_pos_px > self.offset_x + self.rect.width - 8.0f32 {
+                self.offset_x = caret_pos_px - self.rect.width + 8.0f32 + 2.0f32;
+            }
+        } else {
+            self.offset_x = 0.0f32;
         }
-
-        data.text.set(t);
     }
 
-    fn update_offset_x(&mut self) {
-        if self.is_focused {
-            if self.cursor_pos_px < self.offset_x {
-                self.offset_x = self.cursor_pos_px;
-            } else if self.cursor_pos_px > self.offset_x + self.rect.width - 8.0f32 {
-                self.offset_x = self.cursor_pos_px - self.rect.width + 8.0f32 + 2.0f32;
+    fn handle_key(&mut self, data: &mut TextBox, key_code: &Keycode, modifiers: &KeyModifiers) {
+        let selection = data.get_selection();
+        match key_code {
+            Keycode::Home => {
+                data.set_selection(selection.move_caret(0, modifiers.shift));
             }
-        } else {
-            self.offset_x = 0.0f32;
+            Keycode::End => {
+                let len = get_grapheme_count(&data.text.get());
+                data.set_selection(selection.move_caret(len, modifiers.shift));
+            }
+            _ => (),
         }
     }
 }
 
 impl Style<TextBox> for DefaultTextBoxStyle {
     fn setup(&mut self, data: &mut TextBox, control_context: &mut ControlContext) {
+        control_context
+            .get_attached_values_mut()
+            .entry::<Cursor>()
+            .or_insert(CursorIcon::Text);
+
         self.event_subscriptions
             .push(data.text.dirty_watching(&control_context.get_self_rc()));
+        self.event_subscriptions.push(
+            data.selection_start
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.selection_length
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.caret_index
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.placeholder
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_password
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_read_only
+                .dirty_watching(&control_context.get_self_rc()),
+        );
     }
 
     fn handle_event(
         &mut self,
         data: &mut TextBox,
         control_context: &mut ControlContext,
-        drawing_context: &mut dyn DrawingContext,
-        _event_context: &mut dyn EventContext,
+        _drawing_context: &mut dyn DrawingContext,
+        event_context: &mut dyn EventContext,
         event: ControlEvent,
     ) {
+        if self
+            .editor
+            .handle_focus_event(control_context, event_context, &event)
+        {
+            return;
+        }
+
         match event {
-            ControlEvent::FocusEnter => {
-                self.is_focused = true;
-                control_context.set_is_dirty(true);
+            ControlEvent::TapDown { ref position } => {
+                let grapheme_index = self.calc_grapheme_index(position);
+                data.set_selection(TextSelection::new(grapheme_index));
+                self.is_selecting = true;
+                self.editor
+                    .restart_caret_blink(control_context, event_context);
             }
 
-            ControlEvent::FocusLeave => {
-                self.is_focused = false;
-                control_context.set_is_dirty(true);
+            ControlEvent::TapMove { ref position } if self.is_selecting => {
+                let grapheme_index = self.calc_grapheme_index(position);
+                data.set_selection(data.get_selection().move_caret(grapheme_index, true));
             }
 
-            ControlEvent::TapDown { ref position } => {
-                let cursor_pos = self.calc_cursor_pos(
-                    &data.text.get(),
-                    position,
-                    drawing_context.get_resources(),
-                );
-                self.cursor_pos_char = cursor_pos.0;
-                self.cursor_pos_px = cursor_pos.1;
-                control_context.set_is_dirty(true);
+            ControlEvent::TapUp { .. } => {
+                self.is_selecting = false;
             }
 
-            ControlEvent::KeyboardInput(ref key_event) => {
-                if key_event.state == KeyState::Pressed {
-                    if let Some(ref key_code) = key_event.keycode {
-                        match key_code {
-                            Keycode::Backspace => {
-                                if self.cursor_pos_char > 0 {
-                                    self.remove_char(
-                                        data,
-                                        self.cursor_pos_char - 1,
-                                        drawing_context.get_resources(),
-                                    );
-                                }
-                            }
-                            Keycode::Delete => {
-                                let text = data.text.get();
-                                if self.cursor_pos_char < text.chars().count() {
-                                    self.remove_char(
-                                        data,
-                                        self.cursor_pos_char,
-                                        drawing_context.get_resources(),
-                                    );
-                                }
-                            }
-                            Keycode::Home => {
-                                let text = data.text.get();
-                                if self.cursor_pos_char > 0 {
-                                    self.move_cursor(&text, 0, drawing_context.get_resources());
-                                }
-                            }
-                            Keycode::End => {
-                                let text = data.text.get();
-                                let len = text.chars().count();
-                                if self.cursor_pos_char + 1 <= len {
-                                    self.move_cursor(&text, len, drawing_context.get_resources());
-                                }
-                            }
-                            Keycode::Left => {
-                                let text = data.text.get();
-                                if self.cursor_pos_char > 0 {
-                                    self.move_cursor(
-                                        &text,
-                                        self.cursor_pos_char - 1,
-                                        drawing_context.get_resources(),
-                                    );
-                                }
-                            }
-                            Keycode::Right => {
-                                let text = data.text.get();
-                                if self.cursor_pos_char + 1 <= text.chars().count() {
-                                    self.move_cursor(
-                                        &text,
-                                        self.cursor_pos_char + 1,
-                                        drawing_context.get_resources(),
-                                    );
-                                }
-                            }
-                            _ => (),
-                        }
-                    }
+            ControlEvent::DoubleTap { ref position } => {
+                let text = data.text.get();
+                if data.is_password.get() {
+                    data.set_selection(TextSelection::with_anchor(0, get_grapheme_count(&text)));
+                } else {
+                    let grapheme_index = self.calc_grapheme_index(position);
+                    data.set_selection(find_word_at(&text, grapheme_index));
+                }
+                self.is_selecting = false;
+            }
 
-                    if let Some(ref text) = key_event.text {
-                        self.insert_str(data, &text, drawing_context.get_resources());
+            ControlEvent::KeyboardInput(ref key_event) if key_event.state == KeyState::Pressed => {
+                self.editor
+                    .restart_caret_blink(control_context, event_context);
+
+                if let Some(ref key_code) = key_event.keycode {
+                    let selection = data.get_selection();
+                    if !self.editor.handle_key(
+                        data,
+                        selection,
+                        control_context,
+                        key_code,
+                        &key_event.modifiers,
+                    ) {
+                        self.handle_key(data, key_code, &key_event.modifiers);
                     }
+                }
 
-                    control_context.set_is_dirty(true);
+                if let Some(ref text) = key_event.text {
+                    let selection = data.get_selection();
+                    self.editor.type_text(data, selection, text);
                 }
             }
 
@@ -269,20 +411,56 @@ impl Style<TextBox> for DefaultTextBoxStyle {
     fn measure(
         &mut self,
         data: &mut TextBox,
-        _control_context: &mut ControlContext,
+        control_context: &mut ControlContext,
         drawing_context: &mut dyn DrawingContext,
         _size: Size,
     ) {
-        let (_text_width, text_height) = drawing_context
+        let theme = control_context.get_theme();
+        let inherited_properties = control_context.get_inherited_properties();
+        let font_family = inherited_properties
+            .font_family
+            .unwrap_or_else(|| theme.get_font("Default"));
+        let text = data.text.get();
+        self.display_text = if data.is_password.get() {
+            std::iter::repeat(data.password_char)
+                .take(get_grapheme_count(&text))
+                .collect()
+        } else {
+            text
+        };
+        let text = &self.display_text;
+        self.font_name = drawing_context
+            .get_resources()
+            .resolve_font(
+                &font_family,
+                inherited_properties.font_weight.unwrap_or(400),
+                FontStyle::Normal,
+                text,
+            )
+            .unwrap_or(font_family);
+        self.font_size = inherited_properties
+            .font_size
+            .unwrap_or_else(|| theme.get_size("FontSize")) as u8;
+
+        let (char_widths, text_height) = drawing_context
             .get_resources()
-            .get_font_dimensions(self.font_name, self.font_size, &data.text.get())
-            .unwrap_or((0, 0));
-        self.rect = Rect::new(0.0f32, 0.0f32, 8.0f32 + 8.0f32, text_height as f32 + 8.0f32)
+            .get_font_dimensions_each_char(&self.font_name, self.font_size, text)
+            .unwrap_or((Vec::new(), 0));
+        // widths are per char, caret moves by graphemes
+        let char_widths: Vec<f32> = char_widths.into_iter().map(|width| width as f32).collect();
+        self.grapheme_widths = get_grapheme_widths(text, &char_widths);
+        self.text_height = text_height as f32;
+
+        // caret may be moved by the key or by the bound property
+        self.update_offset_x(data.get_selection().caret);
+
+        self.rect = Rect::new(0.0f32, 0.0f32, 8.0f32 + 8.0f32, self.text_height + 8.0f32)
     }
 
-    fn set_rect(&mut self, _data: &mut TextBox, _control_context: &mut ControlContext, rect: Rect) {
+    fn set_rect(&mut self, data: &mut TextBox, _control_context: &mut ControlContext, rect: Rect) {
         self.rect = rect;
-        self.update_offset_x();
+        self.update_offset_x(data.get_selection().caret);
+        self.editor.set_caret_rect(self.get_caret_rect(data));
     }
 
     fn get_rect(&self, _control_context: &ControlContext) -> Rect {
@@ -305,8 +483,8 @@ impl Style<TextBox> for DefaultTextBoxStyle {
     fn to_primitives(
         &self,
         data: &TextBox,
-        _control_context: &ControlContext,
-        drawing_context: &mut dyn DrawingContext,
+        control_context: &ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
 
@@ -315,56 +493,96 @@ impl Style<TextBox> for DefaultTextBoxStyle {
         let width = self.rect.width;
         let height = self.rect.height;
 
-        let (text_width, text_height) = drawing_context
-            .get_resources()
-            .get_font_dimensions(self.font_name, self.font_size, &data.text.get())
-            .unwrap_or((0, 0));
+        let text = &self.display_text;
+        let text_width = self.get_grapheme_pos_px(self.grapheme_widths.len());
+        let text_height = self.text_height;
+        let text_y = y + (height - text_height) / 2.0;
+        let selection = data.get_selection();
+
+        let theme = control_context.get_theme();
 
-        default_theme::border_3d_edit(&mut vec, x, y, width, height, self.is_focused);
+        default_theme::border_3d_edit(
+            &mut vec,
+            &theme,
+            x,
+            y,
+            width,
+            height,
+            self.editor.is_focused(),
+        );
 
-        gradient_rect(
+        default_theme::brush_rect(
             &mut vec,
             x + 3.0f32,
             y + 3.0f32,
             width - 6.0f32,
             height - 6.0f32,
-            if self.is_focused {
-                [1.0, 1.0, 1.0, 0.75]
-            } else {
-                [1.0, 1.0, 1.0, 0.6]
-            },
-            if self.is_focused {
-                [0.9, 0.9, 0.9, 0.75]
+            if self.editor.is_focused() {
+                theme.get_brush("EditBackgroundFocused")
             } else {
-                [0.9, 0.9, 0.9, 0.6]
+                theme.get_brush("EditBackground")
             },
         );
 
         let mut vec2 = Vec::new();
 
-        vec2.push(Primitive::Text {
-            resource_key: self.font_name.to_string(),
-            color: [0.0, 0.0, 0.0, 1.0],
-            position: PixelPoint::new(x + 4.0f32, y + (height - text_height as f32) / 2.0),
-            clipping_rect: PixelRect::new(
-                PixelPoint::new(x + 4.0f32, y + 4.0f32),
-                PixelSize::new(text_width as f32, height),
+        // selected text is drawn as a separate part
+        // (with its own color on the selection background)
+        let parts = [
+            (0, selection.get_start(), theme.get_color("EditForeground")),
+            (
+                selection.get_start(),
+                selection.get_end(),
+                theme.get_color("SelectionForeground"),
             ),
-            size: Length::new(self.font_size as f32),
-            text: data.text.get(),
-        });
-
-        // draw cursor
-        if self.is_focused {
-            vec2.push(Primitive::Rectangle {
-                color: [1.0, 1.0, 0.0, 1.0],
-                rect: PixelRect::new(
-                    PixelPoint::new(
-                        x + 4.0f32 + self.cursor_pos_px,
-                        y + (height - text_height as f32) / 2.0,
+            (
+                selection.get_end(),
+                self.grapheme_widths.len(),
+                theme.get_color("EditForeground"),
+            ),
+        ];
+        for (index, (start, end, color)) in parts.iter().enumerate() {
+            if start >= end {
+                continue;
+            }
+            let start_px = self.get_grapheme_pos_px(*start);
+            let end_px = self.get_grapheme_pos_px(*end);
+
+            if index == 1 {
+                vec2.push(Primitive::Rectangle {
+                    color: theme.get_color("SelectionBackground"),
+                    rect: PixelRect::new(
+                        PixelPoint::new(x + 4.0f32 + start_px, text_y),
+                        PixelSize::new(end_px - start_px, text_height),
                     ),
-                    PixelSize::new(2.0f32, text_height as f32),
+                });
+            }
+
+            vec2.push(Primitive::Text {
+                resource_key: self.font_name.clone(),
+                color: *color,
+                position: PixelPoint::new(x + 4.0f32 + start_px, text_y),
+                clipping_rect: PixelRect::new(
+                    PixelPoint::new(x + 4.0f32, y + 4.0f32),
+                    PixelSize::new(text_width, height),
+                ),
+                size: Length::new(self.font_size as f32),
+                text: TextSelection::with_anchor(*start, *end).get_selected_text(text),
+            });
+        }
+
+        let placeholder = data.placeholder.get();
+        if text.is_empty() && !self.editor.is_focused() && !placeholder.is_empty() {
+            vec2.push(Primitive::Text {
+                resource_key: self.font_name.clone(),
+                color: theme.get_color("EditPlaceholder"),
+                position: PixelPoint::new(x + 4.0f32, text_y),
+                clipping_rect: PixelRect::new(
+                    PixelPoint::new(x + 4.0f32, y + 4.0f32),
+                    PixelSize::new(width - 8.0f32, height),
                 ),
+                size: Length::new(self.font_size as f32),
+                text: placeholder,
             });
         }
 
@@ -372,6 +590,18 @@ impl Style<TextBox> for DefaultTextBoxStyle {
             vec2.translate(PixelPoint::new(-self.offset_x, 0.0f32));
         }
 
+        // draw cursor
+        if self.editor.is_caret_visible() && !data.is_read_only.get() {
+            let caret_rect = self.get_caret_rect(data);
+            vec2.push(Primitive::Rectangle {
+                color: theme.get_color("Caret"),
+                rect: PixelRect::new(
+                    PixelPoint::new(caret_rect.x, caret_rect.y),
+                    PixelSize::new(caret_rect.width, caret_rect.height),
+                ),
+            });
+        }
+
         vec2 = vec2.clip(PixelRect::new(
             PixelPoint::new(x + 4.0f32, y + 4.0f32),
             PixelSize::new(width - 8.0f32, height - 8.0f32),
@@ -382,3 +612,402 @@ impl Style<TextBox> for DefaultTextBoxStyle {
         (vec, Vec::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use typemap::TypeMap;
+
+    use super::*;
+    use crate::test_helpers::*;
+
+    fn create_view(text_box: TextBox) -> Rc<RefCell<StyledControl<TextBox>>> {
+        text_box.to_view(
+            None,
+            ViewContext {
+                attached_values: TypeMap::new(),
+                children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+            },
+        )
+    }
+
+    fn is_caret_drawn(
+        text_box: &Rc<RefCell<StyledControl<TextBox>>>,
+        drawing_context: &mut TestDrawingContext,
+    ) -> bool {
+        let text_box = text_box.borrow();
+        let caret_color = text_box.get_context().get_theme().get_color("Caret");
+        let (primitives, _) = text_box.to_primitives(drawing_context);
+        primitives.iter().any(|primitive| match primitive {
+            Primitive::Rectangle { color, .. } => *color == caret_color,
+            _ => false,
+        })
+    }
+
+    ///
+    /// The TextBox under test with the other one below it (on another layer).
+    ///
+    struct Fixture {
+        text_box: Rc<RefCell<StyledControl<TextBox>>>,
+        other: Rc<RefCell<StyledControl<TextBox>>>,
+        layers: Vec<Rc<RefCell<dyn ControlObject>>>,
+        processor: EventProcessor,
+        drawing_context: TestDrawingContext,
+        clipboard: Rc<RefCell<MemoryClipboardService>>,
+        _services: Rc<RefCell<Services>>,
+    }
+
+    impl Fixture {
+        fn new(text_box: TextBox) -> Self {
+            let text_box = create_view(text_box);
+            let other = create_view(
+                TextBox::builder()
+                    .text(Property::new(String::new()))
+                    .build(),
+            );
+
+            let clipboard = Rc::new(RefCell::new(MemoryClipboardService::new()));
+            let services = Rc::new(RefCell::new(Services::new()));
+            let clipboard_service: Rc<RefCell<dyn ClipboardService>> = clipboard.clone();
+            services.borrow_mut().register(clipboard_service);
+            text_box
+                .borrow_mut()
+                .get_context_mut()
+                .set_services(Some(Rc::downgrade(&services)));
+
+            let mut fixture = Fixture {
+                layers: vec![text_box.clone(), other.clone()],
+                text_box,
+                other,
+                processor: EventProcessor::new(),
+                drawing_context: TestDrawingContext::new(),
+                clipboard,
+                _services: services,
+            };
+            fixture.layout();
+            fixture
+        }
+
+        fn layout(&mut self) {
+            for (index, layer) in self.layers.iter().enumerate() {
+                let mut layer = layer.borrow_mut();
+                layer.measure(&mut self.drawing_context, Size::new(800.0, 600.0));
+                layer.set_rect(Rect::new(0.0, index as f32 * 28.0, 200.0, 28.0));
+            }
+        }
+
+        ///
+        /// Clears the requests after the window is drawn.
+        ///
+        fn render(&mut self) {
+            for layer in self.layers.iter() {
+                let mut layer = layer.borrow_mut();
+                layer.get_context_mut().set_is_dirty(false);
+                layer.get_context_mut().set_is_redraw_needed(false);
+            }
+        }
+
+        fn handle(&mut self, event: InputEvent) {
+            self.processor
+                .handle_event(&self.layers, &mut self.drawing_context, &event);
+            CallbackExecutor::execute_all_in_queue();
+            self.layout();
+        }
+
+        fn handle_timers(&mut self, time: Instant) {
+            self.processor
+                .handle_timers(&self.layers, &mut self.drawing_context, time);
+            CallbackExecutor::execute_all_in_queue();
+        }
+
+        fn focus(&mut self) {
+            self.tap(Point::new(100.0, 14.0));
+        }
+
+        fn focus_other(&mut self) {
+            self.tap(Point::new(100.0, 42.0));
+        }
+
+        fn tap(&mut self, position: Point) {
+            self.handle(InputEvent::CursorMoved { position });
+            self.handle(InputEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+            });
+            self.handle(InputEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+            });
+        }
+
+        fn type_text(&mut self, text: &str) {
+            self.handle(InputEvent::KeyboardInput(KeyEvent {
+                state: KeyState::Pressed,
+                keycode: None,
+                text: Some(text.to_string()),
+                modifiers: KeyModifiers::default(),
+            }));
+        }
+
+        fn press(&mut self, keycode: Keycode) {
+            self.handle(key_pressed(keycode));
+        }
+
+        fn press_ctrl(&mut self, keycode: Keycode) {
+            self.handle(key_pressed_with_modifiers(
+                keycode,
+                KeyModifiers {
+                    ctrl: true,
+                    ..KeyModifiers::default()
+                },
+            ));
+        }
+
+        fn get_text(&self) -> String {
+            self.text_box.borrow().data.text.get()
+        }
+
+        fn is_caret_drawn(&mut self) -> bool {
+            is_caret_drawn(&self.text_box, &mut self.drawing_context)
+        }
+
+        fn get_drawn_texts(&mut self) -> Vec<String> {
+            let (primitives, _) = self
+                .text_box
+                .borrow()
+                .to_primitives(&mut self.drawing_context);
+            primitives
+                .into_iter()
+                .filter_map(|primitive| match primitive {
+                    Primitive::Text { text, .. } => Some(text),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_password_is_masked() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .is_password(Property::new(true))
+                .password_char('*')
+                .build(),
+        );
+        fixture.focus();
+        fixture.type_text("pa");
+        fixture.type_text("e\u{301}");
+
+        assert_eq!(fixture.get_text(), "pae\u{301}");
+        // one password char per grapheme
+        assert_eq!(fixture.get_drawn_texts(), vec!["***".to_string()]);
+    }
+
+    #[test]
+    fn test_password_cannot_be_copied_or_cut() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new("secret".to_string()))
+                .is_password(Property::new(true))
+                .build(),
+        );
+        fixture.clipboard.borrow_mut().set_text("clip");
+        fixture.focus();
+        fixture.press_ctrl(Keycode::A);
+
+        fixture.press_ctrl(Keycode::C);
+        fixture.press_ctrl(Keycode::X);
+        assert_eq!(
+            fixture.clipboard.borrow().get_text(),
+            Some("clip".to_string())
+        );
+        assert_eq!(fixture.get_text(), "secret");
+
+        // plain text is copied
+        fixture.text_box.borrow_mut().data.is_password.set(false);
+        fixture.press_ctrl(Keycode::C);
+        assert_eq!(
+            fixture.clipboard.borrow().get_text(),
+            Some("secret".to_string())
+        );
+    }
+
+    #[test]
+    fn test_placeholder_is_shown_when_empty_and_not_focused() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .placeholder(Property::new("Name".to_string()))
+                .build(),
+        );
+        assert_eq!(fixture.get_drawn_texts(), vec!["Name".to_string()]);
+
+        fixture.focus();
+        assert!(fixture.get_drawn_texts().is_empty());
+
+        fixture.type_text("Jo");
+        assert_eq!(fixture.get_drawn_texts(), vec!["Jo".to_string()]);
+    }
+
+    #[test]
+    fn test_max_length_counts_graphemes() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .max_length(Some(3))
+                .build(),
+        );
+        fixture.clipboard.borrow_mut().set_text("e\u{301}xyz");
+        fixture.focus();
+        fixture.type_text("a");
+
+        // the pasted text is truncated
+        fixture.press_ctrl(Keycode::V);
+        assert_eq!(fixture.get_text(), "ae\u{301}x");
+
+        fixture.type_text("b");
+        assert_eq!(fixture.get_text(), "ae\u{301}x");
+
+        // only the graphemes that fit are typed
+        fixture.press(Keycode::Backspace);
+        fixture.type_text("bc");
+        assert_eq!(fixture.get_text(), "ae\u{301}b");
+    }
+
+    #[test]
+    fn test_read_only_text_is_not_edited() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new("abc".to_string()))
+                .is_read_only(Property::new(true))
+                .build(),
+        );
+        fixture.clipboard.borrow_mut().set_text("clip");
+        fixture.focus();
+
+        fixture.type_text("x");
+        fixture.press(Keycode::Backspace);
+        fixture.press_ctrl(Keycode::V);
+        fixture.press_ctrl(Keycode::A);
+        fixture.press(Keycode::Delete);
+        fixture.press_ctrl(Keycode::X);
+        assert_eq!(fixture.get_text(), "abc");
+
+        // the text can still be copied
+        fixture.press_ctrl(Keycode::C);
+        assert_eq!(
+            fixture.clipboard.borrow().get_text(),
+            Some("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_input_filter_rejects_text() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .input_filter(Some(InputFilter::digits()))
+                .build(),
+        );
+        fixture.clipboard.borrow_mut().set_text("4a5");
+        fixture.focus();
+
+        fixture.type_text("a");
+        assert_eq!(fixture.get_text(), "");
+
+        fixture.type_text("1");
+        fixture.type_text("x");
+        fixture.press_ctrl(Keycode::V);
+        assert_eq!(fixture.get_text(), "145");
+        assert_eq!(fixture.text_box.borrow().data.get_selection().caret, 3);
+    }
+
+    #[test]
+    fn test_caret_blinks_and_is_shown_while_typing() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .build(),
+        );
+        fixture.focus();
+        assert!(fixture.is_caret_drawn());
+
+        let time = fixture.processor.get_next_timer().unwrap();
+        fixture.render();
+        fixture.handle_timers(time);
+        assert!(!fixture.is_caret_drawn());
+
+        // only the caret is drawn again
+        let redraw_rect = fixture.layers[0].borrow().get_context().get_redraw_rect();
+        assert_eq!(redraw_rect, Some(Rect::new(4.0, 4.0, 2.0, 20.0)));
+
+        // typing shows the caret and starts blinking from the beginning
+        fixture.type_text("a");
+        assert!(fixture.is_caret_drawn());
+        let next_time = fixture.processor.get_next_timer().unwrap();
+        assert!(next_time >= time);
+        fixture.handle_timers(next_time - Duration::from_millis(1));
+        assert!(fixture.is_caret_drawn());
+        fixture.handle_timers(next_time);
+        assert!(!fixture.is_caret_drawn());
+    }
+
+    #[test]
+    fn test_caret_stops_blinking_on_focus_leave() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .build(),
+        );
+        fixture.focus();
+        fixture.focus_other();
+        assert!(!fixture.is_caret_drawn());
+
+        // only the other TextBox blinks
+        let other = fixture.other.clone();
+        assert!(is_caret_drawn(&other, &mut fixture.drawing_context));
+        let time = fixture.processor.get_next_timer().unwrap();
+        fixture.handle_timers(time);
+        assert!(!is_caret_drawn(&other, &mut fixture.drawing_context));
+        let time = fixture.processor.get_next_timer().unwrap();
+        fixture.handle_timers(time);
+        assert!(is_caret_drawn(&other, &mut fixture.drawing_context));
+        assert!(!fixture.is_caret_drawn());
+    }
+
+    #[test]
+    fn test_typed_text_is_undone_by_words() {
+        let mut fixture = Fixture::new(
+            TextBox::builder()
+                .text(Property::new(String::new()))
+                .build(),
+        );
+        fixture.focus();
+        for c in "one two".chars() {
+            fixture.type_text(&c.to_string());
+        }
+
+        fixture.press_ctrl(Keycode::Z);
+        assert_eq!(fixture.get_text(), "one ");
+        assert_eq!(
+            fixture.text_box.borrow().data.get_selection(),
+            TextSelection::new(4)
+        );
+        fixture.press_ctrl(Keycode::Z);
+        assert_eq!(fixture.get_text(), "");
+
+        fixture.press_ctrl(Keycode::Y);
+        assert_eq!(fixture.get_text(), "one ");
+        fixture.handle(key_pressed_with_modifiers(
+            Keycode::Z,
+            KeyModifiers {
+                ctrl: true,
+                shift: true,
+                ..KeyModifiers::default()
+            },
+        ));
+        assert_eq!(fixture.get_text(), "one two");
+    }
+}
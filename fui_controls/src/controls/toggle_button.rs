@@ -27,7 +27,7 @@ impl ToggleButton {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultToggleButtonStyle::new(
+                RegisteredStyle::with_default(DefaultToggleButtonStyle::new(
                     DefaultToggleButtonStyleParams::builder().build(),
                 ))
             }),
@@ -40,7 +40,7 @@ impl ToggleButton {
 // Default ToggleButton Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultToggleButtonStyleParams {}
 
 pub struct DefaultToggleButtonStyle {
@@ -203,6 +203,7 @@ impl Style<ToggleButton> for DefaultToggleButtonStyle {
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
         let mut overlay = Vec::new();
+        let theme = control_context.get_theme();
 
         let x = self.rect.x;
         let y = self.rect.y;
@@ -217,6 +218,7 @@ impl Style<ToggleButton> for DefaultToggleButtonStyle {
 
         default_theme::button(
             &mut vec,
+            &theme,
             x,
             y,
             width,
@@ -247,7 +249,7 @@ impl Style<ToggleButton> for DefaultToggleButtonStyle {
 const CHECKBOX_BUTTON_SIZE: f32 = 24.0f32;
 const CHECKBOX_MARGIN: f32 = 6.0f32;
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct CheckBoxToggleButtonStyleParams {}
 
 pub struct CheckBoxToggleButtonStyle {
@@ -422,6 +424,7 @@ impl Style<ToggleButton> for CheckBoxToggleButtonStyle {
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
         let mut overlay = Vec::new();
+        let theme = control_context.get_theme();
 
         let x = self.rect.x;
         let y = self.rect.y;
@@ -435,11 +438,12 @@ impl Style<ToggleButton> for CheckBoxToggleButtonStyle {
 
         default_theme::button_rounded(
             &mut vec,
+            &theme,
             x,
             y,
             CHECKBOX_BUTTON_SIZE,
             height,
-            3.0f32,
+            theme.get_corner_radius("Button"),
             is_pressed,
             self.is_hover.get(),
             self.is_focused.get(),
@@ -464,7 +468,7 @@ impl Style<ToggleButton> for CheckBoxToggleButtonStyle {
                 path: tick_path,
                 thickness: PixelThickness::new(2.0f32),
                 brush: Brush::Color {
-                    color: [1.0f32, 1.0f32, 1.0f32, 0.80f32],
+                    color: theme.get_color("CheckMark"),
                 },
             });
         }
@@ -488,7 +492,7 @@ impl Style<ToggleButton> for CheckBoxToggleButtonStyle {
 // (cannot be unpressed).
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct TabToggleButtonStyleParams {}
 
 pub struct TabToggleButtonStyle {
@@ -651,6 +655,7 @@ impl Style<ToggleButton> for TabToggleButtonStyle {
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
         let mut overlay = Vec::new();
+        let theme = control_context.get_theme();
 
         let x = self.rect.x;
         let y = self.rect.y;
@@ -665,6 +670,7 @@ impl Style<ToggleButton> for TabToggleButtonStyle {
 
         default_theme::button(
             &mut vec,
+            &theme,
             x,
             y,
             width,
@@ -697,7 +703,7 @@ const RADIO_BUTTON_SIZE: f32 = 24.0f32;
 const RADIO_BULLET_SIZE: f32 = 14.0f32;
 const RADIO_MARGIN: f32 = 6.0f32;
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct RadioToggleButtonStyleParams {}
 
 pub struct RadioToggleButtonStyle {
@@ -872,6 +878,7 @@ impl Style<ToggleButton> for RadioToggleButtonStyle {
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
         let mut overlay = Vec::new();
+        let theme = control_context.get_theme();
 
         let x = self.rect.x;
         let y = self.rect.y;
@@ -885,11 +892,12 @@ impl Style<ToggleButton> for RadioToggleButtonStyle {
 
         default_theme::button_rounded(
             &mut vec,
+            &theme,
             x,
             y,
             RADIO_BUTTON_SIZE,
             height,
-            3.0f32,
+            theme.get_corner_radius("Button"),
             is_pressed,
             self.is_hover.get(),
             self.is_focused.get(),
@@ -902,7 +910,7 @@ impl Style<ToggleButton> for RadioToggleButtonStyle {
                     RADIO_BULLET_SIZE / 2.0f32,
                 ),
                 brush: Brush::Color {
-                    color: [1.0f32, 1.0f32, 1.0f32, 0.80f32],
+                    color: theme.get_color("CheckMark"),
                 },
             });
         }
@@ -927,7 +935,7 @@ impl Style<ToggleButton> for RadioToggleButtonStyle {
 // emit clicked event).
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DropDownToggleButtonStyleParams {
     #[builder(default = Callback::empty())]
     pub clicked: Callback<()>,
@@ -1096,6 +1104,7 @@ impl Style<ToggleButton> for DropDownToggleButtonStyle {
     ) -> (Vec<Primitive>, Vec<Primitive>) {
         let mut vec = Vec::new();
         let mut overlay = Vec::new();
+        let theme = control_context.get_theme();
 
         let x = self.rect.x;
         let y = self.rect.y;
@@ -1110,6 +1119,7 @@ impl Style<ToggleButton> for DropDownToggleButtonStyle {
 
         default_theme::button(
             &mut vec,
+            &theme,
             x,
             y,
             width,
@@ -0,0 +1,1231 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use drawing::primitive::{Brush, PathElement, Primitive};
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use fui_core::*;
+use fui_macros::ui;
+use typed_builder::TypedBuilder;
+use typemap::TypeMap;
+
+use crate::{layout::*, DataHolder, ListScrollController};
+
+const PADDING: f32 = 2.0f32;
+const INDENT_SIZE: f32 = 16.0f32;
+const EXPANDER_SIZE: f32 = 16.0f32;
+const EXPANDER_GLYPH_SIZE: f32 = 8.0f32;
+
+///
+/// View model of the TreeView item.
+///
+pub trait HierarchicalViewModel: ViewModel + Sized + 'static {
+    ///
+    /// Returns the children of the item. It is called when the item
+    /// is expanded for the first time, so the children can be loaded lazily.
+    ///
+    fn get_children(&mut self) -> &dyn ObservableCollection<Rc<RefCell<Self>>>;
+
+    fn get_is_expanded(&mut self) -> &mut Property<bool>;
+
+    ///
+    /// Tells if the expander is shown before the children are loaded.
+    ///
+    fn has_children(&self) -> bool {
+        true
+    }
+}
+
+//
+// TreeView.
+//
+
+///
+/// Tree of hierarchical view models.
+///
+/// Children of the item are loaded when it is expanded for the first time.
+/// Up / Down / Home / End / PageUp / PageDown keys move the selection,
+/// Left collapses the item or goes to its parent and Right expands the item
+/// or goes to its first child. Double-click or Enter emits the item_activated callback.
+///
+#[derive(TypedBuilder)]
+pub struct TreeView<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    #[builder(default = Box::new(Vec::<Rc<RefCell<V>>>::new()))]
+    pub items: Box<dyn ObservableCollection<Rc<RefCell<V>>>>,
+
+    #[builder(default = Property::new(None))]
+    pub selected_item: Property<Option<Rc<RefCell<V>>>>,
+
+    #[builder(default = Callback::empty())]
+    pub item_activated: Callback<Rc<RefCell<V>>>,
+}
+
+impl<V> TreeView<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    pub fn to_view(
+        mut self,
+        _style: Option<Box<dyn Style<Self>>>,
+        mut context: ViewContext,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let controller_rc = TreeController::new(
+            Property::binded_two_way(&mut self.selected_item),
+            self.item_activated,
+        );
+
+        {
+            let mut controller = controller_rc.borrow_mut();
+            let sources = (&*self.items).into_iter().collect();
+            controller.set_roots(sources);
+
+            let weak = Rc::downgrade(&controller_rc);
+            if let Some(subscription) = self.items.on_changed(Box::new(move |changed_args| {
+                if let Some(controller) = weak.upgrade() {
+                    controller
+                        .borrow_mut()
+                        .on_children_changed(None, changed_args);
+                }
+            })) {
+                controller.event_subscriptions.push(subscription);
+            }
+
+            let weak = Rc::downgrade(&controller_rc);
+            let subscription = controller.selected_item.on_changed(move |item| {
+                if let Some(controller) = weak.upgrade() {
+                    controller.borrow_mut().on_selected_item_changed(item);
+                }
+            });
+            controller.event_subscriptions.push(subscription);
+
+            let selected_item = controller.selected_item.get();
+            controller.on_selected_item_changed(selected_item);
+        }
+
+        let panel = ui! {
+            Grid {
+                columns: 1,
+                &controller_rc.borrow().rows,
+            }
+        };
+        let content = controller_rc
+            .borrow_mut()
+            .scroll_controller
+            .create_view(panel);
+
+        let controller_clone = controller_rc.clone();
+        let event_filter = EventFilterHandler::new(move |event| match event {
+            ControlEvent::KeyboardInput(key_event) if key_event.state == KeyState::Pressed => {
+                controller_clone.borrow_mut().handle_key(key_event)
+            }
+
+            ControlEvent::MouseWheel { delta, .. } => {
+                controller_clone
+                    .borrow_mut()
+                    .scroll_controller
+                    .scroll_by_wheel(delta.y);
+                true
+            }
+
+            _ => false,
+        });
+        context.attached_values.insert::<EventFilter>(event_filter);
+
+        let data_holder = DataHolder {
+            data: (self.selected_item, self.items, controller_rc),
+        };
+        data_holder.to_view(
+            None,
+            ViewContext {
+                attached_values: context.attached_values,
+                children: Box::new(vec![content as Rc<RefCell<dyn ControlObject>>]),
+            },
+        )
+    }
+}
+
+///
+/// Keeps the tree of nodes and the flat list of the visible rows.
+///
+struct TreeController<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    self_weak: Weak<RefCell<TreeController<V>>>,
+    roots: Vec<Rc<RefCell<TreeNode<V>>>>,
+    rows: ObservableVec<Rc<RefCell<TreeNode<V>>>>,
+    selected: Option<Rc<RefCell<TreeNode<V>>>>,
+    selected_item: Property<Option<Rc<RefCell<V>>>>,
+    item_activated: Callback<Rc<RefCell<V>>>,
+    scroll_controller: ListScrollController,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl<V> TreeController<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    fn new(
+        selected_item: Property<Option<Rc<RefCell<V>>>>,
+        item_activated: Callback<Rc<RefCell<V>>>,
+    ) -> Rc<RefCell<Self>> {
+        let controller_rc = Rc::new(RefCell::new(TreeController {
+            self_weak: Weak::new(),
+            roots: Vec::new(),
+            rows: ObservableVec::new(),
+            selected: None,
+            selected_item,
+            item_activated,
+            scroll_controller: ListScrollController::new(),
+            event_subscriptions: Vec::new(),
+        }));
+        controller_rc.borrow_mut().self_weak = Rc::downgrade(&controller_rc);
+        controller_rc
+    }
+
+    fn set_roots(&mut self, sources: Vec<Rc<RefCell<V>>>) {
+        self.roots = sources
+            .into_iter()
+            .map(|source| self.create_node(source, 0, None))
+            .collect();
+
+        let mut rows = Vec::new();
+        for root in self.roots.iter() {
+            collect_rows(root, &mut rows);
+        }
+        for row in rows {
+            self.rows.push(row);
+        }
+    }
+
+    fn create_node(
+        &self,
+        source_vm: Rc<RefCell<V>>,
+        depth: usize,
+        parent: Option<&Rc<RefCell<TreeNode<V>>>>,
+    ) -> Rc<RefCell<TreeNode<V>>> {
+        let (is_expanded, has_children) = {
+            let mut vm = source_vm.borrow_mut();
+            (
+                Property::binded_two_way(vm.get_is_expanded()),
+                vm.has_children(),
+            )
+        };
+
+        let node_rc = Rc::new(RefCell::new(TreeNode {
+            source_vm,
+            depth,
+            parent: parent.map(Rc::downgrade),
+            children: None,
+            is_expanded,
+            is_children_shown: false,
+            is_selected: Property::new(false),
+            has_children: Property::new(has_children),
+            clicked: Callback::empty(),
+            expander_clicked: Callback::empty(),
+            double_clicked: Callback::empty(),
+            view: None,
+            event_subscriptions: Vec::new(),
+        }));
+
+        {
+            let mut node = node_rc.borrow_mut();
+
+            let weak_node = Rc::downgrade(&node_rc);
+            let controller = self.self_weak.clone();
+            node.clicked.set(move |_| {
+                if let (Some(node), Some(controller)) = (weak_node.upgrade(), controller.upgrade())
+                {
+                    controller.borrow_mut().select(Some(node));
+                }
+            });
+
+            let weak_node = Rc::downgrade(&node_rc);
+            node.expander_clicked.set(move |_| {
+                if let Some(node) = weak_node.upgrade() {
+                    toggle_expanded(&node);
+                }
+            });
+
+            let weak_node = Rc::downgrade(&node_rc);
+            let controller = self.self_weak.clone();
+            node.double_clicked.set(move |_| {
+                if let (Some(node), Some(controller)) = (weak_node.upgrade(), controller.upgrade())
+                {
+                    toggle_expanded(&node);
+                    controller.borrow_mut().activate(&node);
+                }
+            });
+
+            let weak_node = Rc::downgrade(&node_rc);
+            let controller = self.self_weak.clone();
+            let subscription = node.is_expanded.on_changed(move |is_expanded| {
+                if let (Some(node), Some(controller)) = (weak_node.upgrade(), controller.upgrade())
+                {
+                    controller
+                        .borrow_mut()
+                        .on_expanded_changed(&node, is_expanded);
+                }
+            });
+            node.event_subscriptions.push(subscription);
+        }
+
+        if node_rc.borrow().is_expanded.get() {
+            self.load_children(&node_rc);
+            node_rc.borrow_mut().is_children_shown = true;
+        }
+
+        node_rc
+    }
+
+    fn load_children(&self, node_rc: &Rc<RefCell<TreeNode<V>>>) {
+        if node_rc.borrow().children.is_some() {
+            return;
+        }
+
+        let source_vm = node_rc.borrow().source_vm.clone();
+        let depth = node_rc.borrow().depth + 1;
+        let (sources, subscription) = {
+            let mut vm = source_vm.borrow_mut();
+            let children = vm.get_children();
+            let sources: Vec<Rc<RefCell<V>>> = children.into_iter().collect();
+
+            let weak_node = Rc::downgrade(node_rc);
+            let controller = self.self_weak.clone();
+            let subscription = children.on_changed(Box::new(move |changed_args| {
+                if let (Some(node), Some(controller)) = (weak_node.upgrade(), controller.upgrade())
+                {
+                    controller
+                        .borrow_mut()
+                        .on_children_changed(Some(&node), changed_args);
+                }
+            }));
+            (sources, subscription)
+        };
+
+        let children: Vec<Rc<RefCell<TreeNode<V>>>> = sources
+            .into_iter()
+            .map(|source| self.create_node(source, depth, Some(node_rc)))
+            .collect();
+
+        let mut node = node_rc.borrow_mut();
+        node.has_children.set(!children.is_empty());
+        node.children = Some(children);
+        if let Some(subscription) = subscription {
+            node.event_subscriptions.push(subscription);
+        }
+    }
+
+    ///
+    /// Returns the node of the item. Children which are not loaded yet are loaded
+    /// only when the item is not found among the loaded nodes.
+    ///
+    fn find_node(&self, item: &Rc<RefCell<V>>) -> Option<Rc<RefCell<TreeNode<V>>>> {
+        self.roots
+            .iter()
+            .find_map(|root| find_loaded_node(root, item))
+            .or_else(|| {
+                self.roots
+                    .iter()
+                    .find_map(|root| self.load_until_found(root, item))
+            })
+    }
+
+    fn load_until_found(
+        &self,
+        node_rc: &Rc<RefCell<TreeNode<V>>>,
+        item: &Rc<RefCell<V>>,
+    ) -> Option<Rc<RefCell<TreeNode<V>>>> {
+        if Rc::ptr_eq(&node_rc.borrow().source_vm, item) {
+            return Some(node_rc.clone());
+        }
+        if node_rc.borrow().has_children.get() {
+            self.load_children(node_rc);
+        }
+        let children = node_rc.borrow().children.clone().unwrap_or_default();
+        children
+            .iter()
+            .find_map(|child| self.load_until_found(child, item))
+    }
+
+    fn get_row_index(&self, node: &Rc<RefCell<TreeNode<V>>>) -> Option<usize> {
+        (&self.rows)
+            .into_iter()
+            .position(|row| Rc::ptr_eq(row, node))
+    }
+
+    fn get_children(
+        &self,
+        parent: Option<&Rc<RefCell<TreeNode<V>>>>,
+    ) -> Vec<Rc<RefCell<TreeNode<V>>>> {
+        match parent {
+            Some(parent) => parent.borrow().children.clone().unwrap_or_default(),
+            None => self.roots.clone(),
+        }
+    }
+
+    ///
+    /// Returns the row index of the parent's child at the index
+    /// or None when the parent's children are not visible.
+    ///
+    fn get_child_row_index(
+        &self,
+        parent: Option<&Rc<RefCell<TreeNode<V>>>>,
+        index: usize,
+    ) -> Option<usize> {
+        let start = match parent {
+            Some(parent) => {
+                if !parent.borrow().is_children_shown {
+                    return None;
+                }
+                self.get_row_index(parent)? + 1
+            }
+            None => 0,
+        };
+        Some(
+            start
+                + self
+                    .get_children(parent)
+                    .iter()
+                    .take(index)
+                    .map(get_visible_count)
+                    .sum::<usize>(),
+        )
+    }
+
+    fn on_expanded_changed(&mut self, node_rc: &Rc<RefCell<TreeNode<V>>>, is_expanded: bool) {
+        if node_rc.borrow().is_children_shown == is_expanded {
+            return;
+        }
+
+        if is_expanded {
+            self.load_children(node_rc);
+            node_rc.borrow_mut().is_children_shown = true;
+            if let Some(index) = self.get_row_index(node_rc) {
+                let mut rows = Vec::new();
+                collect_rows(node_rc, &mut rows);
+                for (i, row) in rows.into_iter().enumerate().skip(1) {
+                    self.rows.insert(index + i, row);
+                }
+            }
+        } else {
+            if let Some(index) = self.get_row_index(node_rc) {
+                for _ in 1..get_visible_count(node_rc) {
+                    self.rows.remove(index + 1);
+                }
+            }
+            node_rc.borrow_mut().is_children_shown = false;
+            self.ensure_selection_visible(Some(node_rc));
+        }
+    }
+
+    fn on_children_changed(
+        &mut self,
+        parent: Option<&Rc<RefCell<TreeNode<V>>>>,
+        changed_args: ObservableChangedEventArgs<Rc<RefCell<V>>>,
+    ) {
+        match changed_args {
+            ObservableChangedEventArgs::Insert { index, value } => {
+                let depth = parent.map_or(0, |parent| parent.borrow().depth + 1);
+                let node = self.create_node(value, depth, parent);
+                let row_index = self.get_child_row_index(parent, index);
+
+                match parent {
+                    Some(parent) => {
+                        let mut parent = parent.borrow_mut();
+                        if let Some(ref mut children) = parent.children {
+                            children.insert(index, node.clone());
+                        }
+                        parent.has_children.set(true);
+                    }
+                    None => self.roots.insert(index, node.clone()),
+                }
+
+                if let Some(row_index) = row_index {
+                    let mut rows = Vec::new();
+                    collect_rows(&node, &mut rows);
+                    for (i, row) in rows.into_iter().enumerate() {
+                        self.rows.insert(row_index + i, row);
+                    }
+                }
+            }
+
+            ObservableChangedEventArgs::Remove { index } => {
+                let row_index = self.get_child_row_index(parent, index);
+
+                let node = match parent {
+                    Some(parent) => {
+                        let mut parent = parent.borrow_mut();
+                        let node = match parent.children {
+                            Some(ref mut children) => children.remove(index),
+                            None => return,
+                        };
+                        let has_children = parent.children.as_ref().is_some_and(|c| !c.is_empty());
+                        parent.has_children.set(has_children);
+                        node
+                    }
+                    None => self.roots.remove(index),
+                };
+
+                if let Some(row_index) = row_index {
+                    for _ in 0..get_visible_count(&node) {
+                        self.rows.remove(row_index);
+                    }
+                }
+                self.ensure_selection_visible(parent);
+            }
+        }
+    }
+
+    ///
+    /// Moves the selection to the fallback node when the selected node is no longer visible.
+    ///
+    fn ensure_selection_visible(&mut self, fallback: Option<&Rc<RefCell<TreeNode<V>>>>) {
+        let is_hidden = match self.selected {
+            Some(ref selected) => self.get_row_index(selected).is_none(),
+            None => false,
+        };
+        if is_hidden {
+            self.select(fallback.cloned());
+        }
+    }
+
+    fn on_selected_item_changed(&mut self, item: Option<Rc<RefCell<V>>>) {
+        let is_same = match (&self.selected, &item) {
+            (Some(selected), Some(item)) => Rc::ptr_eq(&selected.borrow().source_vm, item),
+            (None, None) => true,
+            _ => false,
+        };
+        if is_same {
+            return;
+        }
+
+        let node = item.and_then(|item| self.find_node(&item));
+
+        // expand the parents (starting from the root), so the item has its row
+        // before it is selected and scrolled into view
+        if let Some(ref node) = node {
+            let mut parents = Vec::new();
+            let mut parent = node.borrow().parent.as_ref().and_then(|p| p.upgrade());
+            while let Some(p) = parent {
+                parent = p.borrow().parent.as_ref().and_then(|p| p.upgrade());
+                parents.push(p);
+            }
+            for parent in parents.iter().rev() {
+                // rows are inserted now, the queued notification finds them shown
+                parent.borrow_mut().is_expanded.set(true);
+                self.on_expanded_changed(parent, true);
+            }
+        }
+
+        self.select(node);
+        if let Some(index) = self.selected.as_ref().and_then(|n| self.get_row_index(n)) {
+            self.scroll_into_view(index);
+        }
+    }
+
+    fn select(&mut self, node: Option<Rc<RefCell<TreeNode<V>>>>) {
+        let is_same = match (&self.selected, &node) {
+            (Some(selected), Some(node)) => Rc::ptr_eq(selected, node),
+            (None, None) => true,
+            _ => false,
+        };
+        if is_same {
+            return;
+        }
+
+        if let Some(ref selected) = self.selected {
+            selected.borrow_mut().is_selected.set(false);
+        }
+        if let Some(ref node) = node {
+            node.borrow_mut().is_selected.set(true);
+        }
+        self.selected = node;
+
+        let item = self
+            .selected
+            .as_ref()
+            .map(|node| node.borrow().source_vm.clone());
+        let is_changed = match (self.selected_item.get(), &item) {
+            (Some(old_item), Some(new_item)) => !Rc::ptr_eq(&old_item, new_item),
+            (old_item, new_item) => old_item.is_some() != new_item.is_some(),
+        };
+        if is_changed {
+            self.selected_item.set(item);
+        }
+    }
+
+    fn activate(&mut self, node: &Rc<RefCell<TreeNode<V>>>) {
+        self.item_activated.emit(node.borrow().source_vm.clone());
+    }
+
+    fn handle_key(&mut self, key_event: &KeyEvent) -> bool {
+        let len = self.rows.len();
+        if len == 0 {
+            return false;
+        }
+        let current = self.selected.as_ref().and_then(|n| self.get_row_index(n));
+
+        let index = key_event.keycode.as_ref().and_then(|keycode| {
+            self.scroll_controller.get_navigation_index(
+                keycode,
+                current,
+                current.and_then(|index| self.get_row_view(index)),
+                len,
+            )
+        });
+        let index = match (index, &key_event.keycode) {
+            (Some(index), _) => index,
+
+            (None, Some(Keycode::Left)) => {
+                let node = match (current, self.selected.clone()) {
+                    (Some(_), Some(node)) => node,
+                    _ => return false,
+                };
+                let is_expanded = {
+                    let node = node.borrow();
+                    node.is_expanded.get() && node.has_children.get()
+                };
+                if is_expanded {
+                    node.borrow_mut().is_expanded.set(false);
+                    return true;
+                }
+                let parent = node.borrow().parent.as_ref().and_then(|p| p.upgrade());
+                match parent.and_then(|parent| self.get_row_index(&parent)) {
+                    Some(index) => index,
+                    None => return true,
+                }
+            }
+
+            (None, Some(Keycode::Right)) => {
+                let (index, node) = match (current, self.selected.clone()) {
+                    (Some(index), Some(node)) => (index, node),
+                    _ => return false,
+                };
+                let (is_expanded, has_children, is_children_shown) = {
+                    let node = node.borrow();
+                    (
+                        node.is_expanded.get(),
+                        node.has_children.get(),
+                        node.is_children_shown,
+                    )
+                };
+                if !has_children {
+                    return true;
+                }
+                if !is_expanded {
+                    node.borrow_mut().is_expanded.set(true);
+                    return true;
+                }
+                if !is_children_shown || get_visible_count(&node) < 2 {
+                    return true;
+                }
+                index + 1
+            }
+
+            (None, Some(Keycode::Enter)) | (None, Some(Keycode::NumpadEnter)) => {
+                if let Some(node) = self.selected.clone() {
+                    self.activate(&node);
+                }
+                return true;
+            }
+
+            _ => return false,
+        };
+
+        self.select(Some(self.rows.get(index)));
+        self.scroll_into_view(index);
+        true
+    }
+
+    fn get_row_view(&self, index: usize) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        self.rows.get(index).borrow().view.as_ref()?.upgrade()
+    }
+
+    fn scroll_into_view(&mut self, index: usize) {
+        if let Some(view) = self.get_row_view(index) {
+            self.scroll_controller.scroll_into_view(&view);
+        }
+    }
+}
+
+struct TreeNode<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    source_vm: Rc<RefCell<V>>,
+    depth: usize,
+    parent: Option<Weak<RefCell<TreeNode<V>>>>,
+
+    // None until the item is expanded for the first time
+    children: Option<Vec<Rc<RefCell<TreeNode<V>>>>>,
+
+    is_expanded: Property<bool>,
+    is_children_shown: bool,
+    is_selected: Property<bool>,
+    has_children: Property<bool>,
+
+    clicked: Callback<()>,
+    expander_clicked: Callback<()>,
+    double_clicked: Callback<()>,
+
+    view: Option<Weak<RefCell<dyn ControlObject>>>,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl<V> ViewModel for TreeNode<V>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+        let mut node = view_model.borrow_mut();
+        let content = ViewModel::create_view(&node.source_vm);
+        let view: Rc<RefCell<dyn ControlObject>> = ui! {
+            TreeViewItem {
+                depth: node.depth,
+                has_children: &node.has_children,
+                is_expanded: &node.is_expanded,
+                is_selected: &node.is_selected,
+                clicked: node.clicked.clone(),
+                expander_clicked: node.expander_clicked.clone(),
+                double_clicked: node.double_clicked.clone(),
+                @content,
+            }
+        };
+        node.view = Some(Rc::downgrade(&view));
+        view
+    }
+}
+
+fn toggle_expanded<V>(node: &Rc<RefCell<TreeNode<V>>>)
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    let mut node = node.borrow_mut();
+    if node.has_children.get() {
+        let is_expanded = node.is_expanded.get();
+        node.is_expanded.set(!is_expanded);
+    }
+}
+
+///
+/// Returns the number of rows of the node and its visible descendants.
+///
+fn get_visible_count<V>(node: &Rc<RefCell<TreeNode<V>>>) -> usize
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    let node = node.borrow();
+    let children_count = match node.children {
+        Some(ref children) if node.is_children_shown => {
+            children.iter().map(get_visible_count).sum()
+        }
+        _ => 0,
+    };
+    1 + children_count
+}
+
+fn collect_rows<V>(node: &Rc<RefCell<TreeNode<V>>>, rows: &mut Vec<Rc<RefCell<TreeNode<V>>>>)
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    rows.push(node.clone());
+    let node = node.borrow();
+    if let Some(ref children) = node.children {
+        if node.is_children_shown {
+            for child in children.iter() {
+                collect_rows(child, rows);
+            }
+        }
+    }
+}
+
+fn find_loaded_node<V>(
+    node: &Rc<RefCell<TreeNode<V>>>,
+    item: &Rc<RefCell<V>>,
+) -> Option<Rc<RefCell<TreeNode<V>>>>
+where
+    V: HierarchicalViewModel + PartialEq,
+{
+    if Rc::ptr_eq(&node.borrow().source_vm, item) {
+        return Some(node.clone());
+    }
+    let node = node.borrow();
+    node.children
+        .as_ref()?
+        .iter()
+        .find_map(|child| find_loaded_node(child, item))
+}
+
+//
+// TreeViewItem.
+//
+
+///
+/// Row of the TreeView. Draws the indentation, the expander and the selection.
+///
+#[derive(TypedBuilder)]
+pub struct TreeViewItem {
+    #[builder(default = 0usize)]
+    pub depth: usize,
+
+    #[builder(default = Property::new(false))]
+    pub has_children: Property<bool>,
+
+    #[builder(default = Property::new(false))]
+    pub is_expanded: Property<bool>,
+
+    #[builder(default = Property::new(false))]
+    pub is_selected: Property<bool>,
+
+    #[builder(default = Callback::empty())]
+    pub clicked: Callback<()>,
+
+    #[builder(default = Callback::empty())]
+    pub expander_clicked: Callback<()>,
+
+    #[builder(default = Callback::empty())]
+    pub double_clicked: Callback<()>,
+}
+
+impl TreeViewItem {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultTreeViewItemStyle::new(
+                    DefaultTreeViewItemStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+}
+
+//
+// Default TreeViewItem Style
+//
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultTreeViewItemStyleParams {}
+
+pub struct DefaultTreeViewItemStyle {
+    rect: Rect,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+impl DefaultTreeViewItemStyle {
+    pub fn new(_params: DefaultTreeViewItemStyleParams) -> Self {
+        DefaultTreeViewItemStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            event_subscriptions: Vec::new(),
+        }
+    }
+
+    fn get_expander_x(&self, data: &TreeViewItem) -> f32 {
+        self.rect.x + PADDING + data.depth as f32 * INDENT_SIZE
+    }
+
+    fn is_on_expander(&self, data: &TreeViewItem, position: Point) -> bool {
+        let expander_x = self.get_expander_x(data);
+        data.has_children.get()
+            && position.x >= expander_x
+            && position.x < expander_x + EXPANDER_SIZE
+    }
+}
+
+impl Style<TreeViewItem> for DefaultTreeViewItemStyle {
+    fn setup(&mut self, data: &mut TreeViewItem, control_context: &mut ControlContext) {
+        self.event_subscriptions.push(
+            data.has_children
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_expanded
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+        self.event_subscriptions.push(
+            data.is_selected
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut TreeViewItem,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        match event {
+            ControlEvent::TapDown { position } => {
+                if self.is_on_expander(data, position) {
+                    data.expander_clicked.emit(());
+                }
+                data.clicked.emit(());
+            }
+
+            ControlEvent::DoubleTap { position } if !self.is_on_expander(data, position) => {
+                data.double_clicked.emit(());
+            }
+
+            _ => (),
+        }
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut TreeViewItem,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let offset = data.depth as f32 * INDENT_SIZE + EXPANDER_SIZE + PADDING * 2.0f32;
+        let children = control_context.get_children();
+
+        let content_size = if let Some(ref content) = children.into_iter().next() {
+            let child_size = Size::new(
+                if size.width.is_finite() {
+                    0f32.max(size.width - offset)
+                } else {
+                    size.width
+                },
+                if size.height.is_finite() {
+                    0f32.max(size.height - PADDING * 2.0f32)
+                } else {
+                    size.height
+                },
+            );
+            content.borrow_mut().measure(drawing_context, child_size);
+            let rect = content.borrow().get_rect();
+            Size::new(rect.width, rect.height)
+        } else {
+            Size::new(0f32, 0f32)
+        };
+
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            content_size.width + offset,
+            content_size.height.max(EXPANDER_SIZE) + PADDING * 2.0f32,
+        )
+    }
+
+    fn set_rect(
+        &mut self,
+        data: &mut TreeViewItem,
+        control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+
+        let offset = data.depth as f32 * INDENT_SIZE + EXPANDER_SIZE + PADDING;
+        let content_rect = Rect::new(
+            rect.x + offset,
+            rect.y + PADDING,
+            0f32.max(rect.width - offset - PADDING),
+            rect.height - PADDING * 2.0f32,
+        );
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().set_rect(content_rect);
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &TreeViewItem,
+        _control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            HitTestResult::Current
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn to_primitives(
+        &self,
+        data: &TreeViewItem,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let x = self.rect.x;
+        let y = self.rect.y;
+        let width = self.rect.width;
+        let height = self.rect.height;
+
+        let theme = control_context.get_theme();
+
+        if data.is_selected.get() {
+            vec.push(Primitive::Rectangle {
+                color: theme.get_color("SelectionBackground"),
+                rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
+            });
+        }
+
+        if data.has_children.get() {
+            let center_x = self.get_expander_x(data) + EXPANDER_SIZE / 2.0f32;
+            let center_y = y + height / 2.0f32;
+            let half = EXPANDER_GLYPH_SIZE / 2.0f32;
+
+            // triangle pointing down when expanded and right when collapsed
+            let path = if data.is_expanded.get() {
+                vec![
+                    PathElement::MoveTo(PixelPoint::new(center_x - half, center_y - half / 2.0f32)),
+                    PathElement::LineTo(PixelPoint::new(center_x + half, center_y - half / 2.0f32)),
+                    PathElement::LineTo(PixelPoint::new(
+                        center_x,
+                        center_y + half / 2.0f32 + 1.0f32,
+                    )),
+                ]
+            } else {
+                vec![
+                    PathElement::MoveTo(PixelPoint::new(center_x - half / 2.0f32, center_y - half)),
+                    PathElement::LineTo(PixelPoint::new(
+                        center_x + half / 2.0f32 + 1.0f32,
+                        center_y,
+                    )),
+                    PathElement::LineTo(PixelPoint::new(center_x - half / 2.0f32, center_y + half)),
+                ]
+            };
+            vec.push(Primitive::Fill {
+                path,
+                brush: Brush::Color {
+                    color: theme.get_color("Foreground"),
+                },
+            });
+        }
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            let (mut vec2, mut overlay2) = content.borrow_mut().to_primitives(drawing_context);
+            vec.append(&mut vec2);
+            overlay.append(&mut overlay2);
+        }
+
+        (vec, overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controls::*;
+
+    struct TestItem {
+        name: &'static str,
+        children: ObservableVec<Rc<RefCell<TestItem>>>,
+        is_expanded: Property<bool>,
+        is_loaded: bool,
+    }
+
+    impl TestItem {
+        fn new(name: &'static str, children: Vec<Rc<RefCell<TestItem>>>) -> Rc<RefCell<Self>> {
+            Rc::new(RefCell::new(TestItem {
+                name,
+                children: children.into_iter().collect(),
+                is_expanded: Property::new(false),
+                is_loaded: false,
+            }))
+        }
+    }
+
+    impl PartialEq for TestItem {
+        fn eq(&self, other: &Self) -> bool {
+            self.name == other.name
+        }
+    }
+
+    impl ViewModel for TestItem {
+        fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+            ui! {
+                Text { text: view_model.borrow().name }
+            }
+        }
+    }
+
+    impl HierarchicalViewModel for TestItem {
+        fn get_children(&mut self) -> &dyn ObservableCollection<Rc<RefCell<Self>>> {
+            self.is_loaded = true;
+            &self.children
+        }
+
+        fn get_is_expanded(&mut self) -> &mut Property<bool> {
+            &mut self.is_expanded
+        }
+    }
+
+    fn get_row_names(controller: &TreeController<TestItem>) -> Vec<&'static str> {
+        (&controller.rows)
+            .into_iter()
+            .map(|row| row.borrow().source_vm.borrow().name)
+            .collect()
+    }
+
+    fn expand(controller: &Rc<RefCell<TreeController<TestItem>>>, index: usize, value: bool) {
+        let node = controller.borrow().rows.get(index);
+        node.borrow_mut().is_expanded.set(value);
+        CallbackExecutor::execute_all_in_queue();
+    }
+
+    #[test]
+    fn test_expand_collapse_and_lazy_loading() {
+        let b = TestItem::new("b", vec![TestItem::new("b1", Vec::new())]);
+        let c = TestItem::new("c", vec![TestItem::new("c1", Vec::new())]);
+        let a = TestItem::new("a", vec![b.clone(), c.clone()]);
+
+        let controller = TreeController::new(Property::new(None), Callback::empty());
+        controller.borrow_mut().set_roots(vec![a.clone()]);
+        assert_eq!(get_row_names(&controller.borrow()), vec!["a"]);
+        assert!(!a.borrow().is_loaded);
+
+        expand(&controller, 0, true);
+        assert_eq!(get_row_names(&controller.borrow()), vec!["a", "b", "c"]);
+        assert!(a.borrow().is_loaded);
+        assert!(!c.borrow().is_loaded);
+
+        expand(&controller, 1, true);
+        assert_eq!(
+            get_row_names(&controller.borrow()),
+            vec!["a", "b", "b1", "c"]
+        );
+
+        // the expanded state of children is kept
+        expand(&controller, 0, false);
+        assert_eq!(get_row_names(&controller.borrow()), vec!["a"]);
+        expand(&controller, 0, true);
+        assert_eq!(
+            get_row_names(&controller.borrow()),
+            vec!["a", "b", "b1", "c"]
+        );
+
+        // the view model is bound to the item
+        assert!(b.borrow().is_expanded.get());
+    }
+
+    #[test]
+    fn test_children_changes() {
+        let b = TestItem::new("b", vec![TestItem::new("b1", Vec::new())]);
+        let a = TestItem::new("a", vec![b.clone()]);
+        a.borrow_mut().is_expanded.set(true);
+        b.borrow_mut().is_expanded.set(true);
+
+        let controller = TreeController::new(Property::new(None), Callback::empty());
+        controller.borrow_mut().set_roots(vec![a.clone()]);
+        assert_eq!(get_row_names(&controller.borrow()), vec!["a", "b", "b1"]);
+
+        a.borrow_mut().children.push(TestItem::new("c", Vec::new()));
+        CallbackExecutor::execute_all_in_queue();
+        assert_eq!(
+            get_row_names(&controller.borrow()),
+            vec!["a", "b", "b1", "c"]
+        );
+
+        // selection moves to the parent when the selected item is removed
+        let b1 = controller.borrow().rows.get(2);
+        controller.borrow_mut().select(Some(b1));
+        b.borrow_mut().children.remove(0);
+        CallbackExecutor::execute_all_in_queue();
+        assert_eq!(get_row_names(&controller.borrow()), vec!["a", "b", "c"]);
+        assert_eq!(
+            controller
+                .borrow()
+                .selected_item
+                .get()
+                .map(|item| item.borrow().name),
+            Some("b")
+        );
+    }
+
+    #[test]
+    fn test_equal_items_are_selected_by_identity() {
+        let first = TestItem::new("same", Vec::new());
+        let second = TestItem::new("same", Vec::new());
+
+        let controller = TreeController::new(Property::new(None), Callback::empty());
+        controller
+            .borrow_mut()
+            .set_roots(vec![first.clone(), second.clone()]);
+
+        controller
+            .borrow_mut()
+            .on_selected_item_changed(Some(second.clone()));
+        let selected = controller.borrow().selected.clone().unwrap();
+        assert!(Rc::ptr_eq(&selected.borrow().source_vm, &second));
+        assert_eq!(controller.borrow().get_row_index(&selected), Some(1));
+        assert!(controller
+            .borrow()
+            .selected_item
+            .get()
+            .is_some_and(|item| Rc::ptr_eq(&item, &second)));
+
+        controller
+            .borrow_mut()
+            .on_selected_item_changed(Some(first.clone()));
+        let selected = controller.borrow().selected.clone().unwrap();
+        assert_eq!(controller.borrow().get_row_index(&selected), Some(0));
+    }
+
+    #[test]
+    fn test_selecting_item_in_collapsed_parents_expands_them() {
+        let b1 = TestItem::new("b1", Vec::new());
+        let b = TestItem::new("b", vec![b1.clone()]);
+        let c = TestItem::new("c", vec![TestItem::new("c1", Vec::new())]);
+        let a = TestItem::new("a", vec![b.clone(), c.clone()]);
+
+        let controller = TreeController::new(Property::new(None), Callback::empty());
+        controller.borrow_mut().set_roots(vec![a.clone()]);
+        assert!(!a.borrow().is_loaded);
+
+        // the children are loaded and the rows inserted before the item is selected
+        controller
+            .borrow_mut()
+            .on_selected_item_changed(Some(b1.clone()));
+        assert!(b.borrow().is_loaded);
+        assert!(!c.borrow().is_loaded);
+        assert_eq!(
+            get_row_names(&controller.borrow()),
+            vec!["a", "b", "b1", "c"]
+        );
+        let selected = controller.borrow().selected.clone().unwrap();
+        assert!(Rc::ptr_eq(&selected.borrow().source_vm, &b1));
+        assert_eq!(controller.borrow().get_row_index(&selected), Some(2));
+
+        // the queued notifications don't insert the rows again
+        CallbackExecutor::execute_all_in_queue();
+        assert_eq!(
+            get_row_names(&controller.borrow()),
+            vec!["a", "b", "b1", "c"]
+        );
+        assert!(a.borrow().is_expanded.get());
+        assert!(b.borrow().is_expanded.get());
+
+        // items which are not in the tree clear the selection
+        controller
+            .borrow_mut()
+            .on_selected_item_changed(Some(TestItem::new("d", Vec::new())));
+        assert!(controller.borrow().selected.is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fui_core::*;
+use typed_builder::TypedBuilder;
+use typemap::TypeMap;
+
+use crate::{layout::*, DataHolder, ListScrollController};
+
+///
+/// Scrollable list which creates views only for the visible items.
+///
+/// Suitable for large collections (like logs or search results).
+/// Inserts and removes of the items collection update only the affected views.
+///
+#[derive(TypedBuilder)]
+pub struct VirtualList<V>
+where
+    V: ViewModel + 'static,
+{
+    #[builder(default = Box::new(Vec::<Rc<RefCell<V>>>::new()))]
+    pub items: Box<dyn ObservableCollection<Rc<RefCell<V>>>>,
+
+    #[builder(default = ItemHeight::Estimated(20.0f32))]
+    pub item_height: ItemHeight,
+
+    ///
+    /// Size of the view cache. It keeps views of the items scrolled out
+    /// of the viewport, so they are not created again when the same items
+    /// are scrolled back.
+    ///
+    #[builder(default = 0usize)]
+    pub view_cache_size: usize,
+
+    ///
+    /// Creates the item views and rebinds the views of the items
+    /// scrolled out of the viewport to other items.
+    ///
+    #[builder(default)]
+    pub view_recycler: Option<Rc<dyn ViewRecycler<V>>>,
+}
+
+impl<V> VirtualList<V>
+where
+    V: ViewModel + 'static,
+{
+    pub fn to_view(
+        self,
+        _style: Option<Box<dyn Style<Self>>>,
+        mut context: ViewContext,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let mut scroll_controller = ListScrollController::new();
+
+        let panel = VirtualizingStackPanel::builder()
+            .items(self.items)
+            .offset_y(scroll_controller.get_offset_y())
+            .viewport_height(scroll_controller.get_viewport_height())
+            .item_height(self.item_height)
+            .view_cache_size(self.view_cache_size)
+            .view_recycler(self.view_recycler)
+            .build()
+            .to_view(
+                None,
+                ViewContext {
+                    attached_values: TypeMap::new(),
+                    children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                },
+            );
+        let content = scroll_controller.create_view(panel);
+
+        let scroll_controller_rc = Rc::new(RefCell::new(scroll_controller));
+        let scroll_controller_clone = scroll_controller_rc.clone();
+        let event_filter = EventFilterHandler::new(move |event| match event {
+            ControlEvent::MouseWheel { delta, .. } => {
+                scroll_controller_clone
+                    .borrow_mut()
+                    .scroll_by_wheel(delta.y);
+                true
+            }
+
+            _ => false,
+        });
+        context.attached_values.insert::<EventFilter>(event_filter);
+
+        let data_holder = DataHolder {
+            data: scroll_controller_rc,
+        };
+        data_holder.to_view(
+            None,
+            ViewContext {
+                attached_values: context.attached_values,
+                children: Box::new(vec![content]),
+            },
+        )
+    }
+}
@@ -0,0 +1,226 @@
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use anyhow::{anyhow, Result};
+use fui_core::*;
+use fui_macros::ui;
+use typemap::TypeMap;
+
+use crate::dialogs::ModalLayer;
+
+///
+/// View model shown as a modal dialog by DialogService.
+///
+pub trait DialogViewModel: ViewModel {
+    type Result: 'static + Clone;
+
+    ///
+    /// Called before the dialog is shown.
+    /// The view model keeps the dialog to close it from its commands.
+    ///
+    fn set_dialog(&mut self, dialog: Dialog<Self::Result>);
+
+    ///
+    /// Result of accepting the dialog with Enter key.
+    /// None keeps the dialog open.
+    ///
+    fn get_accept_result(&self) -> Option<Self::Result> {
+        None
+    }
+}
+
+//
+// Dialog.
+//
+
+// the layer is set after the dialog content (which needs the handle) is created
+type LayerCell = Rc<RefCell<Option<Rc<RefCell<dyn ControlObject>>>>>;
+
+type AcceptResultFn<R> = Box<dyn Fn() -> Option<R>>;
+
+struct DialogState<R> {
+    result: Callback<Option<R>>,
+    remove_layer: Callback<()>,
+    accept_result: RefCell<Option<AcceptResultFn<R>>>,
+    is_closed: Cell<bool>,
+}
+
+///
+/// Handle to the dialog shown on a modal layer.
+///
+/// The result callback is called with Some(result) when the dialog
+/// is closed with a result or None when it is cancelled (e.g. with Esc key).
+///
+pub struct Dialog<R> {
+    state: Rc<DialogState<R>>,
+}
+
+impl<R> Clone for Dialog<R> {
+    fn clone(&self) -> Self {
+        Dialog {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<R: 'static + Clone> Dialog<R> {
+    fn new(
+        window_service: &Weak<RefCell<dyn WindowService>>,
+        layer: &LayerCell,
+        result: Callback<Option<R>>,
+    ) -> Self {
+        let mut remove_layer = Callback::empty();
+        let window_service = window_service.clone();
+        let layer = layer.clone();
+        remove_layer.set(move |_| {
+            let layer = layer.borrow_mut().take();
+            if let (Some(layer), Some(window_service)) = (layer, window_service.upgrade()) {
+                window_service.borrow_mut().remove_layer(&layer);
+            }
+        });
+
+        Dialog {
+            state: Rc::new(DialogState {
+                result,
+                remove_layer,
+                accept_result: RefCell::new(None),
+                is_closed: Cell::new(false),
+            }),
+        }
+    }
+
+    ///
+    /// Sets the function returning the result for Enter key.
+    ///
+    pub fn set_accept_result<F: 'static + Fn() -> Option<R>>(&self, accept_result: F) {
+        self.state
+            .accept_result
+            .replace(Some(Box::new(accept_result)));
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.is_closed.get()
+    }
+
+    pub fn close(&self, result: Option<R>) {
+        if self.state.is_closed.replace(true) {
+            return;
+        }
+
+        // the result is queued before removing the layer,
+        // the layer keeps the dialog (with its callbacks) alive until then
+        self.state.result.emit(result);
+        self.state.remove_layer.emit(());
+    }
+
+    pub fn cancel(&self) {
+        self.close(None);
+    }
+
+    pub fn accept(&self) {
+        let result = self
+            .state
+            .accept_result
+            .borrow()
+            .as_ref()
+            .and_then(|accept_result| accept_result());
+        if let Some(result) = result {
+            self.close(Some(result));
+        }
+    }
+}
+
+//
+// DialogService.
+//
+
+///
+/// Shows modal dialogs on a new top layer of the window.
+///
+/// Input goes only to the top layer, so the controls below
+/// the dialog don't get pointer and keyboard events until it is closed.
+///
+/// Dialogs are shown immediately, so it should be used from callbacks
+/// (not from control's styles during event handling).
+///
+pub struct DialogService {
+    window_service: Weak<RefCell<dyn WindowService>>,
+    services: Weak<RefCell<Services>>,
+}
+
+impl DialogService {
+    ///
+    /// Creates dialog service for window's services.
+    ///
+    pub fn new(services: &Rc<RefCell<Services>>) -> Result<Self> {
+        let window_service = services.borrow().get::<dyn WindowService>()?;
+        Ok(DialogService {
+            window_service: Rc::downgrade(&window_service),
+            services: Rc::downgrade(services),
+        })
+    }
+
+    pub fn show<R, F>(&self, create_content: F, result: Callback<Option<R>>) -> Result<Dialog<R>>
+    where
+        R: 'static + Clone,
+        F: FnOnce(&Dialog<R>) -> Rc<RefCell<dyn ControlObject>>,
+    {
+        let window_service = self
+            .window_service
+            .upgrade()
+            .ok_or_else(|| anyhow!("Window is closed."))?;
+
+        let layer_cell: LayerCell = Rc::new(RefCell::new(None));
+        let dialog = Dialog::new(&self.window_service, &layer_cell, result);
+        let content = create_content(&dialog);
+
+        let mut cancel_callback = Callback::empty();
+        let dialog_clone = dialog.clone();
+        cancel_callback.set(move |_| dialog_clone.cancel());
+
+        let mut accept_callback = Callback::empty();
+        let dialog_clone = dialog.clone();
+        accept_callback.set(move |_| dialog_clone.accept());
+
+        let layer: Rc<RefCell<dyn ControlObject>> = ui! {
+            ModalLayer {
+                cancel: cancel_callback,
+                accept: accept_callback,
+
+                @content,
+            }
+        };
+        layer
+            .borrow_mut()
+            .get_context_mut()
+            .set_services(Some(self.services.clone()));
+
+        layer_cell.replace(Some(layer.clone()));
+        window_service.borrow_mut().add_layer(layer);
+
+        Ok(dialog)
+    }
+
+    pub fn show_view_model<V: DialogViewModel + 'static>(
+        &self,
+        view_model: &Rc<RefCell<V>>,
+        result: Callback<Option<V::Result>>,
+    ) -> Result<Dialog<V::Result>> {
+        let view_model = view_model.clone();
+        self.show(
+            move |dialog| {
+                view_model.borrow_mut().set_dialog(dialog.clone());
+
+                let view_model_weak = Rc::downgrade(&view_model);
+                dialog.set_accept_result(move || {
+                    view_model_weak
+                        .upgrade()
+                        .and_then(|view_model| view_model.borrow().get_accept_result())
+                });
+
+                ViewModel::create_view(&view_model)
+            },
+            result,
+        )
+    }
+}
@@ -0,0 +1,8 @@
+mod dialog_service;
+pub use self::dialog_service::*;
+
+mod modal_layer;
+pub use self::modal_layer::*;
+
+mod standard_dialogs;
+pub use self::standard_dialogs::*;
@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use fui_core::*;
+use typed_builder::TypedBuilder;
+
+///
+/// Window layer hosting a modal dialog.
+///
+/// The content is centered on a dimmed background covering
+/// the whole window, so lower layers don't get pointer input.
+/// Esc key emits cancel and Enter key emits accept callback.
+///
+/// Keyboard focus stays in the layer (controls focused on the lower layers
+/// don't get keyboard events). Moving focus between the dialog's controls
+/// with Tab is not supported, the focus changes only when they are tapped.
+///
+#[derive(TypedBuilder)]
+pub struct ModalLayer {
+    #[builder(default = Callback::empty())]
+    pub cancel: Callback<()>,
+
+    #[builder(default = Callback::empty())]
+    pub accept: Callback<()>,
+}
+
+impl ModalLayer {
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultModalLayerStyle::new(
+                    DefaultModalLayerStyleParams::builder().build(),
+                ))
+            }),
+            context,
+        )
+    }
+}
+
+//
+// Default ModalLayer Style
+//
+
+const DIALOG_PADDING: f32 = 10.0f32;
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultModalLayerStyleParams {
+    ///
+    /// ModalDim color of the theme is used when not set.
+    ///
+    #[builder(default, setter(strip_option))]
+    pub dim_color: Option<Color>,
+
+    ///
+    /// DialogBackground color of the theme is used when not set.
+    ///
+    #[builder(default, setter(strip_option))]
+    pub background_color: Option<Color>,
+}
+
+pub struct DefaultModalLayerStyle {
+    rect: Rect,
+    dialog_rect: Rect,
+    params: DefaultModalLayerStyleParams,
+}
+
+impl DefaultModalLayerStyle {
+    pub fn new(params: DefaultModalLayerStyleParams) -> Self {
+        DefaultModalLayerStyle {
+            rect: Rect::new(0f32, 0f32, 0f32, 0f32),
+            dialog_rect: Rect::new(0f32, 0f32, 0f32, 0f32),
+            params,
+        }
+    }
+}
+
+impl Style<ModalLayer> for DefaultModalLayerStyle {
+    fn setup(&mut self, _data: &mut ModalLayer, _control_context: &mut ControlContext) {}
+
+    fn handle_event(
+        &mut self,
+        data: &mut ModalLayer,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        if let ControlEvent::KeyboardInput(ref key_event) = event {
+            if key_event.state == KeyState::Pressed {
+                match key_event.keycode {
+                    Some(Keycode::Esc) => data.cancel.emit(()),
+                    Some(Keycode::Enter) | Some(Keycode::NumpadEnter) => data.accept.emit(()),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn measure(
+        &mut self,
+        _data: &mut ModalLayer,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let children = control_context.get_children();
+
+        let content_size = if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().measure(
+                drawing_context,
+                Size::new(
+                    0f32.max(size.width - DIALOG_PADDING * 2.0f32),
+                    0f32.max(size.height - DIALOG_PADDING * 2.0f32),
+                ),
+            );
+            let rect = content.borrow().get_rect();
+            Size::new(rect.width, rect.height)
+        } else {
+            Size::new(0f32, 0f32)
+        };
+
+        self.rect = Rect::new(0f32, 0f32, size.width, size.height);
+        self.dialog_rect = Rect::new(
+            0f32,
+            0f32,
+            content_size.width + DIALOG_PADDING * 2.0f32,
+            content_size.height + DIALOG_PADDING * 2.0f32,
+        );
+    }
+
+    fn set_rect(
+        &mut self,
+        _data: &mut ModalLayer,
+        control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+
+        let width = self.dialog_rect.width.min(rect.width);
+        let height = self.dialog_rect.height.min(rect.height);
+        self.dialog_rect = Rect::new(
+            rect.x + (rect.width - width) / 2.0f32,
+            rect.y + (rect.height - height) / 2.0f32,
+            width,
+            height,
+        );
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().set_rect(Rect::new(
+                self.dialog_rect.x + DIALOG_PADDING,
+                self.dialog_rect.y + DIALOG_PADDING,
+                0f32.max(width - DIALOG_PADDING * 2.0f32),
+                0f32.max(height - DIALOG_PADDING * 2.0f32),
+            ));
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &ModalLayer,
+        control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            let c = content.borrow();
+            let rect = c.get_rect();
+            if point.is_inside(&rect) {
+                let child_hit_test = c.hit_test(point);
+                match child_hit_test {
+                    HitTestResult::Current => return HitTestResult::Child(content.clone()),
+                    HitTestResult::Child(..) => return child_hit_test,
+                    HitTestResult::Nothing => (),
+                }
+            }
+        }
+
+        // the layer catches all the pointer input outside the dialog
+        HitTestResult::Current
+    }
+
+    fn to_primitives(
+        &self,
+        _data: &ModalLayer,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let theme = control_context.get_theme();
+
+        vec.push(Primitive::Rectangle {
+            color: self
+                .params
+                .dim_color
+                .unwrap_or_else(|| theme.get_color("ModalDim")),
+            rect: PixelRect::new(
+                PixelPoint::new(self.rect.x, self.rect.y),
+                PixelSize::new(self.rect.width, self.rect.height),
+            ),
+        });
+
+        vec.push(Primitive::Rectangle {
+            color: self
+                .params
+                .background_color
+                .unwrap_or_else(|| theme.get_color("DialogBackground")),
+            rect: PixelRect::new(
+                PixelPoint::new(self.dialog_rect.x, self.dialog_rect.y),
+                PixelSize::new(self.dialog_rect.width, self.dialog_rect.height),
+            ),
+        });
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            let (mut vec2, mut overlay2) = content.borrow().to_primitives(drawing_context);
+            vec.append(&mut vec2);
+            overlay.append(&mut overlay2);
+        }
+
+        (vec, overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use typemap::TypeMap;
+
+    use super::*;
+    use crate::test_helpers::*;
+
+    fn counting_callback(count: &Rc<Cell<usize>>) -> Callback<()> {
+        let count = count.clone();
+        let mut callback = Callback::empty();
+        callback.set(move |_| count.set(count.get() + 1));
+        callback
+    }
+
+    #[test]
+    fn test_esc_cancels_and_enter_accepts() {
+        let cancel_count = Rc::new(Cell::new(0));
+        let accept_count = Rc::new(Cell::new(0));
+        let cancel = counting_callback(&cancel_count);
+        let accept = counting_callback(&accept_count);
+
+        let layer: Rc<RefCell<dyn ControlObject>> = ModalLayer::builder()
+            .cancel(cancel.clone())
+            .accept(accept.clone())
+            .build()
+            .to_view(
+                None,
+                ViewContext {
+                    attached_values: TypeMap::new(),
+                    children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                },
+            );
+        let layers = vec![layer];
+
+        let mut processor = EventProcessor::new();
+        let mut drawing_context = TestDrawingContext::new();
+        let mut handle = |event: InputEvent| {
+            processor.handle_event(&layers, &mut drawing_context, &event);
+            CallbackExecutor::execute_all_in_queue();
+        };
+
+        handle(key_pressed(Keycode::A));
+        assert_eq!((cancel_count.get(), accept_count.get()), (0, 0));
+
+        handle(key_pressed(Keycode::Esc));
+        assert_eq!((cancel_count.get(), accept_count.get()), (1, 0));
+
+        handle(key_pressed(Keycode::Enter));
+        handle(key_pressed(Keycode::NumpadEnter));
+        assert_eq!((cancel_count.get(), accept_count.get()), (1, 2));
+    }
+}
@@ -0,0 +1,247 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::Result;
+use fui_core::*;
+use fui_macros::ui;
+use typemap::TypeMap;
+
+use crate::controls::*;
+use crate::dialogs::*;
+use crate::layout::*;
+
+//
+// MessageBox.
+//
+
+pub struct MessageBoxViewModel {
+    pub title: String,
+    pub message: String,
+    dialog: Option<Dialog<()>>,
+}
+
+impl MessageBoxViewModel {
+    pub fn new<T: Into<String>, M: Into<String>>(title: T, message: M) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(MessageBoxViewModel {
+            title: title.into(),
+            message: message.into(),
+            dialog: None,
+        }))
+    }
+
+    pub fn ok(&mut self) {
+        if let Some(ref dialog) = self.dialog {
+            dialog.close(Some(()));
+        }
+    }
+}
+
+impl DialogViewModel for MessageBoxViewModel {
+    type Result = ();
+
+    fn set_dialog(&mut self, dialog: Dialog<()>) {
+        self.dialog = Some(dialog);
+    }
+
+    fn get_accept_result(&self) -> Option<()> {
+        Some(())
+    }
+}
+
+impl ViewModel for MessageBoxViewModel {
+    fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+        let vm = view_model.borrow();
+        ui! {
+            Vertical {
+                Text { text: &*vm.title },
+                Text { text: &*vm.message },
+                Button {
+                    clicked: Callback::new(view_model, |vm, _| vm.ok()),
+                    Text { text: "OK" },
+                },
+            }
+        }
+    }
+}
+
+//
+// ConfirmViewModel.
+//
+
+pub struct ConfirmViewModel {
+    pub title: String,
+    pub message: String,
+    dialog: Option<Dialog<bool>>,
+}
+
+impl ConfirmViewModel {
+    pub fn new<T: Into<String>, M: Into<String>>(title: T, message: M) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(ConfirmViewModel {
+            title: title.into(),
+            message: message.into(),
+            dialog: None,
+        }))
+    }
+
+    pub fn yes(&mut self) {
+        if let Some(ref dialog) = self.dialog {
+            dialog.close(Some(true));
+        }
+    }
+
+    pub fn no(&mut self) {
+        if let Some(ref dialog) = self.dialog {
+            dialog.close(Some(false));
+        }
+    }
+}
+
+impl DialogViewModel for ConfirmViewModel {
+    type Result = bool;
+
+    fn set_dialog(&mut self, dialog: Dialog<bool>) {
+        self.dialog = Some(dialog);
+    }
+
+    fn get_accept_result(&self) -> Option<bool> {
+        Some(true)
+    }
+}
+
+impl ViewModel for ConfirmViewModel {
+    fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+        let vm = view_model.borrow();
+        ui! {
+            Vertical {
+                Text { text: &*vm.title },
+                Text { text: &*vm.message },
+                Horizontal {
+                    Button {
+                        clicked: Callback::new(view_model, |vm, _| vm.yes()),
+                        Text { text: "Yes" },
+                    },
+                    Button {
+                        clicked: Callback::new(view_model, |vm, _| vm.no()),
+                        Text { text: "No" },
+                    },
+                },
+            }
+        }
+    }
+}
+
+//
+// InputViewModel.
+//
+
+pub struct InputViewModel {
+    pub title: String,
+    pub message: String,
+    pub text: Property<String>,
+    dialog: Option<Dialog<String>>,
+}
+
+impl InputViewModel {
+    pub fn new<T: Into<String>, M: Into<String>, V: Into<String>>(
+        title: T,
+        message: M,
+        text: V,
+    ) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(InputViewModel {
+            title: title.into(),
+            message: message.into(),
+            text: Property::new(text.into()),
+            dialog: None,
+        }))
+    }
+
+    pub fn ok(&mut self) {
+        if let Some(ref dialog) = self.dialog {
+            dialog.close(Some(self.text.get()));
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(ref dialog) = self.dialog {
+            dialog.cancel();
+        }
+    }
+}
+
+impl DialogViewModel for InputViewModel {
+    type Result = String;
+
+    fn set_dialog(&mut self, dialog: Dialog<String>) {
+        self.dialog = Some(dialog);
+    }
+
+    fn get_accept_result(&self) -> Option<String> {
+        Some(self.text.get())
+    }
+}
+
+impl ViewModel for InputViewModel {
+    fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+        let mut vm = view_model.borrow_mut();
+        ui! {
+            Vertical {
+                Text { text: &*vm.title },
+                Text { text: &*vm.message },
+                TextBox { text: &mut vm.text },
+                Horizontal {
+                    Button {
+                        clicked: Callback::new(view_model, |vm, _| vm.ok()),
+                        Text { text: "OK" },
+                    },
+                    Button {
+                        clicked: Callback::new(view_model, |vm, _| vm.cancel()),
+                        Text { text: "Cancel" },
+                    },
+                },
+            }
+        }
+    }
+}
+
+//
+// Standard dialogs.
+//
+
+impl DialogService {
+    ///
+    /// Shows message with OK button.
+    ///
+    pub fn show_message(
+        &self,
+        title: &str,
+        message: &str,
+        result: Callback<Option<()>>,
+    ) -> Result<Dialog<()>> {
+        self.show_view_model(&MessageBoxViewModel::new(title, message), result)
+    }
+
+    ///
+    /// Asks the question with Yes / No buttons.
+    ///
+    pub fn show_confirm(
+        &self,
+        title: &str,
+        message: &str,
+        result: Callback<Option<bool>>,
+    ) -> Result<Dialog<bool>> {
+        self.show_view_model(&ConfirmViewModel::new(title, message), result)
+    }
+
+    ///
+    /// Asks for the text with OK / Cancel buttons.
+    ///
+    pub fn show_input(
+        &self,
+        title: &str,
+        message: &str,
+        text: &str,
+        result: Callback<Option<String>>,
+    ) -> Result<Dialog<String>> {
+        self.show_view_model(&InputViewModel::new(title, message, text), result)
+    }
+}
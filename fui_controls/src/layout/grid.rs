@@ -236,7 +236,7 @@ impl Grid {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultGridStyle::new(
+                RegisteredStyle::with_default(DefaultGridStyle::new(
                     DefaultGridStyleParams::builder().build(),
                 ))
             }),
@@ -249,7 +249,7 @@ impl Grid {
 // Default Grid Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultGridStyleParams {}
 
 pub struct DefaultGridStyle {
@@ -12,3 +12,6 @@ pub use self::stack_panel::*;
 
 mod vertical;
 pub use self::vertical::*;
+
+mod virtualizing_stack_panel;
+pub use self::virtualizing_stack_panel::*;
@@ -41,7 +41,7 @@ impl RelativeLayout {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultRelativeLayoutStyle::new(
+                RegisteredStyle::with_default(DefaultRelativeLayoutStyle::new(
                     DefaultRelativeLayoutStyleParams::builder().build(),
                 ))
             }),
@@ -54,7 +54,7 @@ impl RelativeLayout {
 // Default RelativeLayout Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultRelativeLayoutStyleParams {}
 
 pub struct DefaultRelativeLayoutStyle {
@@ -21,7 +21,7 @@ impl StackPanel {
         StyledControl::new(
             self,
             style.unwrap_or_else(|| {
-                Box::new(DefaultStackPanelStyle::new(
+                RegisteredStyle::with_default(DefaultStackPanelStyle::new(
                     DefaultStackPanelStyleParams::builder().build(),
                 ))
             }),
@@ -34,7 +34,7 @@ impl StackPanel {
 // Default StackPanel Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultStackPanelStyleParams {}
 
 pub struct DefaultStackPanelStyle {
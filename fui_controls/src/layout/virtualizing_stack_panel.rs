@@ -0,0 +1,1015 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::f32;
+use std::ops::Range;
+use std::rc::{Rc, Weak};
+
+use drawing::primitive::Primitive;
+use fui_core::*;
+use typed_builder::TypedBuilder;
+
+const DEFAULT_ITEM_HEIGHT: f32 = 20.0f32;
+
+type ViewsRc = Rc<RefCell<Vec<Rc<RefCell<dyn ControlObject>>>>>;
+
+type CreateBoundViewFn<S> = Box<dyn Fn() -> (S, Rc<RefCell<dyn ControlObject>>)>;
+
+type BindViewFn<V, S> = Box<dyn Fn(&mut S, &Rc<RefCell<V>>)>;
+
+// states of the created views (the views are not kept alive by the recycler)
+type ViewStates<S> = RefCell<Vec<(Weak<RefCell<dyn ControlObject>>, S)>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemHeight {
+    ///
+    /// All the items have the same height.
+    ///
+    Fixed(f32),
+
+    ///
+    /// Items not realized yet are assumed to have this height.
+    /// Realized items use their measured height.
+    ///
+    Estimated(f32),
+}
+
+///
+/// Vertical stack panel which creates views only for the items
+/// intersecting the viewport.
+///
+/// The panel is meant to be the content of the ScrollArea and it has to be bound
+/// to its offset_y and viewport height. Views of items which are scrolled out
+/// are dropped, unless view_cache_size is set. Then they are kept in the view cache
+/// and reused when the same item is scrolled back into view.
+/// With the view_recycler, views of the items scrolled out (or removed)
+/// are rebound to the items scrolled into view instead of creating new ones.
+///
+#[derive(TypedBuilder)]
+pub struct VirtualizingStackPanel<V>
+where
+    V: ViewModel + 'static,
+{
+    #[builder(default = Box::new(Vec::<Rc<RefCell<V>>>::new()))]
+    pub items: Box<dyn ObservableCollection<Rc<RefCell<V>>>>,
+
+    pub offset_y: Property<f32>,
+
+    pub viewport_height: Property<f32>,
+
+    #[builder(default = ItemHeight::Estimated(DEFAULT_ITEM_HEIGHT))]
+    pub item_height: ItemHeight,
+
+    ///
+    /// Size of the view cache. It keeps views of the items scrolled out
+    /// of the viewport, so they are not created again when the same items
+    /// are scrolled back.
+    ///
+    #[builder(default = 0usize)]
+    pub view_cache_size: usize,
+
+    ///
+    /// Creates the item views (instead of the view models) and rebinds
+    /// the unused views to other items.
+    ///
+    #[builder(default)]
+    pub view_recycler: Option<Rc<dyn ViewRecycler<V>>>,
+
+    #[builder(default = Rc::new(RefCell::new(Vec::new())), setter(skip))]
+    realized_views: ViewsRc,
+}
+
+impl<V> VirtualizingStackPanel<V>
+where
+    V: ViewModel + 'static,
+{
+    pub fn to_view(
+        self,
+        style: Option<Box<dyn Style<Self>>>,
+        context: ViewContext,
+    ) -> Rc<RefCell<StyledControl<Self>>> {
+        // children are the realized views only
+        let children = Box::new(RealizedViews {
+            views: self.realized_views.clone(),
+        });
+        StyledControl::new(
+            self,
+            style.unwrap_or_else(|| {
+                RegisteredStyle::with_default(DefaultVirtualizingStackPanelStyle::new(
+                    DefaultVirtualizingStackPanelStyleParams::builder().build(),
+                ))
+            }),
+            ViewContext {
+                attached_values: context.attached_values,
+                children,
+            },
+        )
+    }
+}
+
+struct RealizedViews {
+    views: ViewsRc,
+}
+
+impl ObservableCollection<Rc<RefCell<dyn ControlObject>>> for RealizedViews {
+    fn len(&self) -> usize {
+        self.views.borrow().len()
+    }
+
+    fn get(&self, index: usize) -> Rc<RefCell<dyn ControlObject>> {
+        self.views.borrow()[index].clone()
+    }
+
+    fn on_changed(
+        &self,
+        _: Box<dyn Fn(ObservableChangedEventArgs<Rc<RefCell<dyn ControlObject>>>)>,
+    ) -> Option<EventSubscription> {
+        // the panel sets parents of the views it realizes
+        None
+    }
+}
+
+///
+/// Creates item views which can be reused for other items.
+///
+pub trait ViewRecycler<V> {
+    fn create_view(&self, item: &Rc<RefCell<V>>) -> Rc<RefCell<dyn ControlObject>>;
+
+    ///
+    /// Binds the view created by create_view() for another item to this item.
+    ///
+    fn rebind_view(&self, view: &Rc<RefCell<dyn ControlObject>>, item: &Rc<RefCell<V>>);
+}
+
+///
+/// ViewRecycler which creates the views with their bindable state
+/// (like properties the views are bound to) and binds the state to the items.
+///
+pub struct BindingViewRecycler<V, S> {
+    create_view: CreateBoundViewFn<S>,
+    bind: BindViewFn<V, S>,
+    states: ViewStates<S>,
+}
+
+impl<V, S> BindingViewRecycler<V, S> {
+    pub fn new<C, B>(create_view: C, bind: B) -> Self
+    where
+        C: 'static + Fn() -> (S, Rc<RefCell<dyn ControlObject>>),
+        B: 'static + Fn(&mut S, &Rc<RefCell<V>>),
+    {
+        BindingViewRecycler {
+            create_view: Box::new(create_view),
+            bind: Box::new(bind),
+            states: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<V, S> ViewRecycler<V> for BindingViewRecycler<V, S> {
+    fn create_view(&self, item: &Rc<RefCell<V>>) -> Rc<RefCell<dyn ControlObject>> {
+        let (mut state, view) = (self.create_view)();
+        (self.bind)(&mut state, item);
+
+        let mut states = self.states.borrow_mut();
+        states.retain(|(view, _)| view.strong_count() > 0);
+        states.push((Rc::downgrade(&view), state));
+        view
+    }
+
+    fn rebind_view(&self, view: &Rc<RefCell<dyn ControlObject>>, item: &Rc<RefCell<V>>) {
+        let view = Rc::downgrade(view);
+        if let Some((_, state)) = self
+            .states
+            .borrow_mut()
+            .iter_mut()
+            .find(|(state_view, _)| state_view.ptr_eq(&view))
+        {
+            (self.bind)(state, item);
+        }
+    }
+}
+
+//
+// Default VirtualizingStackPanel Style
+//
+
+#[derive(Clone, TypedBuilder)]
+pub struct DefaultVirtualizingStackPanelStyleParams {}
+
+pub struct DefaultVirtualizingStackPanelStyle<V>
+where
+    V: ViewModel + 'static,
+{
+    rect: Rect,
+    layout: ItemLayout,
+    realized: Vec<RealizedItem<V>>,
+    view_cache: VecDeque<RealizedItem<V>>,
+    unused_views: Vec<Rc<RefCell<dyn ControlObject>>>,
+    items_changes: Rc<RefCell<Vec<ItemsChange>>>,
+    event_subscriptions: Vec<EventSubscription>,
+}
+
+struct RealizedItem<V> {
+    index: usize,
+    item: Rc<RefCell<V>>,
+    view: Rc<RefCell<dyn ControlObject>>,
+}
+
+enum ItemsChange {
+    Insert(usize),
+    Remove(usize),
+}
+
+impl<V> DefaultVirtualizingStackPanelStyle<V>
+where
+    V: ViewModel + 'static,
+{
+    pub fn new(_params: DefaultVirtualizingStackPanelStyleParams) -> Self {
+        DefaultVirtualizingStackPanelStyle {
+            rect: Rect {
+                x: 0f32,
+                y: 0f32,
+                width: 0f32,
+                height: 0f32,
+            },
+            layout: ItemLayout::new(ItemHeight::Estimated(DEFAULT_ITEM_HEIGHT), 0),
+            realized: Vec::new(),
+            view_cache: VecDeque::new(),
+            unused_views: Vec::new(),
+            items_changes: Rc::new(RefCell::new(Vec::new())),
+            event_subscriptions: Vec::new(),
+        }
+    }
+
+    ///
+    /// Updates item heights and indices of the realized and cached items
+    /// without touching the other items.
+    ///
+    fn apply_items_changes(&mut self, data: &VirtualizingStackPanel<V>) {
+        let changes: Vec<ItemsChange> = self.items_changes.borrow_mut().drain(..).collect();
+        for change in changes {
+            match change {
+                ItemsChange::Insert(index) => {
+                    self.layout.insert(index);
+                    for item in self.realized.iter_mut().chain(self.view_cache.iter_mut()) {
+                        if item.index >= index {
+                            item.index += 1;
+                        }
+                    }
+                }
+                ItemsChange::Remove(index) => {
+                    self.layout.remove(index);
+                    if data.view_recycler.is_some() {
+                        for item in self.realized.iter().chain(self.view_cache.iter()) {
+                            if item.index == index {
+                                self.unused_views.push(item.view.clone());
+                            }
+                        }
+                    }
+                    self.realized.retain(|item| item.index != index);
+                    self.view_cache.retain(|item| item.index != index);
+                    for item in self.realized.iter_mut().chain(self.view_cache.iter_mut()) {
+                        if item.index > index {
+                            item.index -= 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.layout.len() != data.items.len() {
+            // the collection was changed without notifications
+            self.layout.reset(data.items.len());
+            self.realized.clear();
+            self.view_cache.clear();
+        }
+    }
+
+    fn realize(
+        &mut self,
+        data: &VirtualizingStackPanel<V>,
+        control_context: &ControlContext,
+        range: Range<usize>,
+    ) {
+        let mut old_realized = std::mem::take(&mut self.realized);
+
+        // views of the items scrolled out can be recycled for the new items
+        let mut new_indices = Vec::new();
+        for index in range {
+            match old_realized.iter().position(|item| item.index == index) {
+                Some(pos) => self.realized.push(old_realized.remove(pos)),
+                None => new_indices.push(index),
+            }
+        }
+        if data.view_cache_size > 0 {
+            for item in old_realized.into_iter() {
+                self.view_cache.push_front(item);
+            }
+        } else if data.view_recycler.is_some() {
+            self.unused_views
+                .extend(old_realized.into_iter().map(|item| item.view));
+        }
+
+        for index in new_indices {
+            let item = data.items.get(index);
+            let view = self.get_view(data, control_context, &item);
+            self.realized.push(RealizedItem { index, item, view });
+        }
+        self.realized.sort_by_key(|item| item.index);
+
+        if data.view_recycler.is_some() {
+            while self.view_cache.len() > data.view_cache_size {
+                if let Some(item) = self.view_cache.pop_back() {
+                    self.unused_views.push(item.view);
+                }
+            }
+        } else {
+            self.view_cache.truncate(data.view_cache_size);
+        }
+
+        *data.realized_views.borrow_mut() =
+            self.realized.iter().map(|item| item.view.clone()).collect();
+    }
+
+    ///
+    /// Returns the view of the newly realized item. It is taken from the view cache
+    /// (when it was created for the same item), recycled or created.
+    ///
+    fn get_view(
+        &mut self,
+        data: &VirtualizingStackPanel<V>,
+        control_context: &ControlContext,
+        item: &Rc<RefCell<V>>,
+    ) -> Rc<RefCell<dyn ControlObject>> {
+        let cached_pos = self
+            .view_cache
+            .iter()
+            .position(|cached| Rc::ptr_eq(&cached.item, item));
+        if let Some(cached) = cached_pos.and_then(|pos| self.view_cache.remove(pos)) {
+            return cached.view;
+        }
+
+        if let Some(ref view_recycler) = data.view_recycler {
+            // the oldest cached views are recycled when there are no unused ones
+            let unused_view = self
+                .unused_views
+                .pop()
+                .or_else(|| self.view_cache.pop_back().map(|cached| cached.view));
+            if let Some(view) = unused_view {
+                view_recycler.rebind_view(&view, item);
+                return view;
+            }
+        }
+
+        let view = match data.view_recycler {
+            Some(ref view_recycler) => view_recycler.create_view(item),
+            None => ViewModel::create_view(item),
+        };
+        {
+            let mut view_mut = view.borrow_mut();
+            let view_context = view_mut.get_context_mut();
+            view_context.set_parent(&control_context.get_self_rc());
+            view_context
+                .set_parent_inherited_properties(control_context.get_inherited_properties());
+            view_context.set_services(control_context.get_services());
+        }
+        view
+    }
+}
+
+impl<V> Style<VirtualizingStackPanel<V>> for DefaultVirtualizingStackPanelStyle<V>
+where
+    V: ViewModel + 'static,
+{
+    fn setup(
+        &mut self,
+        data: &mut VirtualizingStackPanel<V>,
+        control_context: &mut ControlContext,
+    ) {
+        self.layout = ItemLayout::new(data.item_height, data.items.len());
+
+        self.event_subscriptions
+            .push(data.offset_y.dirty_watching(&control_context.get_self_rc()));
+        self.event_subscriptions.push(
+            data.viewport_height
+                .dirty_watching(&control_context.get_self_rc()),
+        );
+
+        let items_changes = self.items_changes.clone();
+        let control_weak = Rc::downgrade(&control_context.get_self_rc());
+        if let Some(subscription) = data.items.on_changed(Box::new(move |changed_args| {
+            items_changes.borrow_mut().push(match changed_args {
+                ObservableChangedEventArgs::Insert { index, .. } => ItemsChange::Insert(index),
+                ObservableChangedEventArgs::Remove { index } => ItemsChange::Remove(index),
+            });
+            if let Some(control) = control_weak.upgrade() {
+                control.borrow_mut().get_context_mut().set_is_dirty(true);
+            }
+        })) {
+            self.event_subscriptions.push(subscription);
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: &mut VirtualizingStackPanel<V>,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        _event: ControlEvent,
+    ) {
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut VirtualizingStackPanel<V>,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        self.apply_items_changes(data);
+
+        let range = self
+            .layout
+            .get_visible_range(data.offset_y.get(), data.viewport_height.get());
+        self.realize(data, control_context, range);
+
+        let available_size = Size::new(size.width, f32::INFINITY);
+        let mut width = 0.0f32;
+        for item in self.realized.iter() {
+            item.view
+                .borrow_mut()
+                .measure(drawing_context, available_size);
+            let rect = item.view.borrow().get_rect();
+            width = width.max(rect.width);
+            self.layout.set_measured_height(item.index, rect.height);
+        }
+
+        self.rect = Rect::new(
+            0.0f32,
+            0.0f32,
+            if size.width.is_finite() {
+                size.width
+            } else {
+                width
+            },
+            self.layout.get_total_height(),
+        );
+    }
+
+    fn set_rect(
+        &mut self,
+        _data: &mut VirtualizingStackPanel<V>,
+        _control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+
+        for item in self.realized.iter() {
+            let child_rect = Rect::new(
+                rect.x,
+                rect.y + self.layout.get_top(item.index),
+                rect.width,
+                self.layout.get_height(item.index),
+            );
+            item.view.borrow_mut().set_rect(child_rect);
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &VirtualizingStackPanel<V>,
+        control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if point.is_inside(&self.rect) {
+            let children = control_context.get_children();
+            for child in children.into_iter() {
+                let c = child.borrow();
+                let rect = c.get_rect();
+                if point.is_inside(&rect) {
+                    let child_hit_test = c.hit_test(point);
+                    match child_hit_test {
+                        HitTestResult::Current => return HitTestResult::Child(child.clone()),
+                        HitTestResult::Child(..) => return child_hit_test,
+                        HitTestResult::Nothing => (),
+                    }
+                }
+            }
+            HitTestResult::Nothing
+        } else {
+            HitTestResult::Nothing
+        }
+    }
+
+    fn to_primitives(
+        &self,
+        _data: &VirtualizingStackPanel<V>,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let children = control_context.get_children();
+        for child in children.into_iter() {
+            let (mut vec2, mut overlay2) = child.borrow().to_primitives(drawing_context);
+            vec.append(&mut vec2);
+            overlay.append(&mut overlay2);
+        }
+
+        (vec, overlay)
+    }
+}
+
+///
+/// Heights and positions of all the items (realized or not).
+///
+/// With estimated heights the positions are taken from the Fenwick tree
+/// of the heights, so the layout pass does not sum the whole collection.
+/// The tree is updated in place when an item is measured and rebuilt lazily
+/// (from the first changed index only) after inserts and removes.
+///
+struct ItemLayout {
+    item_height: ItemHeight,
+    measured_heights: Vec<Option<f32>>,
+
+    // 1-based Fenwick tree, the first height_tree_valid_len nodes are up to date
+    height_tree: RefCell<Vec<f32>>,
+    height_tree_valid_len: Cell<usize>,
+}
+
+impl ItemLayout {
+    fn new(item_height: ItemHeight, len: usize) -> Self {
+        ItemLayout {
+            item_height,
+            measured_heights: vec![None; len],
+            height_tree: RefCell::new(vec![0.0f32]),
+            height_tree_valid_len: Cell::new(0),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.measured_heights.len()
+    }
+
+    fn reset(&mut self, len: usize) {
+        self.measured_heights = vec![None; len];
+        self.invalidate_height_tree(0);
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.measured_heights.insert(index, None);
+        self.invalidate_height_tree(index);
+    }
+
+    fn remove(&mut self, index: usize) {
+        if index < self.measured_heights.len() {
+            self.measured_heights.remove(index);
+            self.invalidate_height_tree(index);
+        }
+    }
+
+    fn set_measured_height(&mut self, index: usize, height: f32) {
+        if let ItemHeight::Estimated(..) = self.item_height {
+            let delta = height - self.get_height(index);
+            self.measured_heights[index] = Some(height);
+
+            if delta != 0.0f32 && index < self.height_tree_valid_len.get() {
+                let mut height_tree = self.height_tree.borrow_mut();
+                let valid_len = self.height_tree_valid_len.get();
+                let mut node = index + 1;
+                while node <= valid_len {
+                    height_tree[node] += delta;
+                    node += lowest_bit(node);
+                }
+            }
+        }
+    }
+
+    fn get_height(&self, index: usize) -> f32 {
+        match self.item_height {
+            ItemHeight::Fixed(height) => height,
+            ItemHeight::Estimated(height) => self.measured_heights[index].unwrap_or(height),
+        }
+    }
+
+    fn get_top(&self, index: usize) -> f32 {
+        match self.item_height {
+            ItemHeight::Fixed(height) => index as f32 * height,
+            ItemHeight::Estimated(..) => {
+                self.update_height_tree();
+                let height_tree = self.height_tree.borrow();
+                let mut top = 0.0f32;
+                let mut node = index;
+                while node > 0 {
+                    top += height_tree[node];
+                    node -= lowest_bit(node);
+                }
+                top
+            }
+        }
+    }
+
+    fn get_total_height(&self) -> f32 {
+        self.get_top(self.len())
+    }
+
+    ///
+    /// Returns indices of the items intersecting the viewport.
+    ///
+    fn get_visible_range(&self, offset: f32, viewport_height: f32) -> Range<usize> {
+        let len = self.len();
+        let end_offset = offset + viewport_height;
+        match self.item_height {
+            ItemHeight::Fixed(height) => {
+                if height <= 0.0f32 {
+                    return 0..0;
+                }
+                let start = ((offset / height).floor().max(0.0f32) as usize).min(len);
+                let end = ((end_offset / height).ceil().max(0.0f32) as usize).min(len);
+                start..end.max(start)
+            }
+            ItemHeight::Estimated(..) => {
+                // the first item with the bottom below the offset
+                let start = self.find_last_top(|top| top <= offset);
+                if self.get_top(start) >= end_offset {
+                    return start..start;
+                }
+
+                // the item after the last one with the top above the end of the viewport
+                let end = (self.find_last_top(|top| top < end_offset) + 1).min(len);
+                start..end
+            }
+        }
+    }
+
+    ///
+    /// Returns the last index with get_top(index) matching the predicate
+    /// (the predicate has to be true for smaller tops).
+    ///
+    fn find_last_top<F: Fn(f32) -> bool>(&self, predicate: F) -> usize {
+        self.update_height_tree();
+        let height_tree = self.height_tree.borrow();
+        let len = self.len();
+
+        let mut index = 0;
+        let mut top = 0.0f32;
+        let mut step = len.next_power_of_two();
+        while step > 0 {
+            if index + step <= len && predicate(top + height_tree[index + step]) {
+                index += step;
+                top += height_tree[index];
+            }
+            step /= 2;
+        }
+        index
+    }
+
+    fn invalidate_height_tree(&mut self, index: usize) {
+        if index < self.height_tree_valid_len.get() {
+            self.height_tree_valid_len.set(index);
+        }
+    }
+
+    ///
+    /// Rebuilds nodes of the Fenwick tree which cover changed indices.
+    ///
+    fn update_height_tree(&self) {
+        let len = self.len();
+        let valid_len = self.height_tree_valid_len.get();
+        if valid_len == len {
+            return;
+        }
+
+        let mut height_tree = self.height_tree.borrow_mut();
+        height_tree.resize(len + 1, 0.0f32);
+        for node in valid_len + 1..=len {
+            // the node sums its own height and the nodes covering the rest of its range
+            let mut sum = self.get_height(node - 1);
+            let mut step = 1;
+            while step < lowest_bit(node) {
+                sum += height_tree[node - step];
+                step *= 2;
+            }
+            height_tree[node] = sum;
+        }
+        self.height_tree_valid_len.set(len);
+    }
+}
+
+fn lowest_bit(node: usize) -> usize {
+    node & node.wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+    use fui_macros::ui;
+    use typemap::TypeMap;
+
+    use super::*;
+    use crate::controls::*;
+    use crate::test_helpers::*;
+
+    struct TestItem {
+        name: String,
+        view_count: Rc<Cell<usize>>,
+    }
+
+    impl ViewModel for TestItem {
+        fn create_view(view_model: &Rc<RefCell<Self>>) -> Rc<RefCell<dyn ControlObject>> {
+            let view_model = view_model.borrow();
+            view_model.view_count.set(view_model.view_count.get() + 1);
+            ui! {
+                Text { text: view_model.name.clone() }
+            }
+        }
+    }
+
+    ///
+    /// Panel with 10 000 items, each 20 pixels high, in the 100 pixels high viewport.
+    ///
+    struct Fixture {
+        items: ObservableVec<Rc<RefCell<TestItem>>>,
+        view_count: Rc<Cell<usize>>,
+        offset_y: Property<f32>,
+        panel: Rc<RefCell<StyledControl<VirtualizingStackPanel<TestItem>>>>,
+        drawing_context: TestDrawingContext,
+    }
+
+    impl Fixture {
+        fn new(
+            view_cache_size: usize,
+            view_recycler: Option<Rc<dyn ViewRecycler<TestItem>>>,
+        ) -> Self {
+            let view_count = Rc::new(Cell::new(0));
+            let mut items = ObservableVec::new();
+            for index in 0..10_000 {
+                items.push(Self::create_item(&view_count, index));
+            }
+            let mut offset_y = Property::new(0.0f32);
+
+            let items_collection: &dyn ObservableCollection<Rc<RefCell<TestItem>>> = &items;
+            let panel = VirtualizingStackPanel::builder()
+                .items(Box::new(items_collection.map(|item| item.clone())))
+                .offset_y(Property::binded_two_way(&mut offset_y))
+                .viewport_height(Property::new(100.0f32))
+                .item_height(ItemHeight::Fixed(20.0f32))
+                .view_cache_size(view_cache_size)
+                .view_recycler(view_recycler)
+                .build()
+                .to_view(
+                    None,
+                    ViewContext {
+                        attached_values: TypeMap::new(),
+                        children: Box::new(Vec::<Rc<RefCell<dyn ControlObject>>>::new()),
+                    },
+                );
+
+            let mut fixture = Fixture {
+                items,
+                view_count,
+                offset_y,
+                panel,
+                drawing_context: TestDrawingContext::new(),
+            };
+            fixture.layout();
+            fixture
+        }
+
+        fn create_item(view_count: &Rc<Cell<usize>>, index: usize) -> Rc<RefCell<TestItem>> {
+            Rc::new(RefCell::new(TestItem {
+                name: format!("item {}", index),
+                view_count: view_count.clone(),
+            }))
+        }
+
+        fn layout(&mut self) {
+            CallbackExecutor::execute_all_in_queue();
+            let mut panel = self.panel.borrow_mut();
+            panel.measure(&mut self.drawing_context, Size::new(200.0, f32::INFINITY));
+            let height = panel.get_rect().height;
+            panel.set_rect(Rect::new(0.0, 0.0, 200.0, height));
+        }
+
+        fn scroll_to(&mut self, offset_y: f32) {
+            self.offset_y.set(offset_y);
+            self.layout();
+        }
+
+        fn get_realized_count(&self) -> usize {
+            self.panel.borrow().get_context().get_children().len()
+        }
+
+        fn get_drawn_texts(&mut self) -> Vec<String> {
+            // texts of the rebound views are updated by the next layout
+            self.layout();
+            let (primitives, _) = self.panel.borrow().to_primitives(&mut self.drawing_context);
+            primitives
+                .into_iter()
+                .filter_map(|primitive| match primitive {
+                    Primitive::Text { text, .. } => Some(text),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_only_views_in_viewport_are_realized() {
+        let mut fixture = Fixture::new(0, None);
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 5);
+        assert_eq!(fixture.panel.borrow().get_rect().height, 200_000.0f32);
+
+        fixture.scroll_to(50_000.0f32);
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 10);
+
+        // changes after the viewport don't realize any views
+        fixture
+            .items
+            .insert(9_000, Fixture::create_item(&fixture.view_count, 10_000));
+        fixture.items.remove(8_000);
+        fixture.layout();
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 10);
+
+        // items moved into the viewport by the changes before it are realized
+        fixture
+            .items
+            .insert(0, Fixture::create_item(&fixture.view_count, 10_001));
+        fixture.layout();
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 11);
+
+        // the inserted item is the only new view
+        fixture
+            .items
+            .insert(2_502, Fixture::create_item(&fixture.view_count, 10_002));
+        fixture.layout();
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 12);
+        assert_eq!(
+            fixture.get_drawn_texts(),
+            vec![
+                "item 2499",
+                "item 2500",
+                "item 10002",
+                "item 2501",
+                "item 2502"
+            ]
+        );
+
+        fixture.items.remove(2_502);
+        fixture.layout();
+        assert_eq!(fixture.get_realized_count(), 5);
+        assert_eq!(fixture.view_count.get(), 13);
+    }
+
+    #[test]
+    fn test_removed_items_are_purged_from_view_cache() {
+        let mut fixture = Fixture::new(10, None);
+        let first = fixture.items.get(0);
+        fixture.scroll_to(1_000.0f32);
+        assert_eq!(fixture.view_count.get(), 10);
+
+        // the view of the removed item is not kept in the cache
+        fixture.items.remove(0);
+        fixture.layout();
+        assert_eq!(Rc::strong_count(&first), 1);
+        assert_eq!(fixture.view_count.get(), 11);
+
+        // the other cached views are still reused
+        fixture.scroll_to(0.0f32);
+        assert_eq!(fixture.view_count.get(), 12);
+        assert_eq!(
+            fixture.get_drawn_texts(),
+            vec!["item 1", "item 2", "item 3", "item 4", "item 5"]
+        );
+    }
+
+    #[test]
+    fn test_views_are_recycled() {
+        let created_count = Rc::new(Cell::new(0));
+        let created_count_clone = created_count.clone();
+        let view_recycler = BindingViewRecycler::new(
+            move || {
+                created_count_clone.set(created_count_clone.get() + 1);
+                let name = Property::new(String::new());
+                let view = ui! {
+                    Text { text: Property::binded_from(&name) }
+                };
+                (name, view)
+            },
+            |name: &mut Property<String>, item: &Rc<RefCell<TestItem>>| {
+                name.set(item.borrow().name.clone())
+            },
+        );
+        let mut fixture = Fixture::new(0, Some(Rc::new(view_recycler)));
+        assert_eq!(created_count.get(), 5);
+
+        fixture.scroll_to(50_010.0f32);
+        assert_eq!(fixture.get_realized_count(), 6);
+        assert_eq!(created_count.get(), 6);
+        assert_eq!(
+            fixture.get_drawn_texts(),
+            vec![
+                "item 2500",
+                "item 2501",
+                "item 2502",
+                "item 2503",
+                "item 2504",
+                "item 2505"
+            ]
+        );
+
+        fixture.scroll_to(0.0f32);
+        fixture.items.remove(2);
+        fixture.layout();
+        assert_eq!(created_count.get(), 6);
+        assert_eq!(
+            fixture.get_drawn_texts(),
+            vec!["item 0", "item 1", "item 3", "item 4", "item 5"]
+        );
+
+        // view models don't create any views
+        assert_eq!(fixture.view_count.get(), 0);
+    }
+
+    #[test]
+    fn test_fixed_item_layout() {
+        let layout = ItemLayout::new(ItemHeight::Fixed(10.0f32), 100_000);
+        assert_eq!(layout.get_total_height(), 1_000_000.0f32);
+        assert_eq!(layout.get_visible_range(0.0f32, 35.0f32), 0..4);
+        assert_eq!(
+            layout.get_visible_range(500_005.0f32, 20.0f32),
+            50_000..50_003
+        );
+        assert_eq!(
+            layout.get_visible_range(999_990.0f32, 50.0f32),
+            99_999..100_000
+        );
+    }
+
+    #[test]
+    fn test_estimated_item_layout() {
+        let mut layout = ItemLayout::new(ItemHeight::Estimated(10.0f32), 10);
+        layout.set_measured_height(1, 30.0f32);
+        assert_eq!(layout.get_top(2), 40.0f32);
+        assert_eq!(layout.get_total_height(), 120.0f32);
+        assert_eq!(layout.get_visible_range(15.0f32, 30.0f32), 1..3);
+
+        // measured height moves with the item
+        layout.insert(0);
+        assert_eq!(layout.get_height(2), 30.0f32);
+        layout.remove(2);
+        assert_eq!(layout.get_total_height(), 100.0f32);
+        assert_eq!(layout.get_visible_range(200.0f32, 30.0f32), 10..10);
+    }
+
+    #[test]
+    fn test_estimated_item_layout_with_many_items() {
+        let mut layout = ItemLayout::new(ItemHeight::Estimated(10.0f32), 100_000);
+        assert_eq!(layout.get_total_height(), 1_000_000.0f32);
+        assert_eq!(
+            layout.get_visible_range(500_005.0f32, 20.0f32),
+            50_000..50_003
+        );
+
+        for index in 50_000..50_003 {
+            layout.set_measured_height(index, 20.0f32);
+        }
+        assert_eq!(layout.get_top(50_003), 500_060.0f32);
+        assert_eq!(layout.get_total_height(), 1_000_030.0f32);
+        assert_eq!(
+            layout.get_visible_range(500_030.0f32, 30.0f32),
+            50_001..50_003
+        );
+
+        // the measured heights move with the items
+        layout.insert(0);
+        layout.insert(100_001);
+        layout.remove(50_000);
+        assert_eq!(layout.len(), 100_001);
+        assert_eq!(layout.get_height(50_000), 20.0f32);
+        assert_eq!(layout.get_height(50_002), 20.0f32);
+        assert_eq!(layout.get_height(50_003), 10.0f32);
+        assert_eq!(layout.get_top(50_000), 500_000.0f32);
+        assert_eq!(layout.get_total_height(), 1_000_040.0f32);
+
+        layout.set_measured_height(100_000, 5.0f32);
+        assert_eq!(layout.get_total_height(), 1_000_035.0f32);
+        assert_eq!(
+            layout.get_visible_range(1_000_050.0f32, 100.0f32),
+            100_001..100_001
+        );
+        assert_eq!(
+            layout.get_visible_range(1_000_022.0f32, 10.0f32),
+            99_999..100_001
+        );
+    }
+}
@@ -4,6 +4,9 @@ pub use controllers::*;
 mod controls;
 pub use controls::*;
 
+mod dialogs;
+pub use dialogs::*;
+
 mod layout;
 pub use layout::*;
 
@@ -12,5 +15,8 @@ pub use special::*;
 
 mod style;
 
+#[cfg(test)]
+mod test_helpers;
+
 mod view_models;
 pub use view_models::*;
@@ -32,7 +32,7 @@ impl<T: 'static> DataHolder<T> {
 // Default DataHolder Style
 //
 
-#[derive(TypedBuilder)]
+#[derive(Clone, TypedBuilder)]
 pub struct DefaultDataHolderStyleParams {}
 
 pub struct DefaultDataHolderStyle;
@@ -3,14 +3,6 @@ use drawing::primitive_extensions::*;
 use drawing::units::*;
 use fui_core::*;
 
-const BORDER_LIGHT1: Color = [0.65, 0.65, 0.65, 1.0];
-const BORDER_LIGHT2: Color = [0.35, 0.35, 0.35, 1.0];
-const BORDER_MEDIUM1: Color = [0.15, 0.15, 0.15, 1.0];
-const BORDER_MEDIUM2: Color = [0.12, 0.12, 0.12, 1.0];
-const BORDER_DARK: Color = [0.0, 0.0, 0.0, 1.0];
-
-const GRADIENT_TOP_NORMAL: Color = [0.35, 0.35, 0.35, 1.0];
-const GRADIENT_BOT_NORMAL: Color = [0.28, 0.28, 0.28, 1.0];
 const HOVER_HIGHLIGHT: [f32; 3] = [1.25f32, 1.25f32, 1.25f32];
 const PRESSED_HIGHLIGHT: [f32; 3] = [0.75f32, 0.75f32, 0.75f32];
 const FOCUSED_HIGHLIGHT: [f32; 3] = [2.0f32, 2.0f32, 1.0f32];
@@ -24,8 +16,28 @@ fn multiply_color(color: Color, factor: [f32; 3]) -> Color {
     ]
 }
 
+fn get_brush_colors(brush: ThemeBrush) -> (Color, Color) {
+    match brush {
+        ThemeBrush::Color(color) => (color, color),
+        ThemeBrush::LinearGradient {
+            start_color,
+            end_color,
+        } => (start_color, end_color),
+    }
+}
+
+fn get_border_colors(theme: &Theme) -> (Color, Color, Color, Color) {
+    (
+        theme.get_color("BorderLight1"),
+        theme.get_color("BorderLight2"),
+        theme.get_color("BorderMedium1"),
+        theme.get_color("BorderMedium2"),
+    )
+}
+
 pub fn border_3d_single(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -42,24 +54,25 @@ pub fn border_3d_single(
     let grad_width = h2 / grad_len;
     let grad_height = width * height / grad_len;
 
+    let (border_light1, border_light2, border_medium1, border_medium2) = get_border_colors(theme);
     let (mut border_color1, mut border_color2, mut border_color3, mut border_color4) = if is_pressed
     {
         (
-            multiply_color(BORDER_MEDIUM2, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_MEDIUM1, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_LIGHT2, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_LIGHT1, PRESSED_HIGHLIGHT),
+            multiply_color(border_medium2, PRESSED_HIGHLIGHT),
+            multiply_color(border_medium1, PRESSED_HIGHLIGHT),
+            multiply_color(border_light2, PRESSED_HIGHLIGHT),
+            multiply_color(border_light1, PRESSED_HIGHLIGHT),
         )
     } else {
         if is_hover {
             (
-                multiply_color(BORDER_LIGHT1, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_LIGHT2, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_MEDIUM1, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_MEDIUM2, HOVER_HIGHLIGHT),
+                multiply_color(border_light1, HOVER_HIGHLIGHT),
+                multiply_color(border_light2, HOVER_HIGHLIGHT),
+                multiply_color(border_medium1, HOVER_HIGHLIGHT),
+                multiply_color(border_medium2, HOVER_HIGHLIGHT),
             )
         } else {
-            (BORDER_LIGHT1, BORDER_LIGHT2, BORDER_MEDIUM1, BORDER_MEDIUM2)
+            (border_light1, border_light2, border_medium1, border_medium2)
         }
     };
 
@@ -117,6 +130,7 @@ pub fn border_3d_single(
 
 pub fn border_3d_single_rounded(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -134,24 +148,25 @@ pub fn border_3d_single_rounded(
     let grad_width = h2 / grad_len;
     let grad_height = width * height / grad_len;
 
+    let (border_light1, border_light2, border_medium1, border_medium2) = get_border_colors(theme);
     let (mut border_color1, mut border_color2, mut border_color3, mut border_color4) = if is_pressed
     {
         (
-            multiply_color(BORDER_MEDIUM2, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_MEDIUM1, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_LIGHT2, PRESSED_HIGHLIGHT),
-            multiply_color(BORDER_LIGHT1, PRESSED_HIGHLIGHT),
+            multiply_color(border_medium2, PRESSED_HIGHLIGHT),
+            multiply_color(border_medium1, PRESSED_HIGHLIGHT),
+            multiply_color(border_light2, PRESSED_HIGHLIGHT),
+            multiply_color(border_light1, PRESSED_HIGHLIGHT),
         )
     } else {
         if is_hover {
             (
-                multiply_color(BORDER_LIGHT1, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_LIGHT2, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_MEDIUM1, HOVER_HIGHLIGHT),
-                multiply_color(BORDER_MEDIUM2, HOVER_HIGHLIGHT),
+                multiply_color(border_light1, HOVER_HIGHLIGHT),
+                multiply_color(border_light2, HOVER_HIGHLIGHT),
+                multiply_color(border_medium1, HOVER_HIGHLIGHT),
+                multiply_color(border_medium2, HOVER_HIGHLIGHT),
             )
         } else {
-            (BORDER_LIGHT1, BORDER_LIGHT2, BORDER_MEDIUM1, BORDER_MEDIUM2)
+            (border_light1, border_light2, border_medium1, border_medium2)
         }
     };
 
@@ -199,6 +214,7 @@ pub fn border_3d_single_rounded(
 
 pub fn border_3d(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -211,6 +227,7 @@ pub fn border_3d(
 
     border_3d_single(
         vec,
+        theme,
         x + line_thickness,
         y + line_thickness,
         width - line_thickness * 2.0f32,
@@ -227,12 +244,15 @@ pub fn border_3d(
             PixelThickness::new(line_thickness),
         ),
         thickness: PixelThickness::new(line_thickness),
-        brush: Brush::Color { color: BORDER_DARK },
+        brush: Brush::Color {
+            color: theme.get_color("BorderDark"),
+        },
     });
 }
 
 pub fn border_3d_rounded(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -246,6 +266,7 @@ pub fn border_3d_rounded(
 
     border_3d_single_rounded(
         vec,
+        theme,
         x + line_thickness,
         y + line_thickness,
         width - line_thickness * 2.0f32,
@@ -264,22 +285,26 @@ pub fn border_3d_rounded(
             radius + line_thickness,
         ),
         thickness: PixelThickness::new(line_thickness),
-        brush: Brush::Color { color: BORDER_DARK },
+        brush: Brush::Color {
+            color: theme.get_color("BorderDark"),
+        },
     });
 }
 
 pub fn border_3d_edit(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
     height: f32,
     is_focused: bool,
 ) {
-    border_3d_single(vec, x, y, width, height, false, false, is_focused);
+    border_3d_single(vec, theme, x, y, width, height, false, false, is_focused);
 
     border_3d_single(
         vec,
+        theme,
         x + 2.0f32,
         y + 2.0f32,
         width - 4.0f32,
@@ -289,7 +314,7 @@ pub fn border_3d_edit(
         is_focused,
     );
 
-    let mut color = [0.4, 0.4, 0.4, 1.0];
+    let mut color = theme.get_color("EditBorder");
     if is_focused {
         color = multiply_color(color, FOCUSED_HIGHLIGHT);
     }
@@ -353,14 +378,28 @@ pub fn gradient_rect_rounded(
     });
 }
 
+pub fn brush_rect(
+    vec: &mut Vec<Primitive>,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    brush: ThemeBrush,
+) {
+    let (start_color, end_color) = get_brush_colors(brush);
+    gradient_rect(vec, x, y, width, height, start_color, end_color);
+}
+
 pub fn shadow_under_rect(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
     height: f32,
     shadow_size: f32,
 ) {
+    let shadow_color = theme.get_color("Shadow");
     let mut shadow_fill_path = Vec::new();
     shadow_fill_path.append(&mut rect_path(PixelRect::new(
         PixelPoint::new(x + width, y),
@@ -379,14 +418,15 @@ pub fn shadow_under_rect(
             ),
             radius: shadow_size,
             feather: shadow_size * 0.5f32,
-            inner_color: [0.0, 0.0, 0.0, 0.35],
-            outer_color: [0.0, 0.0, 0.0, 0.0],
+            inner_color: shadow_color,
+            outer_color: [shadow_color[0], shadow_color[1], shadow_color[2], 0.0],
         },
     });
 }
 
 pub fn shadow_under_rect_rounded(
     vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -394,6 +434,7 @@ pub fn shadow_under_rect_rounded(
     radius: f32,
     shadow_size: f32,
 ) {
+    let shadow_color = theme.get_color("Shadow");
     let mut shadow_fill_path = Vec::new();
     shadow_fill_path.append(&mut rect_path(PixelRect::new(
         PixelPoint::new(x + width, y),
@@ -416,14 +457,15 @@ pub fn shadow_under_rect_rounded(
             ),
             radius: shadow_size,
             feather: shadow_size * 0.5f32,
-            inner_color: [0.0, 0.0, 0.0, 0.35],
-            outer_color: [0.0, 0.0, 0.0, 0.0],
+            inner_color: shadow_color,
+            outer_color: [shadow_color[0], shadow_color[1], shadow_color[2], 0.0],
         },
     });
 }
 
 pub fn button(
     mut vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -432,19 +474,21 @@ pub fn button(
     is_hover: bool,
     _is_focused: bool,
 ) {
+    let (gradient_top_normal, gradient_bottom_normal) =
+        get_brush_colors(theme.get_brush("ButtonBackground"));
     let (gradient_top_color, gradient_bottom_color) = if is_pressed {
         (
-            multiply_color(GRADIENT_BOT_NORMAL, PRESSED_HIGHLIGHT),
-            multiply_color(GRADIENT_TOP_NORMAL, PRESSED_HIGHLIGHT),
+            multiply_color(gradient_bottom_normal, PRESSED_HIGHLIGHT),
+            multiply_color(gradient_top_normal, PRESSED_HIGHLIGHT),
         )
     } else {
         if is_hover {
             (
-                multiply_color(GRADIENT_TOP_NORMAL, HOVER_HIGHLIGHT),
-                multiply_color(GRADIENT_BOT_NORMAL, HOVER_HIGHLIGHT),
+                multiply_color(gradient_top_normal, HOVER_HIGHLIGHT),
+                multiply_color(gradient_bottom_normal, HOVER_HIGHLIGHT),
             )
         } else {
-            (GRADIENT_TOP_NORMAL, GRADIENT_BOT_NORMAL)
+            (gradient_top_normal, gradient_bottom_normal)
         }
     };
 
@@ -458,10 +502,11 @@ pub fn button(
         gradient_bottom_color,
     );
 
-    border_3d(&mut vec, x, y, width, height, is_pressed, is_hover, false);
+    border_3d(&mut vec, theme, x, y, width, height, is_pressed, is_hover, false);
 
     shadow_under_rect(
         &mut vec,
+        theme,
         x,
         y,
         width,
@@ -472,6 +517,7 @@ pub fn button(
 
 pub fn button_rounded(
     mut vec: &mut Vec<Primitive>,
+    theme: &Theme,
     x: f32,
     y: f32,
     width: f32,
@@ -481,24 +527,27 @@ pub fn button_rounded(
     is_hover: bool,
     _is_focused: bool,
 ) {
+    let (gradient_top_normal, gradient_bottom_normal) =
+        get_brush_colors(theme.get_brush("ButtonBackground"));
     let (gradient_top_color, gradient_bottom_color) = if is_pressed {
         (
-            multiply_color(GRADIENT_BOT_NORMAL, PRESSED_HIGHLIGHT),
-            multiply_color(GRADIENT_TOP_NORMAL, PRESSED_HIGHLIGHT),
+            multiply_color(gradient_bottom_normal, PRESSED_HIGHLIGHT),
+            multiply_color(gradient_top_normal, PRESSED_HIGHLIGHT),
         )
     } else {
         if is_hover {
             (
-                multiply_color(GRADIENT_TOP_NORMAL, HOVER_HIGHLIGHT),
-                multiply_color(GRADIENT_BOT_NORMAL, HOVER_HIGHLIGHT),
+                multiply_color(gradient_top_normal, HOVER_HIGHLIGHT),
+                multiply_color(gradient_bottom_normal, HOVER_HIGHLIGHT),
             )
         } else {
-            (GRADIENT_TOP_NORMAL, GRADIENT_BOT_NORMAL)
+            (gradient_top_normal, gradient_bottom_normal)
         }
     };
 
     shadow_under_rect_rounded(
         &mut vec,
+        theme,
         x,
         y,
         width,
@@ -519,6 +568,6 @@ pub fn button_rounded(
     );
 
     border_3d_rounded(
-        &mut vec, x, y, width, height, radius, is_pressed, is_hover, false,
+        &mut vec, theme, x, y, width, height, radius, is_pressed, is_hover, false,
     );
 }
@@ -0,0 +1,119 @@
+// Fixtures shared by the control tests.
+
+use fui_core::*;
+
+///
+/// Every character is 10 pixels wide and 20 pixels high.
+///
+pub struct TestResources;
+
+impl Resources for TestResources {
+    fn resolve_font(
+        &mut self,
+        family: &str,
+        _weight: u16,
+        _style: FontStyle,
+        _text: &str,
+    ) -> anyhow::Result<String> {
+        Ok(family.to_string())
+    }
+
+    fn resolve_font_runs(
+        &mut self,
+        family: &str,
+        _weight: u16,
+        _style: FontStyle,
+        text: &str,
+    ) -> anyhow::Result<Vec<FontRun>> {
+        Ok(vec![FontRun {
+            font_name: family.to_string(),
+            text: text.to_string(),
+        }])
+    }
+
+    fn get_font_dimensions(
+        &mut self,
+        _font_name: &str,
+        _size: u8,
+        text: &str,
+    ) -> anyhow::Result<(u16, u16)> {
+        Ok((text.chars().count() as u16 * 10, 20))
+    }
+
+    fn get_font_dimensions_each_char(
+        &mut self,
+        _font_name: &str,
+        _size: u8,
+        text: &str,
+    ) -> anyhow::Result<(Vec<i16>, u16)> {
+        Ok((text.chars().map(|_| 10).collect(), 20))
+    }
+
+    fn create_texture(
+        &mut self,
+        _memory: &[u8],
+        _width: u16,
+        _height: u16,
+        _format: ColorFormat,
+        _updatable: bool,
+    ) -> anyhow::Result<i32> {
+        unimplemented!("controls under test don't use textures")
+    }
+
+    fn update_texture(
+        &mut self,
+        _texture_id: i32,
+        _memory: &[u8],
+        _offset_x: u16,
+        _offset_y: u16,
+        _width: u16,
+        _height: u16,
+    ) -> anyhow::Result<()> {
+        unimplemented!("controls under test don't use textures")
+    }
+
+    fn get_texture_size(&mut self, _texture_id: i32) -> anyhow::Result<(u16, u16)> {
+        unimplemented!("controls under test don't use textures")
+    }
+}
+
+pub struct TestDrawingContext {
+    resources: TestResources,
+}
+
+impl TestDrawingContext {
+    pub fn new() -> Self {
+        TestDrawingContext {
+            resources: TestResources,
+        }
+    }
+}
+
+impl Default for TestDrawingContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DrawingContext for TestDrawingContext {
+    fn get_drawing_area_size(&self) -> (u16, u16) {
+        (800, 600)
+    }
+
+    fn get_resources(&mut self) -> &mut dyn Resources {
+        &mut self.resources
+    }
+}
+
+pub fn key_pressed(keycode: Keycode) -> InputEvent {
+    key_pressed_with_modifiers(keycode, KeyModifiers::default())
+}
+
+pub fn key_pressed_with_modifiers(keycode: Keycode, modifiers: KeyModifiers) -> InputEvent {
+    InputEvent::KeyboardInput(KeyEvent {
+        state: KeyState::Pressed,
+        keycode: Some(keycode),
+        text: None,
+        modifiers,
+    })
+}
@@ -19,5 +19,11 @@ pub use self::rounding::*;
 mod size;
 pub use self::size::*;
 
+mod text_selection;
+pub use self::text_selection::*;
+
+mod text_undo_stack;
+pub use self::text_undo_stack::*;
+
 mod unit;
 pub use self::unit::*;
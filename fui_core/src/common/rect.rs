@@ -15,4 +15,18 @@ impl Rect {
             height: height,
         }
     }
+
+    ///
+    /// Returns the smallest rect containing both rects.
+    ///
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect::new(
+            x,
+            y,
+            (self.x + self.width).max(other.x + other.width) - x,
+            (self.y + self.height).max(other.y + other.height) - y,
+        )
+    }
 }
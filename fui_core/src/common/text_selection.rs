@@ -0,0 +1,270 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+///
+/// Selection in the edited text.
+///
+/// Anchor is the place where the selection started and caret is the place
+/// where it ends (where the caret is drawn). Both are indices of extended
+/// grapheme clusters (user-perceived characters), so the caret never ends up
+/// inside of combining sequence, emoji ZWJ sequence or flag.
+/// Selection is empty when they are equal.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextSelection {
+    pub anchor: usize,
+    pub caret: usize,
+}
+
+impl TextSelection {
+    pub fn new(caret: usize) -> Self {
+        TextSelection {
+            anchor: caret,
+            caret,
+        }
+    }
+
+    pub fn with_anchor(anchor: usize, caret: usize) -> Self {
+        TextSelection { anchor, caret }
+    }
+
+    pub fn get_start(&self) -> usize {
+        self.anchor.min(self.caret)
+    }
+
+    pub fn get_end(&self) -> usize {
+        self.anchor.max(self.caret)
+    }
+
+    pub fn get_length(&self) -> usize {
+        self.get_end() - self.get_start()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.caret
+    }
+
+    ///
+    /// Moves the caret. The selection is extended when `extend` is true,
+    /// otherwise it is collapsed at the new caret position.
+    ///
+    pub fn move_caret(&self, caret: usize, extend: bool) -> Self {
+        if extend {
+            TextSelection::with_anchor(self.anchor, caret)
+        } else {
+            TextSelection::new(caret)
+        }
+    }
+
+    ///
+    /// Limits the selection to the text of the given length (in graphemes).
+    ///
+    pub fn clamp(&self, length: usize) -> Self {
+        TextSelection::with_anchor(self.anchor.min(length), self.caret.min(length))
+    }
+
+    pub fn get_selected_text(&self, text: &str) -> String {
+        text.graphemes(true)
+            .skip(self.get_start())
+            .take(self.get_length())
+            .collect()
+    }
+
+    ///
+    /// Replaces the selected text (or inserts at the caret when the selection is empty).
+    /// Returns the new text and the selection with the caret after the inserted text.
+    ///
+    pub fn replace(&self, text: &str, insert: &str) -> (String, TextSelection) {
+        let head: String = text
+            .graphemes(true)
+            .take(self.get_start())
+            .chain(std::iter::once(insert))
+            .collect();
+        let tail: String = text.graphemes(true).skip(self.get_end()).collect();
+
+        // inserted text can join the grapheme before it (like combining mark does)
+        let caret = get_grapheme_count(&head);
+        (head + &tail, TextSelection::new(caret))
+    }
+}
+
+pub fn get_graphemes(text: &str) -> Vec<&str> {
+    text.graphemes(true).collect()
+}
+
+pub fn get_grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+///
+/// Sums the widths of the graphemes (as returned by get_font_dimensions_each_char)
+/// into the widths of the graphemes.
+///
+pub fn get_grapheme_widths(text: &str, char_widths: &[f32]) -> Vec<f32> {
+    let mut char_index = 0;
+    text.graphemes(true)
+        .map(|grapheme| {
+            let count = grapheme.chars().count();
+            let width = char_widths
+                .iter()
+                .skip(char_index)
+                .take(count)
+                .sum();
+            char_index += count;
+            width
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GraphemeClass {
+    Space,
+    Word,
+    Punctuation,
+}
+
+fn get_grapheme_class(grapheme: &str) -> GraphemeClass {
+    // the base character decides (the rest are combining marks and joiners)
+    let c = grapheme.chars().next().unwrap_or(' ');
+    if c.is_whitespace() {
+        GraphemeClass::Space
+    } else if c.is_alphanumeric() || c == '_' {
+        GraphemeClass::Word
+    } else {
+        GraphemeClass::Punctuation
+    }
+}
+
+///
+/// Returns the start of the word before the grapheme index (used by Ctrl+Left).
+///
+pub fn find_previous_word_start(text: &str, index: usize) -> usize {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    let mut index = index.min(graphemes.len());
+    while index > 0 && get_grapheme_class(graphemes[index - 1]) == GraphemeClass::Space {
+        index -= 1;
+    }
+    if index > 0 {
+        let class = get_grapheme_class(graphemes[index - 1]);
+        while index > 0 && get_grapheme_class(graphemes[index - 1]) == class {
+            index -= 1;
+        }
+    }
+    index
+}
+
+///
+/// Returns the start of the word after the grapheme index (used by Ctrl+Right).
+///
+pub fn find_next_word_start(text: &str, index: usize) -> usize {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    let mut index = index.min(graphemes.len());
+    if index < graphemes.len() {
+        let class = get_grapheme_class(graphemes[index]);
+        if class != GraphemeClass::Space {
+            while index < graphemes.len() && get_grapheme_class(graphemes[index]) == class {
+                index += 1;
+            }
+        }
+    }
+    while index < graphemes.len() && get_grapheme_class(graphemes[index]) == GraphemeClass::Space {
+        index += 1;
+    }
+    index
+}
+
+///
+/// Returns the selection of the word at the grapheme index (used by double click).
+///
+pub fn find_word_at(text: &str, index: usize) -> TextSelection {
+    let graphemes: Vec<&str> = text.graphemes(true).collect();
+    if graphemes.is_empty() {
+        return TextSelection::new(0);
+    }
+
+    // the caret after the last grapheme of the word still selects the word
+    let mut index = index.min(graphemes.len() - 1);
+    if index > 0
+        && get_grapheme_class(graphemes[index]) == GraphemeClass::Space
+        && get_grapheme_class(graphemes[index - 1]) != GraphemeClass::Space
+    {
+        index -= 1;
+    }
+
+    let class = get_grapheme_class(graphemes[index]);
+    let mut start = index;
+    while start > 0 && get_grapheme_class(graphemes[start - 1]) == class {
+        start -= 1;
+    }
+    let mut end = index + 1;
+    while end < graphemes.len() && get_grapheme_class(graphemes[end]) == class {
+        end += 1;
+    }
+    TextSelection::with_anchor(start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_selection() {
+        let selection = TextSelection::with_anchor(7, 2);
+        assert_eq!(selection.get_selected_text("ałbęcde fg"), "bęcde");
+
+        let (text, selection) = selection.replace("ałbęcde fg", "X");
+        assert_eq!(text, "ałX fg");
+        assert_eq!(selection, TextSelection::new(3));
+
+        let (text, selection) = selection.replace(&text, "yz");
+        assert_eq!(text, "ałXyz fg");
+        assert_eq!(selection, TextSelection::new(5));
+    }
+
+    #[test]
+    fn test_multi_codepoint_graphemes() {
+        // "e" with combining acute accent, family (ZWJ sequence) and flag
+        let text = "Re\u{301}ne\u{301} \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F1F5}\u{1F1F1}";
+        assert_eq!(get_grapheme_count(text), 7);
+
+        // backspace at the end removes the whole flag
+        let (text, selection) = TextSelection::with_anchor(7, 6).replace(text, "");
+        assert_eq!(selection, TextSelection::new(6));
+        assert_eq!(get_grapheme_count(&text), 6);
+
+        // delete removes the whole family
+        let (text, selection) = TextSelection::with_anchor(5, 6).replace(&text, "");
+        assert_eq!(text, "Re\u{301}ne\u{301} ");
+        assert_eq!(selection, TextSelection::new(5));
+
+        // combining mark typed after the letter joins its grapheme
+        let (text, selection) = TextSelection::new(1).replace("Zoe", "\u{308}");
+        assert_eq!(text, "Z\u{308}oe");
+        assert_eq!(selection, TextSelection::new(1));
+        let (text, selection) = TextSelection::new(3).replace(&text, "\u{308}");
+        assert_eq!(text, "Z\u{308}oe\u{308}");
+        assert_eq!(selection, TextSelection::new(3));
+
+        assert_eq!(find_word_at("Zoe\u{308} Smith", 1), TextSelection::with_anchor(0, 3));
+        assert_eq!(find_next_word_start("Zoe\u{308} Smith", 0), 4);
+
+        let widths = get_grapheme_widths("ae\u{301}\u{1F1F5}\u{1F1F1}", &[5.0, 6.0, 0.0, 8.0, 8.0]);
+        assert_eq!(widths, vec![5.0, 6.0, 16.0]);
+    }
+
+    #[test]
+    fn test_word_navigation() {
+        let text = "one  two, three";
+        assert_eq!(find_next_word_start(text, 0), 5);
+        assert_eq!(find_next_word_start(text, 5), 8);
+        assert_eq!(find_next_word_start(text, 8), 10);
+        assert_eq!(find_next_word_start(text, 10), 15);
+        assert_eq!(find_previous_word_start(text, 15), 10);
+        assert_eq!(find_previous_word_start(text, 10), 8);
+        assert_eq!(find_previous_word_start(text, 8), 5);
+        assert_eq!(find_previous_word_start(text, 4), 0);
+
+        assert_eq!(find_word_at(text, 6), TextSelection::with_anchor(5, 8));
+        assert_eq!(find_word_at(text, 3), TextSelection::with_anchor(0, 3));
+        assert_eq!(find_word_at(text, 15), TextSelection::with_anchor(10, 15));
+    }
+}
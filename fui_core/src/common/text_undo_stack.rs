@@ -0,0 +1,244 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::common::TextSelection;
+
+const MAX_UNDO_STEPS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEditKind {
+    ///
+    /// Typed characters are grouped into word-sized steps.
+    ///
+    Typing,
+
+    ///
+    /// Subsequent Backspace / Delete keys are grouped into one step.
+    ///
+    Deleting,
+
+    ///
+    /// Every edit is a separate step (paste, cut etc.).
+    ///
+    Other,
+}
+
+struct TextUndoEntry {
+    text: String,
+    selection: TextSelection,
+}
+
+///
+/// Undo / redo stack of the text editor.
+///
+/// Entries keep the whole text with the selection, so undo restores
+/// the caret and the selection too.
+///
+pub struct TextUndoStack {
+    undo_entries: Vec<TextUndoEntry>,
+    redo_entries: Vec<TextUndoEntry>,
+
+    last_kind: Option<TextEditKind>,
+    last_text: String,
+    last_selection: TextSelection,
+}
+
+impl TextUndoStack {
+    pub fn new() -> Self {
+        TextUndoStack {
+            undo_entries: Vec::new(),
+            redo_entries: Vec::new(),
+            last_kind: None,
+            last_text: String::new(),
+            last_selection: TextSelection::new(0),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_entries.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_entries.clear();
+        self.redo_entries.clear();
+        self.last_kind = None;
+    }
+
+    ///
+    /// Records the edit. The edit joins the previous step when it is of the same kind
+    /// and continues at the place where the previous one ended.
+    ///
+    pub fn record(
+        &mut self,
+        kind: TextEditKind,
+        text_before: &str,
+        selection_before: TextSelection,
+        text_after: &str,
+        selection_after: TextSelection,
+    ) {
+        if text_before != self.last_text {
+            // the text was changed from outside (by the binding)
+            self.clear();
+        }
+
+        let is_continued = self.last_kind == Some(kind)
+            && kind != TextEditKind::Other
+            && selection_before.is_empty()
+            && selection_before == self.last_selection;
+        let is_new_word = kind == TextEditKind::Typing
+            && selection_before.caret > 0
+            && text_before
+                .graphemes(true)
+                .nth(selection_before.caret - 1)
+                .is_some_and(|grapheme| grapheme.chars().all(char::is_whitespace));
+
+        if !is_continued || is_new_word {
+            self.undo_entries.push(TextUndoEntry {
+                text: text_before.to_string(),
+                selection: selection_before,
+            });
+            if self.undo_entries.len() > MAX_UNDO_STEPS {
+                self.undo_entries.remove(0);
+            }
+        }
+        self.redo_entries.clear();
+
+        self.last_kind = Some(kind);
+        self.last_text = text_after.to_string();
+        self.last_selection = selection_after;
+    }
+
+    ///
+    /// Returns the text and the selection before the last step.
+    ///
+    pub fn undo(
+        &mut self,
+        text: &str,
+        selection: TextSelection,
+    ) -> Option<(String, TextSelection)> {
+        if text != self.last_text {
+            self.clear();
+        }
+        let entry = self.undo_entries.pop()?;
+        self.redo_entries.push(TextUndoEntry {
+            text: text.to_string(),
+            selection,
+        });
+        Some(self.restore(entry))
+    }
+
+    ///
+    /// Returns the text and the selection of the last undone step.
+    ///
+    pub fn redo(
+        &mut self,
+        text: &str,
+        selection: TextSelection,
+    ) -> Option<(String, TextSelection)> {
+        if text != self.last_text {
+            self.clear();
+        }
+        let entry = self.redo_entries.pop()?;
+        self.undo_entries.push(TextUndoEntry {
+            text: text.to_string(),
+            selection,
+        });
+        Some(self.restore(entry))
+    }
+
+    fn restore(&mut self, entry: TextUndoEntry) -> (String, TextSelection) {
+        // the next edit starts a new step
+        self.last_kind = None;
+        self.last_text = entry.text.clone();
+        self.last_selection = entry.selection;
+        (entry.text, entry.selection)
+    }
+}
+
+impl Default for TextUndoStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(
+        stack: &mut TextUndoStack,
+        text: &mut String,
+        selection: &mut TextSelection,
+        typed: &str,
+    ) {
+        for c in typed.chars() {
+            let (new_text, new_selection) = selection.replace(text, &c.to_string());
+            stack.record(
+                TextEditKind::Typing,
+                text,
+                *selection,
+                &new_text,
+                new_selection,
+            );
+            *text = new_text;
+            *selection = new_selection;
+        }
+    }
+
+    #[test]
+    fn test_typing_is_undone_by_words() {
+        let mut stack = TextUndoStack::new();
+        let mut text = String::new();
+        let mut selection = TextSelection::new(0);
+        type_text(&mut stack, &mut text, &mut selection, "one two");
+
+        let (text1, selection1) = stack.undo(&text, selection).unwrap();
+        assert_eq!(text1, "one ");
+        assert_eq!(selection1, TextSelection::new(4));
+
+        let (text2, selection2) = stack.undo(&text1, selection1).unwrap();
+        assert_eq!(text2, "");
+        assert_eq!(selection2, TextSelection::new(0));
+        assert!(!stack.can_undo());
+
+        let (text3, selection3) = stack.redo(&text2, selection2).unwrap();
+        assert_eq!(text3, "one ");
+        assert_eq!(selection3, TextSelection::new(4));
+    }
+
+    #[test]
+    fn test_caret_move_and_selection_start_new_step() {
+        let mut stack = TextUndoStack::new();
+        let mut text = String::new();
+        let mut selection = TextSelection::new(0);
+        type_text(&mut stack, &mut text, &mut selection, "abc");
+
+        // caret moved to the start
+        selection = TextSelection::new(0);
+        type_text(&mut stack, &mut text, &mut selection, "x");
+
+        // selection replaced
+        selection = TextSelection::with_anchor(1, 4);
+        let (new_text, new_selection) = selection.replace(&text, "");
+        stack.record(
+            TextEditKind::Deleting,
+            &text,
+            selection,
+            &new_text,
+            new_selection,
+        );
+
+        let (text1, selection1) = stack.undo(&new_text, new_selection).unwrap();
+        assert_eq!(text1, "xabc");
+        assert_eq!(selection1, TextSelection::with_anchor(1, 4));
+        let (text2, selection2) = stack.undo(&text1, selection1).unwrap();
+        assert_eq!(text2, "abc");
+        assert_eq!(selection2, TextSelection::new(0));
+
+        // text changed from outside clears the history
+        assert!(stack.undo("other", TextSelection::new(0)).is_none());
+    }
+}
@@ -6,6 +6,7 @@ use drawing::primitive::Primitive;
 use crate::common::*;
 use crate::control::ControlObject;
 use crate::events::*;
+use crate::services::CursorIcon;
 use crate::DrawingContext;
 
 pub enum HitTestResult {
@@ -24,6 +25,9 @@ pub trait ControlBehavior {
 
     fn hit_test(&self, point: Point) -> HitTestResult;
 
+    /// Returns the cursor shown when the pointer is at the point over the control.
+    fn get_cursor_icon(&self, point: Point) -> Option<CursorIcon>;
+
     /// Returns primitives.
     /// First vector contains primitives for normal layer (most controls).
     /// Second vector contains primitives for overlay layer (used by popup / menu etc.).
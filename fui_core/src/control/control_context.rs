@@ -4,7 +4,7 @@ use std::rc::{Rc, Weak};
 use typemap::TypeMap;
 
 use crate::control::*;
-use crate::{Services, observable::*};
+use crate::{Rect, Services, StyleRegistry, Theme, observable::*};
 
 pub struct ControlContext {
     self_weak: Option<Weak<RefCell<dyn ControlObject>>>,
@@ -15,8 +15,12 @@ pub struct ControlContext {
     attached_values: TypeMap,
 
     services: Option<Weak<RefCell<Services>>>,
+    parent_inherited_properties: InheritedProperties,
+    inherited_properties_subscriptions: Vec<EventSubscription>,
 
     is_dirty: bool,
+    is_redraw_needed: bool,
+    redraw_rect: Option<Rect>,
 }
 
 impl ControlContext {
@@ -28,7 +32,11 @@ impl ControlContext {
             children_collection_changed_event_subscription: None,
             attached_values: view_context.attached_values,
             services: None,
+            parent_inherited_properties: InheritedProperties::new(),
+            inherited_properties_subscriptions: Vec::new(),
             is_dirty: true,
+            is_redraw_needed: false,
+            redraw_rect: None,
         }
     }
 
@@ -67,6 +75,10 @@ impl ControlContext {
         &self.attached_values
     }
 
+    pub fn get_attached_values_mut(&mut self) -> &mut TypeMap {
+        &mut self.attached_values
+    }
+
     ///
     /// Available only when control is added to the window.
     /// Not yet set during control setup().
@@ -75,6 +87,19 @@ impl ControlContext {
         self.services.clone()
     }
 
+    ///
+    /// Returns the current theme (or the default one
+    /// when there is no theme service).
+    ///
+    pub fn get_theme(&self) -> Rc<Theme> {
+        self.services
+            .as_ref()
+            .and_then(|services| services.upgrade())
+            .and_then(|services| services.borrow().get_theme_service())
+            .map(|theme_service| theme_service.borrow().get_theme())
+            .unwrap_or_else(Theme::get_default)
+    }
+
     pub fn set_services(&mut self, services: Option<Weak<RefCell<Services>>>) {
         for child in self.children.into_iter() {
             child.borrow_mut().get_context_mut().set_services(services.clone());
@@ -82,6 +107,63 @@ impl ControlContext {
         self.services = services;
     }
 
+    ///
+    /// Returns values inherited from the parents
+    /// overridden by the attached values of this control.
+    ///
+    pub fn get_inherited_properties(&self) -> InheritedProperties {
+        self.parent_inherited_properties
+            .with_attached_values(&self.attached_values)
+    }
+
+    ///
+    /// Sets values inherited from the parent and propagates them down.
+    ///
+    /// Called when the control is added to the parent.
+    ///
+    pub fn set_parent_inherited_properties(&mut self, inherited_properties: InheritedProperties) {
+        self.parent_inherited_properties = inherited_properties;
+        self.is_dirty = true;
+        self.propagate_inherited_properties();
+    }
+
+    ///
+    /// Returns style registries set with Styles attached value
+    /// on this control and its parents (the innermost first).
+    ///
+    pub fn get_style_registries(&self) -> Vec<Rc<RefCell<StyleRegistry>>> {
+        self.get_inherited_properties().style_registries
+    }
+
+    ///
+    /// Subscribes for changes of inherited attached values of this control,
+    /// so new values are propagated down to the children.
+    ///
+    pub fn watch_inherited_properties(&mut self) {
+        let control = match self.self_weak {
+            Some(ref control) => control.clone(),
+            None => return,
+        };
+
+        let subscriptions = vec![
+            watch_inherited_property(self.attached_values.get::<FontFamily>(), &control),
+            watch_inherited_property(self.attached_values.get::<FontSize>(), &control),
+            watch_inherited_property(self.attached_values.get::<FontWeight>(), &control),
+            watch_inherited_property(self.attached_values.get::<Foreground>(), &control),
+        ];
+        self.inherited_properties_subscriptions = subscriptions.into_iter().flatten().collect();
+    }
+
+    fn propagate_inherited_properties(&mut self) {
+        let inherited_properties = self.get_inherited_properties();
+        for child in self.children.into_iter() {
+            child
+                .borrow_mut()
+                .get_context_mut()
+                .set_parent_inherited_properties(inherited_properties.clone());
+        }
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.is_dirty
     }
@@ -94,4 +176,72 @@ impl ControlContext {
             }
         }
     }
+
+    pub fn is_redraw_needed(&self) -> bool {
+        self.is_redraw_needed
+    }
+
+    ///
+    /// Requests drawing without measuring and arranging the controls again.
+    /// Can be used when only the look has changed.
+    ///
+    pub fn set_is_redraw_needed(&mut self, is_redraw_needed: bool) {
+        self.is_redraw_needed = is_redraw_needed;
+        self.redraw_rect = None;
+        if is_redraw_needed {
+            if let Some(ref parent) = self.get_parent() {
+                parent
+                    .borrow_mut()
+                    .get_context_mut()
+                    .set_is_redraw_needed(is_redraw_needed)
+            }
+        }
+    }
+
+    ///
+    /// Requests drawing only the rect (in window coordinates) of the control
+    /// without measuring and arranging the controls again (like blinking caret).
+    /// The rect is passed up to the root view, where rects of all the controls are merged.
+    ///
+    pub fn add_redraw_rect(&mut self, rect: Rect) {
+        self.redraw_rect = match self.redraw_rect {
+            Some(redraw_rect) => Some(redraw_rect.union(&rect)),
+            // the whole control is drawn already
+            None if self.is_redraw_needed => None,
+            None => Some(rect),
+        };
+        self.is_redraw_needed = true;
+        if let Some(ref parent) = self.get_parent() {
+            parent.borrow_mut().get_context_mut().add_redraw_rect(rect)
+        }
+    }
+
+    ///
+    /// Returns the part of the window to draw again
+    /// (None when the whole window is requested).
+    ///
+    pub fn get_redraw_rect(&self) -> Option<Rect> {
+        if self.is_redraw_needed {
+            self.redraw_rect
+        } else {
+            None
+        }
+    }
+}
+
+fn watch_inherited_property<T: 'static + Clone + PartialEq>(
+    property: Option<&Property<T>>,
+    control: &Weak<RefCell<dyn ControlObject>>,
+) -> Option<EventSubscription> {
+    property.map(|property| {
+        let control = control.clone();
+        property.on_changed(move |_| {
+            if let Some(control) = control.upgrade() {
+                let mut control = control.borrow_mut();
+                let context = control.get_context_mut();
+                context.propagate_inherited_properties();
+                context.set_is_dirty(true);
+            }
+        })
+    })
 }
@@ -0,0 +1,162 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use typemap::TypeMap;
+
+use crate::{Color, Property, StyleRegistry, Styles};
+
+///
+/// Inherited attached value with font family (or font file name) of the texts.
+///
+pub struct FontFamily;
+impl typemap::Key for FontFamily {
+    type Value = Property<String>;
+}
+
+///
+/// Inherited attached value with font size of the texts.
+///
+pub struct FontSize;
+impl typemap::Key for FontSize {
+    type Value = Property<f32>;
+}
+
+///
+/// Inherited attached value with font weight of the texts
+/// (100 - 900, where 400 is normal and 700 is bold).
+///
+pub struct FontWeight;
+impl typemap::Key for FontWeight {
+    type Value = Property<u16>;
+}
+
+///
+/// Inherited attached value with foreground (text) color.
+///
+pub struct Foreground;
+impl typemap::Key for Foreground {
+    type Value = Property<Color>;
+}
+
+///
+/// Values inherited down the control tree.
+///
+/// Values set as attached values on the control override
+/// the ones inherited from its parents.
+///
+#[derive(Clone)]
+pub struct InheritedProperties {
+    ///
+    /// Style registries set with Styles attached value (the innermost first).
+    ///
+    pub style_registries: Vec<Rc<RefCell<StyleRegistry>>>,
+
+    pub font_family: Option<String>,
+    pub font_size: Option<f32>,
+    pub font_weight: Option<u16>,
+    pub foreground: Option<Color>,
+}
+
+impl InheritedProperties {
+    pub fn new() -> Self {
+        InheritedProperties {
+            style_registries: Vec::new(),
+            font_family: None,
+            font_size: None,
+            font_weight: None,
+            foreground: None,
+        }
+    }
+
+    pub fn with_attached_values(&self, attached_values: &TypeMap) -> Self {
+        let mut properties = self.clone();
+        if let Some(registry) = attached_values.get::<Styles>() {
+            properties.style_registries.insert(0, registry.clone());
+        }
+        if let Some(font_family) = attached_values.get::<FontFamily>() {
+            properties.font_family = Some(font_family.get());
+        }
+        if let Some(font_size) = attached_values.get::<FontSize>() {
+            properties.font_size = Some(font_size.get());
+        }
+        if let Some(font_weight) = attached_values.get::<FontWeight>() {
+            properties.font_weight = Some(font_weight.get());
+        }
+        if let Some(foreground) = attached_values.get::<Foreground>() {
+            properties.foreground = Some(foreground.get());
+        }
+        properties
+    }
+}
+
+impl Default for InheritedProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Rect;
+    use crate::events::test_helpers::*;
+    use crate::observable::*;
+    use crate::ControlObject;
+
+    #[test]
+    fn test_attached_values_override_inherited_ones() {
+        let mut parent_values = TypeMap::new();
+        parent_values.insert::<FontSize>(Property::new(30.0f32));
+        parent_values.insert::<Foreground>(Property::new([1.0f32, 0.0, 0.0, 1.0]));
+        let parent = InheritedProperties::new().with_attached_values(&parent_values);
+
+        let mut child_values = TypeMap::new();
+        child_values.insert::<FontSize>(Property::new(12.0f32));
+        let child = parent.with_attached_values(&child_values);
+
+        assert_eq!(child.font_size, Some(12.0f32));
+        assert_eq!(child.foreground, Some([1.0f32, 0.0, 0.0, 1.0]));
+        assert_eq!(child.font_family, None);
+        assert_eq!(parent.font_size, Some(30.0f32));
+    }
+
+    #[test]
+    fn test_values_are_propagated_when_control_is_moved_to_other_parent() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let grandchild: Rc<RefCell<dyn ControlObject>> =
+            TestControl::create(rect, TypeMap::new(), Vec::new());
+        let child: Rc<RefCell<dyn ControlObject>> =
+            TestControl::create(rect, TypeMap::new(), vec![grandchild.clone()]);
+
+        let mut items_a = ObservableVec::<()>::new();
+        let child_clone = child.clone();
+        let children_a =
+            (&items_a as &dyn ObservableCollection<()>).map(move |_| child_clone.clone());
+        let mut values_a = TypeMap::new();
+        values_a.insert::<Foreground>(Property::new([1.0f32, 0.0, 0.0, 1.0]));
+        let _parent_a =
+            TestControl::create_with_children_collection(rect, values_a, Box::new(children_a));
+
+        let mut items_b = ObservableVec::<()>::new();
+        let child_clone = child.clone();
+        let children_b =
+            (&items_b as &dyn ObservableCollection<()>).map(move |_| child_clone.clone());
+        let mut values_b = TypeMap::new();
+        values_b.insert::<Foreground>(Property::new([0.0f32, 0.0, 1.0, 1.0]));
+        values_b.insert::<FontSize>(Property::new(30.0f32));
+        let _parent_b =
+            TestControl::create_with_children_collection(rect, values_b, Box::new(children_b));
+
+        items_a.push(());
+        CallbackExecutor::execute_all_in_queue();
+        let properties = grandchild.borrow().get_context().get_inherited_properties();
+        assert_eq!(properties.foreground, Some([1.0f32, 0.0, 0.0, 1.0]));
+        assert_eq!(properties.font_size, None);
+
+        items_a.remove(0);
+        items_b.push(());
+        CallbackExecutor::execute_all_in_queue();
+        let properties = grandchild.borrow().get_context().get_inherited_properties();
+        assert_eq!(properties.foreground, Some([0.0f32, 0.0, 1.0, 1.0]));
+        assert_eq!(properties.font_size, Some(30.0f32));
+    }
+}
@@ -7,5 +7,8 @@ pub use control_object::*;
 mod control_context;
 pub use control_context::*;
 
+mod inherited_properties;
+pub use inherited_properties::*;
+
 mod styled_control;
 pub use styled_control::*;
@@ -5,6 +5,7 @@ use std::rc::Rc;
 use crate::common::*;
 use crate::control::*;
 use crate::observable::*;
+use crate::services::CursorIcon;
 use crate::style::*;
 use crate::{DrawingContext, view::ViewContext, EventContext};
 
@@ -31,6 +32,7 @@ impl<D: 'static> StyledControl<D> {
         // set self
         let control_weak = Rc::downgrade(&control);
         control.borrow_mut().control_context.set_self(control_weak);
+        control.borrow_mut().control_context.watch_inherited_properties();
 
         let control_clone: Rc<RefCell<dyn ControlObject>> = control.clone();
         let handler = Box::new(
@@ -42,8 +44,15 @@ impl<D: 'static> StyledControl<D> {
                         .set_parent(&control_clone);
                     
                     // dynamically created controls require to set services
-                    let services = control_clone.borrow_mut().get_context().get_services();
-                    child.borrow_mut().get_context_mut().set_services(services);
+                    let (services, inherited_properties) = {
+                        let control = control_clone.borrow();
+                        let context = control.get_context();
+                        (context.get_services(), context.get_inherited_properties())
+                    };
+                    let mut child = child.borrow_mut();
+                    let child_context = child.get_context_mut();
+                    child_context.set_parent_inherited_properties(inherited_properties);
+                    child_context.set_services(services);
                 }
                 control_clone
                     .borrow_mut()
@@ -62,16 +71,17 @@ impl<D: 'static> StyledControl<D> {
             .get_context_mut()
             .set_children_collection_changed_event_subscription(subscription);
 
+        let inherited_properties = control.borrow().get_context().get_inherited_properties();
         for child in control.borrow_mut()
             .get_context_mut()
             .get_children()
             .into_iter() {
             let control: Rc<RefCell<dyn ControlObject>> = control.clone();
 
-            child
-                .borrow_mut()
-                .get_context_mut()
-                .set_parent(&control);
+            let mut child = child.borrow_mut();
+            let child_context = child.get_context_mut();
+            child_context.set_parent(&control);
+            child_context.set_parent_inherited_properties(inherited_properties.clone());
         }
 
         control.borrow_mut().setup();
@@ -176,6 +186,11 @@ impl<D: 'static> ControlBehavior for StyledControl<D> {
         self.style.hit_test(&self.data, &self.control_context, point)
     }
 
+    fn get_cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.style
+            .get_cursor_icon(&self.data, &self.control_context, point)
+    }
+
     fn to_primitives(&self, drawing_context: &mut dyn DrawingContext) -> (Vec<Primitive>, Vec<Primitive>) {
         self.style
             .to_primitives(&self.data, &self.control_context, drawing_context)
@@ -3,3 +3,9 @@ pub use self::resources::*;
 
 mod drawing_context;
 pub use self::drawing_context::*;
+
+mod text_layout;
+pub use self::text_layout::*;
+
+mod rich_text_layout;
+pub use self::rich_text_layout::*;
@@ -2,17 +2,57 @@ pub use drawing::color::ColorFormat;
 
 use anyhow::Result;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
+///
+/// Part of the text displayed with one font.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontRun {
+    pub font_name: String,
+    pub text: String,
+}
+
 pub trait Resources {
+    ///
+    /// Returns the font name (to be used with the other methods)
+    /// best matching the family, weight and style, that can display the text.
+    ///
+    fn resolve_font(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        text: &str,
+    ) -> Result<String>;
+
+    ///
+    /// Splits the text into runs displayed with the font best matching
+    /// the family, weight and style, and with the fallback fonts
+    /// for the characters missing in it.
+    ///
+    fn resolve_font_runs(
+        &mut self,
+        family: &str,
+        weight: u16,
+        style: FontStyle,
+        text: &str,
+    ) -> Result<Vec<FontRun>>;
+
     fn get_font_dimensions(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(u16, u16)>;
 
     fn get_font_dimensions_each_char(
         &mut self,
-        font_name: &'static str,
+        font_name: &str,
         size: u8,
         text: &str,
     ) -> Result<(Vec<i16>, u16)>;
@@ -0,0 +1,327 @@
+use crate::common::Point;
+use crate::drawing::text_layout::*;
+
+///
+/// Run of the rich text measured with its font.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeasuredRun {
+    pub text: String,
+    pub char_widths: Vec<f32>,
+    pub font_height: f32,
+}
+
+///
+/// Part of the rich text line drawn with the style of one run.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichTextSegment {
+    pub run_index: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub text: String,
+}
+
+///
+/// Lays out the text made of runs with different fonts.
+///
+/// Lines are wrapped across the runs, the same way as for the TextLayout.
+/// Each line is as high as the highest run on it.
+///
+pub struct RichTextLayout {
+    chars: Vec<char>,
+    char_widths: Vec<f32>,
+    run_ends: Vec<usize>,
+    run_heights: Vec<f32>,
+    ellipsis_width: f32,
+    layout: TextLayout,
+    line_heights: Vec<f32>,
+}
+
+impl RichTextLayout {
+    pub fn new(runs: Vec<MeasuredRun>, ellipsis_width: f32, params: TextLayoutParams) -> Self {
+        let mut text = String::new();
+        let mut char_widths = Vec::new();
+        let mut run_ends = Vec::new();
+        let mut run_heights = Vec::new();
+        for run in runs {
+            let count = run.text.chars().count();
+            let mut widths = run.char_widths;
+            widths.resize(count, 0.0f32);
+
+            text.push_str(&run.text);
+            char_widths.extend(widths);
+            run_ends.push(char_widths.len());
+            run_heights.push(run.font_height);
+        }
+
+        let max_height = run_heights.iter().cloned().fold(0.0f32, f32::max);
+        let layout = TextLayout::new(
+            &text,
+            char_widths.clone(),
+            max_height,
+            ellipsis_width,
+            params,
+        );
+
+        let mut rich_layout = RichTextLayout {
+            chars: text.chars().collect(),
+            char_widths,
+            run_ends,
+            run_heights,
+            ellipsis_width,
+            layout,
+            line_heights: Vec::new(),
+        };
+        rich_layout.update_line_heights();
+        rich_layout
+    }
+
+    pub fn get_lines(&self) -> &[TextLine] {
+        self.layout.get_lines()
+    }
+
+    pub fn get_width(&self) -> f32 {
+        self.layout.get_width()
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.line_heights.iter().sum()
+    }
+
+    ///
+    /// Lays out the text for the available width.
+    ///
+    pub fn layout(&mut self, max_width: f32) {
+        self.layout.layout(max_width);
+        self.update_line_heights();
+    }
+
+    ///
+    /// Returns the parts of the lines to draw aligned in the box of the given width.
+    ///
+    pub fn get_segments(&self, box_width: f32) -> Vec<RichTextSegment> {
+        let params = *self.layout.get_params();
+        let mut segments = Vec::new();
+        let mut line_top = 0.0f32;
+
+        for (line, line_height) in self.layout.get_lines().iter().zip(&self.line_heights) {
+            let font_height = self.get_line_font_height(line);
+            let full_width = if line.has_ellipsis {
+                line.width + self.ellipsis_width
+            } else {
+                line.width
+            };
+            let free_space = (box_width - full_width).max(0.0f32);
+
+            let is_justified = params.alignment == TextAlignment::Justify
+                && !line.is_paragraph_end
+                && !line.has_ellipsis;
+            let spaces = self.chars[line.start..line.end]
+                .iter()
+                .filter(|c| **c == ' ')
+                .count();
+            let extra_space = if is_justified && spaces > 0 {
+                free_space / spaces as f32
+            } else {
+                0.0f32
+            };
+
+            let mut x = match params.alignment {
+                TextAlignment::Left | TextAlignment::Justify => 0.0f32,
+                TextAlignment::Center => free_space / 2.0f32,
+                TextAlignment::Right => free_space,
+            };
+
+            // runs of different heights share the bottom line
+            let bottom = line_top + (line_height + font_height) / 2.0f32;
+
+            let line_start = segments.len();
+            let mut current: Option<RichTextSegment> = None;
+            for i in line.start..line.end {
+                let run_index = self.get_run_index(i);
+                let is_gap = extra_space > 0.0f32 && self.chars[i] == ' ';
+
+                let continues = match current {
+                    Some(ref segment) => segment.run_index == run_index && !is_gap,
+                    None => false,
+                };
+                if !continues {
+                    if let Some(segment) = current.take() {
+                        segments.push(segment);
+                    }
+                }
+
+                if is_gap {
+                    x += self.char_widths[i] + extra_space;
+                    continue;
+                }
+
+                let segment = current.get_or_insert_with(|| {
+                    let height = self.run_heights[run_index];
+                    RichTextSegment {
+                        run_index,
+                        x,
+                        y: bottom - height,
+                        width: 0.0f32,
+                        height,
+                        text: String::new(),
+                    }
+                });
+                segment.text.push(self.chars[i]);
+                segment.width += self.char_widths[i];
+                x += self.char_widths[i];
+            }
+            if let Some(segment) = current.take() {
+                segments.push(segment);
+            }
+
+            if line.has_ellipsis && !self.run_ends.is_empty() {
+                if segments.len() == line_start {
+                    let run_index = self.get_run_index(line.start);
+                    let height = self.run_heights[run_index];
+                    segments.push(RichTextSegment {
+                        run_index,
+                        x,
+                        y: bottom - height,
+                        width: 0.0f32,
+                        height,
+                        text: String::new(),
+                    });
+                }
+                if let Some(segment) = segments.last_mut() {
+                    segment.text.push_str(ELLIPSIS);
+                    segment.width += self.ellipsis_width;
+                }
+            }
+
+            line_top += line_height;
+        }
+
+        segments
+    }
+
+    ///
+    /// Returns index of the run at the point (relative to the layout)
+    /// when the text is aligned in the box of the given width.
+    ///
+    pub fn get_run_at(&self, box_width: f32, point: Point) -> Option<usize> {
+        let mut line_top = 0.0f32;
+        for line_height in &self.line_heights {
+            if point.y >= line_top && point.y < line_top + line_height {
+                return self
+                    .get_segments(box_width)
+                    .into_iter()
+                    .filter(|segment| {
+                        segment.y + segment.height > line_top && segment.y < line_top + line_height
+                    })
+                    .find(|segment| point.x >= segment.x && point.x < segment.x + segment.width)
+                    .map(|segment| segment.run_index);
+            }
+            line_top += line_height;
+        }
+        None
+    }
+
+    fn get_run_index(&self, char_index: usize) -> usize {
+        self.run_ends
+            .iter()
+            .position(|end| *end > char_index)
+            .unwrap_or_else(|| self.run_ends.len().max(1) - 1)
+    }
+
+    fn get_line_font_height(&self, line: &TextLine) -> f32 {
+        if self.run_ends.is_empty() {
+            return 0.0f32;
+        }
+        if line.start == line.end {
+            // empty lines get the height of the run they are in
+            return self.run_heights[self.get_run_index(line.start)];
+        }
+        let first_run = self.get_run_index(line.start);
+        let last_run = self.get_run_index(line.end - 1);
+        self.run_heights[first_run..=last_run]
+            .iter()
+            .cloned()
+            .fold(0.0f32, f32::max)
+    }
+
+    fn update_line_heights(&mut self) {
+        let line_height = self.layout.get_params().line_height;
+        self.line_heights = self
+            .layout
+            .get_lines()
+            .iter()
+            .map(|line| self.get_line_font_height(line) * line_height)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_run(text: &str, char_width: f32, font_height: f32) -> MeasuredRun {
+        MeasuredRun {
+            text: text.to_string(),
+            char_widths: vec![char_width; text.chars().count()],
+            font_height,
+        }
+    }
+
+    fn get_texts(segments: &[RichTextSegment]) -> Vec<(usize, &str)> {
+        segments
+            .iter()
+            .map(|segment| (segment.run_index, segment.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_wrapping_across_runs() {
+        let runs = vec![
+            create_run("one tw", 10.0f32, 20.0f32),
+            create_run("o three", 20.0f32, 40.0f32),
+        ];
+        let params = TextLayoutParams {
+            wrapping: TextWrapping::Word,
+            ..TextLayoutParams::default()
+        };
+        let mut layout = RichTextLayout::new(runs, 10.0f32, params);
+        layout.layout(100.0f32);
+
+        let segments = layout.get_segments(100.0f32);
+        assert_eq!(
+            get_texts(&segments),
+            vec![(0, "one tw"), (1, "o"), (1, "three")]
+        );
+
+        // first line is as high as the highest run on it,
+        // the runs are aligned to the bottom
+        assert_eq!(layout.get_height(), 80.0f32);
+        assert_eq!(segments[0].y, 20.0f32);
+        assert_eq!(segments[1].y, 0.0f32);
+        assert_eq!(segments[1].x, 60.0f32);
+        assert_eq!(segments[2].y, 40.0f32);
+    }
+
+    #[test]
+    fn test_run_at_point() {
+        let runs = vec![
+            create_run("abc", 10.0f32, 20.0f32),
+            create_run("", 10.0f32, 20.0f32),
+            create_run("de", 10.0f32, 20.0f32),
+        ];
+        let params = TextLayoutParams {
+            alignment: TextAlignment::Right,
+            ..TextLayoutParams::default()
+        };
+        let layout = RichTextLayout::new(runs, 10.0f32, params);
+
+        assert_eq!(layout.get_run_at(100.0f32, Point::new(45.0f32, 5.0f32)), None);
+        assert_eq!(layout.get_run_at(100.0f32, Point::new(55.0f32, 5.0f32)), Some(0));
+        assert_eq!(layout.get_run_at(100.0f32, Point::new(85.0f32, 5.0f32)), Some(2));
+        assert_eq!(layout.get_run_at(100.0f32, Point::new(85.0f32, 25.0f32)), None);
+    }
+}
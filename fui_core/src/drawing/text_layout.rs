@@ -0,0 +1,457 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextWrapping {
+    NoWrap,
+    Word,
+    Character,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextTrimming {
+    None,
+    CharacterEllipsis,
+    WordEllipsis,
+}
+
+pub const ELLIPSIS: &str = "\u{2026}";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextLayoutParams {
+    pub wrapping: TextWrapping,
+    pub alignment: TextAlignment,
+    pub trimming: TextTrimming,
+
+    ///
+    /// Line height as a multiple of the font height.
+    ///
+    pub line_height: f32,
+
+    pub max_lines: Option<usize>,
+}
+
+impl Default for TextLayoutParams {
+    fn default() -> Self {
+        TextLayoutParams {
+            wrapping: TextWrapping::NoWrap,
+            alignment: TextAlignment::Left,
+            trimming: TextTrimming::None,
+            line_height: 1.0f32,
+            max_lines: None,
+        }
+    }
+}
+
+///
+/// Line of the laid out text.
+/// Start and end are char indices (end is exclusive).
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextLine {
+    pub start: usize,
+    pub end: usize,
+    pub width: f32,
+    pub is_paragraph_end: bool,
+    pub has_ellipsis: bool,
+}
+
+///
+/// Part of the line drawn at the position relative to the layout.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSegment {
+    pub x: f32,
+    pub y: f32,
+    pub text: String,
+}
+
+///
+/// Splits the text into lines (explicit newlines and wrapping),
+/// trims lines with ellipsis and aligns them.
+///
+/// Works on the widths of the characters, so it can be laid out again
+/// for different width without measuring the text.
+///
+pub struct TextLayout {
+    chars: Vec<char>,
+    char_widths: Vec<f32>,
+    font_height: f32,
+    ellipsis_width: f32,
+    params: TextLayoutParams,
+    lines: Vec<TextLine>,
+}
+
+impl TextLayout {
+    pub fn new(
+        text: &str,
+        char_widths: Vec<f32>,
+        font_height: f32,
+        ellipsis_width: f32,
+        params: TextLayoutParams,
+    ) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut char_widths = char_widths;
+        char_widths.resize(chars.len(), 0.0f32);
+
+        let mut layout = TextLayout {
+            chars,
+            char_widths,
+            font_height,
+            ellipsis_width,
+            params,
+            lines: Vec::new(),
+        };
+        layout.layout(std::f32::INFINITY);
+        layout
+    }
+
+    pub fn get_params(&self) -> &TextLayoutParams {
+        &self.params
+    }
+
+    pub fn get_lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    pub fn get_line_height(&self) -> f32 {
+        self.font_height * self.params.line_height
+    }
+
+    pub fn get_width(&self) -> f32 {
+        self.lines
+            .iter()
+            .map(|line| self.get_line_full_width(line))
+            .fold(0.0f32, f32::max)
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.lines.len() as f32 * self.get_line_height()
+    }
+
+    pub fn get_line_text(&self, line: &TextLine) -> String {
+        let mut text: String = self.chars[line.start..line.end].iter().collect();
+        if line.has_ellipsis {
+            text.push_str(ELLIPSIS);
+        }
+        text
+    }
+
+    ///
+    /// Lays out the text for the available width.
+    ///
+    pub fn layout(&mut self, max_width: f32) {
+        let mut lines = Vec::new();
+
+        let mut paragraph_start = 0;
+        for i in 0..=self.chars.len() {
+            if i == self.chars.len() || self.chars[i] == '\n' {
+                let mut paragraph_end = i;
+                if paragraph_end > paragraph_start && self.chars[paragraph_end - 1] == '\r' {
+                    paragraph_end -= 1;
+                }
+                self.layout_paragraph(paragraph_start, paragraph_end, max_width, &mut lines);
+                paragraph_start = i + 1;
+            }
+        }
+
+        if let Some(max_lines) = self.params.max_lines {
+            if lines.len() > max_lines {
+                lines.truncate(max_lines.max(1));
+                if self.params.trimming != TextTrimming::None {
+                    if let Some(last_line) = lines.last_mut() {
+                        last_line.has_ellipsis = true;
+                    }
+                }
+            }
+        }
+
+        if self.params.trimming != TextTrimming::None {
+            for line in lines.iter_mut() {
+                if line.has_ellipsis || line.width > max_width {
+                    self.trim_line(line, max_width);
+                }
+            }
+        }
+
+        self.lines = lines;
+    }
+
+    ///
+    /// Returns the parts of the lines to draw aligned in the box of the given width.
+    ///
+    pub fn get_segments(&self, box_width: f32) -> Vec<TextSegment> {
+        let line_height = self.get_line_height();
+        let mut segments = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let y = index as f32 * line_height;
+            let free_space = (box_width - self.get_line_full_width(line)).max(0.0f32);
+
+            let is_justified = self.params.alignment == TextAlignment::Justify
+                && !line.is_paragraph_end
+                && !line.has_ellipsis;
+            let spaces = self.chars[line.start..line.end]
+                .iter()
+                .filter(|c| **c == ' ')
+                .count();
+
+            if is_justified && spaces > 0 {
+                let extra_space = free_space / spaces as f32;
+                let mut x = 0.0f32;
+                let mut word = String::new();
+                let mut word_x = 0.0f32;
+                for i in line.start..line.end {
+                    if self.chars[i] == ' ' {
+                        if !word.is_empty() {
+                            segments.push(TextSegment {
+                                x: word_x,
+                                y,
+                                text: word.clone(),
+                            });
+                            word.clear();
+                        }
+                        x += self.char_widths[i] + extra_space;
+                    } else {
+                        if word.is_empty() {
+                            word_x = x;
+                        }
+                        word.push(self.chars[i]);
+                        x += self.char_widths[i];
+                    }
+                }
+                if !word.is_empty() {
+                    segments.push(TextSegment { x: word_x, y, text: word });
+                }
+            } else {
+                let x = match self.params.alignment {
+                    TextAlignment::Left | TextAlignment::Justify => 0.0f32,
+                    TextAlignment::Center => free_space / 2.0f32,
+                    TextAlignment::Right => free_space,
+                };
+                let text = self.get_line_text(line);
+                if !text.is_empty() {
+                    segments.push(TextSegment { x, y, text });
+                }
+            }
+        }
+
+        segments
+    }
+
+    fn get_line_full_width(&self, line: &TextLine) -> f32 {
+        if line.has_ellipsis {
+            line.width + self.ellipsis_width
+        } else {
+            line.width
+        }
+    }
+
+    fn get_range_width(&self, start: usize, end: usize) -> f32 {
+        self.char_widths[start..end].iter().sum()
+    }
+
+    fn layout_paragraph(
+        &self,
+        start: usize,
+        end: usize,
+        max_width: f32,
+        lines: &mut Vec<TextLine>,
+    ) {
+        if self.params.wrapping == TextWrapping::NoWrap || !max_width.is_finite() || start == end {
+            lines.push(TextLine {
+                start,
+                end,
+                width: self.get_range_width(start, end),
+                is_paragraph_end: true,
+                has_ellipsis: false,
+            });
+            return;
+        }
+
+        let mut line_start = start;
+        while line_start < end {
+            let line_end = self.find_line_end(line_start, end, max_width);
+
+            let mut visible_end = line_end;
+            while visible_end > line_start && self.chars[visible_end - 1] == ' ' {
+                visible_end -= 1;
+            }
+
+            lines.push(TextLine {
+                start: line_start,
+                end: visible_end,
+                width: self.get_range_width(line_start, visible_end),
+                is_paragraph_end: line_end >= end,
+                has_ellipsis: false,
+            });
+
+            line_start = line_end;
+            while line_start < end && self.chars[line_start] == ' ' {
+                line_start += 1;
+            }
+        }
+    }
+
+    fn find_line_end(&self, start: usize, end: usize, max_width: f32) -> usize {
+        let mut width = 0.0f32;
+        let mut last_break = None;
+
+        let mut i = start;
+        while i < end {
+            if self.chars[i] == ' ' {
+                while i < end && self.chars[i] == ' ' {
+                    width += self.char_widths[i];
+                    i += 1;
+                }
+                if self.params.wrapping == TextWrapping::Word {
+                    last_break = Some(i);
+                }
+                continue;
+            }
+
+            if width + self.char_widths[i] > max_width && i > start {
+                // words longer than the line are broken anywhere
+                return last_break.unwrap_or(i);
+            }
+
+            width += self.char_widths[i];
+            i += 1;
+        }
+
+        end
+    }
+
+    fn trim_line(&self, line: &mut TextLine, max_width: f32) {
+        let fits = |end: usize| self.get_range_width(line.start, end) + self.ellipsis_width <= max_width;
+
+        let mut end = line.end;
+        if self.params.trimming == TextTrimming::WordEllipsis {
+            while end > line.start && !(fits(end) && self.is_word_end(end)) {
+                end -= 1;
+            }
+        }
+        if self.params.trimming == TextTrimming::CharacterEllipsis || end == line.start {
+            // words which don't fit are trimmed by characters
+            end = line.end;
+            while end > line.start && !fits(end) {
+                end -= 1;
+            }
+        }
+        while end > line.start && self.chars[end - 1] == ' ' {
+            end -= 1;
+        }
+
+        line.end = end;
+        line.width = self.get_range_width(line.start, end);
+        line.has_ellipsis = true;
+    }
+
+    fn is_word_end(&self, index: usize) -> bool {
+        index >= self.chars.len() || self.chars[index] == ' ' || self.chars[index] == '\n'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_layout(text: &str, params: TextLayoutParams, max_width: f32) -> TextLayout {
+        // every character is 10 pixels wide
+        let char_widths = vec![10.0f32; text.chars().count()];
+        let mut layout = TextLayout::new(text, char_widths, 20.0f32, 10.0f32, params);
+        layout.layout(max_width);
+        layout
+    }
+
+    fn get_texts(layout: &TextLayout) -> Vec<String> {
+        layout
+            .get_lines()
+            .iter()
+            .map(|line| layout.get_line_text(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_explicit_newlines() {
+        let layout = create_layout("ab\r\ncd\n\ne", TextLayoutParams::default(), 1000.0f32);
+        assert_eq!(get_texts(&layout), vec!["ab", "cd", "", "e"]);
+        assert_eq!(layout.get_height(), 80.0f32);
+    }
+
+    #[test]
+    fn test_word_wrapping() {
+        let params = TextLayoutParams {
+            wrapping: TextWrapping::Word,
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("one two three abcdefghijkl", params, 80.0f32);
+        assert_eq!(
+            get_texts(&layout),
+            vec!["one two", "three", "abcdefgh", "ijkl"]
+        );
+        assert_eq!(layout.get_width(), 80.0f32);
+    }
+
+    #[test]
+    fn test_character_wrapping() {
+        let params = TextLayoutParams {
+            wrapping: TextWrapping::Character,
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("one two", params, 40.0f32);
+        assert_eq!(get_texts(&layout), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_trimming_and_max_lines() {
+        let params = TextLayoutParams {
+            trimming: TextTrimming::CharacterEllipsis,
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("one two three", params, 60.0f32);
+        assert_eq!(get_texts(&layout), vec![format!("one t{}", ELLIPSIS)]);
+
+        let params = TextLayoutParams {
+            wrapping: TextWrapping::Word,
+            trimming: TextTrimming::WordEllipsis,
+            max_lines: Some(2),
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("one two three four five", params, 100.0f32);
+        assert_eq!(
+            get_texts(&layout),
+            vec!["one two".to_string(), format!("three{}", ELLIPSIS)]
+        );
+    }
+
+    #[test]
+    fn test_alignment() {
+        let params = TextLayoutParams {
+            alignment: TextAlignment::Right,
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("ab", params, 100.0f32);
+        assert_eq!(layout.get_segments(100.0f32)[0].x, 80.0f32);
+
+        let params = TextLayoutParams {
+            wrapping: TextWrapping::Word,
+            alignment: TextAlignment::Justify,
+            ..TextLayoutParams::default()
+        };
+        let layout = create_layout("a b c dddddd", params, 60.0f32);
+        let segments = layout.get_segments(60.0f32);
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| (segment.x, segment.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0.0f32, "a"), (25.0f32, "b"), (50.0f32, "c"), (0.0f32, "dddddd")]
+        );
+    }
+}
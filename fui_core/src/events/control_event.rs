@@ -1,4 +1,5 @@
 use crate::common::Point;
+use crate::events::drag_drop::{DragData, DropEffect};
 use crate::events::key_event::KeyEvent;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -13,5 +14,33 @@ pub enum ControlEvent {
     TapUp { position: Point },
     TapMove { position: Point },
 
+    DragStart { position: Point },
+    DragMove { position: Point, delta: Point },
+    DragEnd { position: Point },
+
+    LongPress { position: Point },
+
+    DoubleTap { position: Point },
+
+    /// Velocity is in pixels per second.
+    Fling { position: Point, velocity: Point },
+
+    /// Scale is relative to the previous Pinch event.
+    Pinch { center: Point, scale: f32 },
+
+    /// Angle (in radians) is relative to the previous Rotate event.
+    Rotate { center: Point, angle: f32 },
+
+    /// Delta is in lines (positive y when the wheel is rotated away from the user).
+    MouseWheel { position: Point, delta: Point },
+
+    DragEnter { data: DragData, position: Point },
+    DragOver { data: DragData, position: Point },
+    DragLeave,
+    Drop { data: DragData, position: Point, effect: DropEffect },
+
     KeyboardInput(KeyEvent),
+
+    /// The time set by EventContext::set_timer() has come.
+    Timer,
 }
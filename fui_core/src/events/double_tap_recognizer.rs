@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant};
+
+use crate::common::Point;
+use crate::events::gesture_recognizer::distance;
+use crate::events::*;
+
+const DEFAULT_DOUBLE_TAP_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_DOUBLE_TAP_TOLERANCE: f32 = 8.0f32;
+
+///
+/// Recognizes two taps (press & release) close to each other.
+///
+/// DoubleTap is raised on the second press, if it comes in the interval
+/// from the first release and its position is within the tolerance.
+///
+pub struct DoubleTapRecognizer {
+    interval: Duration,
+    tolerance: f32,
+    position: Point,
+    pressed: Option<(Instant, Point)>,
+    last_tap: Option<(Instant, Point)>,
+}
+
+impl DoubleTapRecognizer {
+    pub fn new() -> Self {
+        DoubleTapRecognizer::with_params(DEFAULT_DOUBLE_TAP_INTERVAL, DEFAULT_DOUBLE_TAP_TOLERANCE)
+    }
+
+    pub fn with_params(interval: Duration, tolerance: f32) -> Self {
+        DoubleTapRecognizer {
+            interval,
+            tolerance,
+            position: Point::new(0f32, 0f32),
+            pressed: None,
+            last_tap: None,
+        }
+    }
+}
+
+impl Default for DoubleTapRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for DoubleTapRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, time: Instant) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match event {
+            InputEvent::CursorMoved { position } => {
+                self.position = *position;
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+            } => {
+                self.pressed = Some((time, self.position));
+
+                if let Some((tap_time, tap_position)) = self.last_tap.take() {
+                    if time <= tap_time + self.interval
+                        && distance(&tap_position, &self.position) <= self.tolerance
+                    {
+                        // the second press is consumed by the double tap
+                        self.pressed = None;
+                        gestures.push(Gesture::DoubleTap {
+                            position: self.position,
+                        });
+                    }
+                }
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+            } => {
+                if let Some((press_time, press_position)) = self.pressed.take() {
+                    if time <= press_time + self.interval
+                        && distance(&press_position, &self.position) <= self.tolerance
+                    {
+                        self.last_tap = Some((time, self.position));
+                    }
+                }
+            }
+
+            _ => (),
+        }
+
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Point;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use std::time::{Duration, Instant};
+
+    fn tap(recognizer: &mut DoubleTapRecognizer, time: Instant) -> Vec<Gesture> {
+        let mut gestures = recognizer.handle_event(&left_button(ElementState::Pressed), time);
+        gestures.append(&mut recognizer.handle_event(
+            &left_button(ElementState::Released),
+            time + Duration::from_millis(50),
+        ));
+        gestures
+    }
+
+    #[test]
+    fn test_double_tap() {
+        let mut recognizer = DoubleTapRecognizer::with_params(Duration::from_millis(500), 8.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&cursor_moved(5.0, 5.0), t0);
+        assert!(tap(&mut recognizer, t0).is_empty());
+        recognizer.handle_event(&cursor_moved(7.0, 6.0), t0 + Duration::from_millis(100));
+        assert_eq!(
+            tap(&mut recognizer, t0 + Duration::from_millis(300)),
+            vec![Gesture::DoubleTap {
+                position: Point::new(7.0, 6.0)
+            }]
+        );
+
+        // third tap starts a new sequence
+        assert!(tap(&mut recognizer, t0 + Duration::from_millis(400)).is_empty());
+    }
+
+    #[test]
+    fn test_taps_too_slow() {
+        let mut recognizer = DoubleTapRecognizer::with_params(Duration::from_millis(500), 8.0f32);
+        let t0 = Instant::now();
+
+        assert!(tap(&mut recognizer, t0).is_empty());
+        assert!(tap(&mut recognizer, t0 + Duration::from_millis(800)).is_empty());
+    }
+
+    #[test]
+    fn test_taps_too_far() {
+        let mut recognizer = DoubleTapRecognizer::with_params(Duration::from_millis(500), 8.0f32);
+        let t0 = Instant::now();
+
+        assert!(tap(&mut recognizer, t0).is_empty());
+        recognizer.handle_event(&cursor_moved(50.0, 0.0), t0 + Duration::from_millis(100));
+        assert!(tap(&mut recognizer, t0 + Duration::from_millis(200)).is_empty());
+    }
+}
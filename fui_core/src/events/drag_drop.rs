@@ -0,0 +1,203 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::common::Point;
+use crate::control::ControlObject;
+use crate::observable::Callback;
+
+///
+/// Data transferred by drag and drop operation.
+///
+/// The format is a key used by drop targets to decide
+/// if they accept the data before downcasting the payload.
+///
+#[derive(Clone)]
+pub struct DragData {
+    format: String,
+    payload: Rc<dyn Any>,
+}
+
+impl DragData {
+    pub fn new(format: &str, payload: Box<dyn Any>) -> Self {
+        DragData {
+            format: format.to_string(),
+            payload: Rc::from(payload),
+        }
+    }
+
+    pub fn get_format(&self) -> &str {
+        &self.format
+    }
+
+    pub fn get_payload<T: 'static>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragData")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl PartialEq for DragData {
+    fn eq(&self, other: &Self) -> bool {
+        self.format == other.format
+            && std::ptr::eq(
+                Rc::as_ptr(&self.payload) as *const u8,
+                Rc::as_ptr(&other.payload) as *const u8,
+            )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DropEffect {
+    None,
+    Copy,
+    Move,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropEventArgs {
+    pub data: DragData,
+    pub position: Point,
+    pub effect: DropEffect,
+}
+
+//
+// Attached values.
+//
+
+///
+/// Marks a control as a drag source.
+///
+pub struct DragSource;
+impl typemap::Key for DragSource {
+    type Value = DragSourceHandler;
+}
+
+///
+/// Marks a control as a drop target.
+///
+/// DragEnter / DragOver / DragLeave / Drop events are routed
+/// to the nearest control (starting from the one under the pointer)
+/// with this value attached.
+///
+pub struct DropTarget;
+impl typemap::Key for DropTarget {
+    type Value = DropTargetHandler;
+}
+
+//
+// DragSourceHandler.
+//
+
+type VisualFactory = Box<dyn Fn() -> Rc<RefCell<dyn ControlObject>>>;
+
+pub struct DragSourceHandler {
+    data: Box<dyn Fn() -> Option<DragData>>,
+    visual: Option<VisualFactory>,
+    completed: Callback<DropEffect>,
+}
+
+impl DragSourceHandler {
+    ///
+    /// The data function is called when dragging starts.
+    /// Returning None cancels the drag and drop operation.
+    ///
+    pub fn new<F: 'static + Fn() -> Option<DragData>>(data: F) -> Self {
+        DragSourceHandler {
+            data: Box::new(data),
+            visual: None,
+            completed: Callback::empty(),
+        }
+    }
+
+    ///
+    /// The visual is shown under the pointer (on a separate layer)
+    /// during the drag and drop operation.
+    ///
+    pub fn with_visual<F: 'static + Fn() -> Rc<RefCell<dyn ControlObject>>>(
+        mut self,
+        visual: F,
+    ) -> Self {
+        self.visual = Some(Box::new(visual));
+        self
+    }
+
+    ///
+    /// The completed callback is called with the effect accepted by the drop target
+    /// (DropEffect::None when the data was not dropped).
+    ///
+    pub fn with_completed(mut self, completed: Callback<DropEffect>) -> Self {
+        self.completed = completed;
+        self
+    }
+
+    pub fn get_data(&self) -> Option<DragData> {
+        (self.data)()
+    }
+
+    pub fn create_visual(&self) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        self.visual.as_ref().map(|visual| visual())
+    }
+
+    pub fn get_completed(&self) -> &Callback<DropEffect> {
+        &self.completed
+    }
+}
+
+//
+// DropTargetHandler.
+//
+
+pub struct DropTargetHandler {
+    formats: Vec<String>,
+    effect: DropEffect,
+    drop: Callback<DropEventArgs>,
+}
+
+impl DropTargetHandler {
+    ///
+    /// Accepts data in any of the given formats with DropEffect::Move.
+    ///
+    pub fn new(formats: &[&str]) -> Self {
+        DropTargetHandler {
+            formats: formats.iter().map(|f| f.to_string()).collect(),
+            effect: DropEffect::Move,
+            drop: Callback::empty(),
+        }
+    }
+
+    pub fn with_effect(mut self, effect: DropEffect) -> Self {
+        self.effect = effect;
+        self
+    }
+
+    pub fn with_drop(mut self, drop: Callback<DropEventArgs>) -> Self {
+        self.drop = drop;
+        self
+    }
+
+    ///
+    /// Returns the effect for the data entering the target.
+    /// The control can still change it when handling DragEnter / DragOver events
+    /// with EventContext::set_drop_effect(), the effect is kept until the pointer
+    /// leaves the target.
+    ///
+    pub fn get_effect(&self, data: &DragData) -> DropEffect {
+        if self.formats.iter().any(|f| f == data.get_format()) {
+            self.effect
+        } else {
+            DropEffect::None
+        }
+    }
+
+    pub fn get_drop(&self) -> &Callback<DropEventArgs> {
+        &self.drop
+    }
+}
@@ -0,0 +1,118 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use typemap::TypeMap;
+
+use crate::common::*;
+use crate::control::*;
+use crate::events::*;
+use crate::style::*;
+use crate::view::ViewContext;
+use crate::DrawingContext;
+
+///
+/// Window layer showing the drag visual under the pointer
+/// during drag and drop operation.
+///
+/// It is transparent for hit testing.
+///
+pub struct DragLayer {
+    pub position: Point,
+}
+
+impl DragLayer {
+    pub fn create(
+        visual: Rc<RefCell<dyn ControlObject>>,
+        position: Point,
+    ) -> Rc<RefCell<StyledControl<DragLayer>>> {
+        StyledControl::new(
+            DragLayer { position },
+            Box::new(DragLayerStyle {
+                rect: Rect::new(0f32, 0f32, 0f32, 0f32),
+            }),
+            ViewContext {
+                attached_values: TypeMap::new(),
+                children: Box::new(vec![visual]),
+            },
+        )
+    }
+}
+
+struct DragLayerStyle {
+    rect: Rect,
+}
+
+impl Style<DragLayer> for DragLayerStyle {
+    fn setup(&mut self, _data: &mut DragLayer, _control_context: &mut ControlContext) {}
+
+    fn handle_event(
+        &mut self,
+        _data: &mut DragLayer,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        _event: ControlEvent,
+    ) {
+    }
+
+    fn measure(
+        &mut self,
+        _data: &mut DragLayer,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().measure(
+                drawing_context,
+                Size::new(f32::INFINITY, f32::INFINITY),
+            );
+        }
+
+        self.rect = Rect::new(0f32, 0f32, size.width, size.height);
+    }
+
+    fn set_rect(&mut self, data: &mut DragLayer, control_context: &mut ControlContext, rect: Rect) {
+        self.rect = rect;
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            let content_rect = content.borrow().get_rect();
+            content.borrow_mut().set_rect(Rect::new(
+                data.position.x,
+                data.position.y,
+                content_rect.width,
+                content_rect.height,
+            ));
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &DragLayer,
+        _control_context: &ControlContext,
+        _point: Point,
+    ) -> HitTestResult {
+        HitTestResult::Nothing
+    }
+
+    fn to_primitives(
+        &self,
+        _data: &DragLayer,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let children = control_context.get_children();
+        if let Some(child) = children.into_iter().next() {
+            child.borrow().to_primitives(drawing_context)
+        } else {
+            (Vec::new(), Vec::new())
+        }
+    }
+}
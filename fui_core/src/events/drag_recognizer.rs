@@ -0,0 +1,188 @@
+use std::time::Instant;
+
+use crate::common::Point;
+use crate::events::gesture_recognizer::distance;
+use crate::events::*;
+
+const DEFAULT_DRAG_THRESHOLD: f32 = 4.0f32;
+
+///
+/// Recognizes dragging with the left mouse button pressed.
+///
+/// DragStart is raised when the pointer moves more than the threshold
+/// from the press position. The first DragMove's delta is relative
+/// to the press position, so deltas always sum up to the total move.
+///
+pub struct DragRecognizer {
+    threshold: f32,
+    position: Point,
+    press_position: Option<Point>,
+    last_position: Point,
+    is_dragging: bool,
+}
+
+impl DragRecognizer {
+    pub fn new() -> Self {
+        DragRecognizer::with_threshold(DEFAULT_DRAG_THRESHOLD)
+    }
+
+    pub fn with_threshold(threshold: f32) -> Self {
+        DragRecognizer {
+            threshold,
+            position: Point::new(0f32, 0f32),
+            press_position: None,
+            last_position: Point::new(0f32, 0f32),
+            is_dragging: false,
+        }
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.is_dragging
+    }
+}
+
+impl Default for DragRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for DragRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, _time: Instant) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match event {
+            InputEvent::CursorMoved { position } => {
+                self.position = *position;
+
+                if let Some(press_position) = self.press_position {
+                    if !self.is_dragging
+                        && distance(&press_position, &self.position) > self.threshold
+                    {
+                        self.is_dragging = true;
+                        self.last_position = press_position;
+                        gestures.push(Gesture::DragStart {
+                            position: press_position,
+                        });
+                    }
+
+                    if self.is_dragging {
+                        gestures.push(Gesture::DragMove {
+                            position: self.position,
+                            delta: Point::new(
+                                self.position.x - self.last_position.x,
+                                self.position.y - self.last_position.y,
+                            ),
+                        });
+                        self.last_position = self.position;
+                    }
+                }
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+            } => {
+                self.press_position = Some(self.position);
+                self.is_dragging = false;
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+            } => {
+                if self.is_dragging {
+                    gestures.push(Gesture::DragEnd {
+                        position: self.position,
+                    });
+                }
+                self.press_position = None;
+                self.is_dragging = false;
+            }
+
+            _ => (),
+        }
+
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Point;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_move_below_threshold_is_not_drag() {
+        let mut recognizer = DragRecognizer::with_threshold(5.0f32);
+        let time = Instant::now();
+
+        assert!(recognizer
+            .handle_event(&cursor_moved(10.0, 10.0), time)
+            .is_empty());
+        assert!(recognizer
+            .handle_event(&left_button(ElementState::Pressed), time)
+            .is_empty());
+        assert!(recognizer
+            .handle_event(&cursor_moved(13.0, 13.0), time)
+            .is_empty());
+        assert!(recognizer
+            .handle_event(&left_button(ElementState::Released), time)
+            .is_empty());
+        assert!(!recognizer.is_dragging());
+    }
+
+    #[test]
+    fn test_drag_start_move_end() {
+        let mut recognizer = DragRecognizer::with_threshold(5.0f32);
+        let time = Instant::now();
+
+        recognizer.handle_event(&cursor_moved(10.0, 10.0), time);
+        recognizer.handle_event(&left_button(ElementState::Pressed), time);
+
+        assert_eq!(
+            recognizer.handle_event(&cursor_moved(20.0, 10.0), time),
+            vec![
+                Gesture::DragStart {
+                    position: Point::new(10.0, 10.0)
+                },
+                Gesture::DragMove {
+                    position: Point::new(20.0, 10.0),
+                    delta: Point::new(10.0, 0.0),
+                },
+            ]
+        );
+        assert!(recognizer.is_dragging());
+
+        assert_eq!(
+            recognizer.handle_event(&cursor_moved(22.0, 7.0), time),
+            vec![Gesture::DragMove {
+                position: Point::new(22.0, 7.0),
+                delta: Point::new(2.0, -3.0),
+            }]
+        );
+
+        assert_eq!(
+            recognizer.handle_event(&left_button(ElementState::Released), time),
+            vec![Gesture::DragEnd {
+                position: Point::new(22.0, 7.0)
+            }]
+        );
+        assert!(!recognizer.is_dragging());
+    }
+
+    #[test]
+    fn test_move_without_press_is_not_drag() {
+        let mut recognizer = DragRecognizer::new();
+        let time = Instant::now();
+
+        assert!(recognizer
+            .handle_event(&cursor_moved(0.0, 0.0), time)
+            .is_empty());
+        assert!(recognizer
+            .handle_event(&cursor_moved(100.0, 100.0), time)
+            .is_empty());
+    }
+}
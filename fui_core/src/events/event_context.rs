@@ -1,5 +1,5 @@
-use std::{cell::RefCell, rc::Rc};
-use crate::{ControlEvent, ControlObject};
+use std::{cell::RefCell, rc::Rc, time::Instant};
+use crate::{ControlEvent, ControlObject, DropEffect, KeyModifiers};
 
 pub trait EventContext {
     fn get_hovered_control(&self) -> Option<Rc<RefCell<dyn ControlObject>>>;
@@ -11,5 +11,18 @@ pub trait EventContext {
     fn get_focused_control(&self) -> Option<Rc<RefCell<dyn ControlObject>>>;
     fn set_focused_control(&mut self, control: Option<Rc<RefCell<dyn ControlObject>>>);
 
+    /// Sets the effect of the drag and drop operation.
+    /// Can be used by drop targets when handling DragEnter / DragOver events.
+    fn set_drop_effect(&mut self, effect: DropEffect);
+
+    /// Returns the modifier keys pressed now.
+    /// Can be used by pointer events (like Ctrl+click).
+    fn get_key_modifiers(&self) -> KeyModifiers;
+
     fn queue_event(&mut self, control: Option<Rc<RefCell<dyn ControlObject>>>, event: ControlEvent);
+
+    /// Sends the Timer event to the control at the given time.
+    /// A control has at most one timer, setting it again replaces the previous one.
+    fn set_timer(&mut self, control: Rc<RefCell<dyn ControlObject>>, time: Instant);
+    fn clear_timer(&mut self, control: &Rc<RefCell<dyn ControlObject>>);
 }
@@ -0,0 +1,33 @@
+use std::rc::Rc;
+
+use crate::events::ControlEvent;
+
+///
+/// Attached value with the filter of the events sent to the control and its children.
+///
+/// Filters are called before the event is handled, starting from the one
+/// closest to the target control. When the filter returns true, the event is handled
+/// and it is not sent any further. Composed controls can use it to handle keys,
+/// mouse wheel or gestures of their parts.
+///
+pub struct EventFilter;
+impl typemap::Key for EventFilter {
+    type Value = EventFilterHandler;
+}
+
+#[derive(Clone)]
+pub struct EventFilterHandler {
+    filter: Rc<dyn Fn(&ControlEvent) -> bool>,
+}
+
+impl EventFilterHandler {
+    pub fn new<F: 'static + Fn(&ControlEvent) -> bool>(filter: F) -> Self {
+        EventFilterHandler {
+            filter: Rc::new(filter),
+        }
+    }
+
+    pub fn filter(&self, event: &ControlEvent) -> bool {
+        (self.filter)(event)
+    }
+}
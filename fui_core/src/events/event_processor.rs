@@ -1,24 +1,68 @@
 use std::cell::RefCell;
+use std::time::{Duration, Instant};
 use std::{collections::VecDeque, rc::{Rc, Weak}};
 
+use crate::common::Point;
 use crate::control::*;
+use crate::observable::Callback;
+use crate::services::{Cursor, CursorIcon, LayerOptions};
 use crate::{DrawingContext, events::*};
 
+const TOOL_TIP_DELAY: Duration = Duration::from_millis(500);
+
 struct QueuedEvent {
     pub control: Rc<RefCell<dyn ControlObject>>,
     pub event: ControlEvent,
 }
 
+struct ControlTimer {
+    control: Weak<RefCell<dyn ControlObject>>,
+    time: Instant,
+}
+
+struct DragOperation {
+    source: Weak<RefCell<dyn ControlObject>>,
+    // drop targets are searched on the layer where the operation started
+    root_view: Weak<RefCell<dyn ControlObject>>,
+    data: DragData,
+    target: Option<Weak<RefCell<dyn ControlObject>>>,
+    effect: DropEffect,
+    layer: Option<Rc<RefCell<StyledControl<DragLayer>>>>,
+}
+
 pub struct EventProcessor {
     hovered_control: Option<Weak<RefCell<dyn ControlObject>>>,
     captured_control: Option<Weak<RefCell<dyn ControlObject>>>,
     focused_control: Option<Weak<RefCell<dyn ControlObject>>>,
+    gesture_control: Option<Weak<RefCell<dyn ControlObject>>>,
 
     is_hover_enabled: bool,
 
     gesture_detector: GestureDetector,
+    gesture_recognizers: Vec<Box<dyn GestureRecognizer>>,
+    touch_emulator: TouchEmulator,
+
+    drag_operation: Option<DragOperation>,
+
+    tool_tip_control: Option<Weak<RefCell<dyn ControlObject>>>,
+    tool_tip_time: Option<Instant>,
+    tool_tip_position: Point,
+    tool_tip_layer: Option<Rc<RefCell<dyn ControlObject>>>,
+
+    // layers cannot be added or removed while handling window events
+    // (the window is borrowed), so it is done with a queued callback
+    layer_visibility: Option<Callback<(Rc<RefCell<dyn ControlObject>>, bool)>>,
+
+    // the last pointer position used to route events between layers
+    pointer_position: Point,
+
+    cursor_icon: CursorIcon,
+
+    key_modifiers: KeyModifiers,
 
     event_queue: VecDeque<QueuedEvent>,
+
+    control_timers: Vec<ControlTimer>,
 }
 
 impl EventProcessor {
@@ -27,41 +71,425 @@ impl EventProcessor {
             hovered_control: None,
             captured_control: None,
             focused_control: None,
+            gesture_control: None,
 
             is_hover_enabled: true,
 
             gesture_detector: GestureDetector::new(),
+            gesture_recognizers: vec![
+                Box::new(DragRecognizer::new()),
+                Box::new(LongPressRecognizer::new()),
+                Box::new(DoubleTapRecognizer::new()),
+                Box::new(FlingRecognizer::new()),
+                Box::new(PinchRotateRecognizer::new()),
+            ],
+            touch_emulator: TouchEmulator::new(),
+
+            drag_operation: None,
+
+            tool_tip_control: None,
+            tool_tip_time: None,
+            tool_tip_position: Point::new(0f32, 0f32),
+            tool_tip_layer: None,
+
+            layer_visibility: None,
+
+            pointer_position: Point::new(0f32, 0f32),
+
+            cursor_icon: CursorIcon::Default,
+
+            key_modifiers: KeyModifiers::default(),
 
             event_queue: VecDeque::new(),
+
+            control_timers: Vec::new(),
         }
     }
   
+    ///
+    /// Handles the input event.
+    ///
+    /// The input layers are the layers which can get the input,
+    /// from the top one down to the first modal layer.
+    /// Pointer events go to the top layer hit by the pointer
+    /// and keyboard events go to the layer with the focused control
+    /// (or the bottom one).
+    ///
+    /// Keyboard events are sent to the focused control and then to the root view
+    /// of the layer, so the layer can handle keys like Esc / Enter for all its controls
+    /// (Enter is not passed to the layer when the focused control has AcceptsReturn set).
+    /// Focused controls on the other layers don't get keyboard events.
+    /// There is no Tab traversal, the focus is moved only by tapping the controls.
+    ///
     pub fn handle_event(
         &mut self,
-        root_view: &Rc<RefCell<dyn ControlObject>>,
+        input_layers: &[Rc<RefCell<dyn ControlObject>>],
         drawing_context: &mut dyn DrawingContext,
         event: &InputEvent,
     ) {
-        self.handle_keyboard_event(root_view, event);
-        self.handle_gesture_event(root_view, event);
-        self.handle_hover_event(root_view, event);
+        match event {
+            InputEvent::CursorMoved { position } | InputEvent::Touch { position, .. } => {
+                self.pointer_position = *position;
+            }
+            InputEvent::ModifiersChanged(modifiers) => {
+                self.key_modifiers = modifiers.clone();
+            }
+            // typed characters don't carry the modifiers
+            InputEvent::KeyboardInput(key_event) if key_event.keycode.is_some() => {
+                self.key_modifiers = key_event.modifiers.clone();
+            }
+            _ => (),
+        }
+
+        let root_view = match event {
+            InputEvent::KeyboardInput(..) => self.get_keyboard_root_view(input_layers),
+            _ => self.get_pointer_root_view(input_layers),
+        };
+
+        if let Some(ref root_view) = root_view {
+            self.handle_keyboard_event(root_view, event);
+            self.handle_gesture_event(root_view, event);
+            self.handle_hover_event(root_view, event);
+        }
+        self.handle_mouse_wheel_event(event);
+        self.handle_tool_tip_event(event);
+
+        self.send_queued_events(drawing_context);
+
+        self.update_cursor_icon();
+
+        // touch points of the primary finger are also handled as pointer events
+        for pointer_event in self.touch_emulator.handle_event(event) {
+            self.handle_event(input_layers, drawing_context, &pointer_event);
+        }
+    }
+
+    ///
+    /// Lets the gesture recognizers fire time based gestures (like long press)
+    /// and sends the Timer events to the controls.
+    /// Should be called not later than at the time returned by get_next_timer().
+    ///
+    pub fn handle_timers(
+        &mut self,
+        input_layers: &[Rc<RefCell<dyn ControlObject>>],
+        drawing_context: &mut dyn DrawingContext,
+        time: Instant,
+    ) {
+        if let Some(tool_tip_time) = self.tool_tip_time {
+            if time >= tool_tip_time {
+                self.tool_tip_time = None;
+                self.show_tool_tip();
+            }
+        }
+
+        let mut gestures = Vec::new();
+        for gesture_recognizer in self.gesture_recognizers.iter_mut() {
+            gestures.append(&mut gesture_recognizer.handle_timers(time));
+        }
+        if let Some(ref root_view) = self.get_pointer_root_view(input_layers) {
+            for gesture in gestures {
+                self.handle_gesture(root_view, gesture);
+            }
+        }
+
+        // removed controls lose their timers
+        self.control_timers
+            .retain(|timer| timer.control.upgrade().is_some());
+        let (due_timers, control_timers) = self
+            .control_timers
+            .drain(..)
+            .partition(|timer| time >= timer.time);
+        self.control_timers = control_timers;
+        for timer in due_timers {
+            self.queue_event(timer.control.upgrade(), ControlEvent::Timer);
+        }
+
+        self.send_queued_events(drawing_context);
+    }
 
+    pub fn get_next_timer(&self) -> Option<Instant> {
+        self.gesture_recognizers
+            .iter()
+            .filter_map(|gesture_recognizer| gesture_recognizer.get_next_timer())
+            .chain(self.tool_tip_time)
+            .chain(self.control_timers.iter().map(|timer| timer.time))
+            .min()
+    }
+
+    ///
+    /// Returns the cursor resolved from the captured or hovered control.
+    ///
+    pub fn get_cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    pub fn add_gesture_recognizer(&mut self, gesture_recognizer: Box<dyn GestureRecognizer>) {
+        self.gesture_recognizers.push(gesture_recognizer);
+    }
+
+    fn send_queued_events(&mut self, drawing_context: &mut dyn DrawingContext) {
         while let Some(queue_event) = self.event_queue.pop_front() {
             self.send_event_to_control(Some(queue_event.control), drawing_context, queue_event.event);
         }
     }
 
+    ///
+    /// Returns the layer with the captured control
+    /// or the top layer hit by the pointer.
+    ///
+    fn get_pointer_root_view(
+        &self,
+        input_layers: &[Rc<RefCell<dyn ControlObject>>],
+    ) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        if let Some(captured_root) = self
+            .get_captured_control()
+            .map(find_root)
+            .filter(|root| input_layers.iter().any(|layer| Rc::ptr_eq(layer, root)))
+        {
+            return Some(captured_root);
+        }
+
+        // hits not handled by non-modal layers go through to the layers below
+        input_layers
+            .iter()
+            .find(|layer| match layer.borrow().hit_test(self.pointer_position) {
+                HitTestResult::Nothing => false,
+                _ => true,
+            })
+            .or_else(|| input_layers.last())
+            .cloned()
+    }
+
+    ///
+    /// Returns the layer with the focused control or the bottom input layer.
+    ///
+    fn get_keyboard_root_view(
+        &self,
+        input_layers: &[Rc<RefCell<dyn ControlObject>>],
+    ) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        self.get_focused_control()
+            .map(find_root)
+            .filter(|root| input_layers.iter().any(|layer| Rc::ptr_eq(layer, root)))
+            .or_else(|| input_layers.last().cloned())
+    }
+
+    fn init_layer_visibility(&mut self, control: &Rc<RefCell<dyn ControlObject>>) -> bool {
+        if self.layer_visibility.is_none() {
+            let window_service = control
+                .borrow()
+                .get_context()
+                .get_services()
+                .and_then(|services| services.upgrade())
+                .and_then(|services| services.borrow().get_window_service());
+
+            if let Some(window_service) = window_service {
+                let window_service = Rc::downgrade(&window_service);
+                let mut layer_visibility = Callback::empty();
+                layer_visibility.set(
+                    move |(layer, is_visible): (Rc<RefCell<dyn ControlObject>>, bool)| {
+                        if let Some(window_service) = window_service.upgrade() {
+                            if is_visible {
+                                window_service
+                                    .borrow_mut()
+                                    .add_layer_with_options(layer, LayerOptions::overlay());
+                            } else {
+                                window_service.borrow_mut().remove_layer(&layer);
+                            }
+                        }
+                    },
+                );
+                self.layer_visibility = Some(layer_visibility);
+            }
+        }
+        self.layer_visibility.is_some()
+    }
+
+    ///
+    /// Overlay layers are transparent for hit testing,
+    /// so the input goes through them to the layers below.
+    ///
+    fn show_overlay_layer(&mut self, layer: Rc<RefCell<dyn ControlObject>>) {
+        if let Some(ref layer_visibility) = self.layer_visibility {
+            layer_visibility.emit((layer, true));
+        }
+    }
+
+    fn hide_overlay_layer(&mut self, layer: Rc<RefCell<dyn ControlObject>>) {
+        if let Some(ref layer_visibility) = self.layer_visibility {
+            layer_visibility.emit((layer, false));
+        }
+    }
+
+    fn handle_tool_tip_event(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::CursorMoved { position } => {
+                self.tool_tip_position = *position;
+
+                let control = self.get_hovered_control().and_then(|control| {
+                    find_control(control, |c| {
+                        c.get_context().get_attached_values().contains::<ToolTip>()
+                    })
+                });
+                let old_control = self
+                    .tool_tip_control
+                    .as_ref()
+                    .and_then(|control| control.upgrade());
+                let is_control_changed = match (&old_control, &control) {
+                    (Some(old_control), Some(control)) => !Rc::ptr_eq(old_control, control),
+                    (None, None) => false,
+                    _ => true,
+                };
+
+                if is_control_changed {
+                    self.hide_tool_tip();
+                    self.tool_tip_time = control
+                        .as_ref()
+                        .map(|_| Instant::now() + TOOL_TIP_DELAY);
+                    self.tool_tip_control = control.as_ref().map(|c| Rc::downgrade(c));
+                } else if self.tool_tip_layer.is_none() && self.tool_tip_time.is_some() {
+                    // the pointer has to rest for a while
+                    self.tool_tip_time = Some(Instant::now() + TOOL_TIP_DELAY);
+                }
+            }
+
+            InputEvent::CursorLeft { .. } => {
+                self.hide_tool_tip();
+                self.tool_tip_time = None;
+                self.tool_tip_control = None;
+            }
+
+            // the tool tip is not shown again
+            // until the pointer leaves the control
+            InputEvent::MouseInput { .. }
+            | InputEvent::KeyboardInput(..)
+            | InputEvent::Touch { .. } => {
+                self.hide_tool_tip();
+                self.tool_tip_time = None;
+            }
+
+            _ => (),
+        }
+    }
+
+    fn show_tool_tip(&mut self) {
+        let control = match self
+            .tool_tip_control
+            .as_ref()
+            .and_then(|control| control.upgrade())
+        {
+            Some(control) => control,
+            None => return,
+        };
+
+        if self.tool_tip_layer.is_some() || !self.init_layer_visibility(&control) {
+            return;
+        }
+
+        // the tool tip could have been removed during the delay
+        let layer = {
+            let control = control.borrow();
+            match control.get_context().get_attached_values().get::<ToolTip>() {
+                Some(content) => ToolTipLayer::create(content, self.tool_tip_position),
+                None => return,
+            }
+        };
+        let services = control.borrow().get_context().get_services();
+        layer.borrow_mut().get_context_mut().set_services(services);
+
+        let layer: Rc<RefCell<dyn ControlObject>> = layer;
+        self.show_overlay_layer(layer.clone());
+        self.tool_tip_layer = Some(layer);
+    }
+
+    fn hide_tool_tip(&mut self) {
+        if let Some(layer) = self.tool_tip_layer.take() {
+            self.hide_overlay_layer(layer);
+        }
+    }
+
+    fn update_cursor_icon(&mut self) {
+        if let Some(ref drag_operation) = self.drag_operation {
+            self.cursor_icon = match drag_operation.effect {
+                DropEffect::None => CursorIcon::NotAllowed,
+                _ => CursorIcon::Default,
+            };
+            return;
+        }
+
+        // the cursor of the control at the pointer position wins
+        // over the Cursor attached values
+        let mut control = self
+            .get_captured_control()
+            .or_else(|| self.get_hovered_control());
+        let mut cursor_icon = None;
+        while let Some(current) = control {
+            let current = current.borrow();
+            cursor_icon = current.get_cursor_icon(self.pointer_position).or_else(|| {
+                current
+                    .get_context()
+                    .get_attached_values()
+                    .get::<Cursor>()
+                    .cloned()
+            });
+            if cursor_icon.is_some() {
+                break;
+            }
+            control = current.get_context().get_parent();
+        }
+        self.cursor_icon = cursor_icon.unwrap_or(CursorIcon::Default);
+    }
+
     fn handle_keyboard_event(
         &mut self,
-        _root_view: &Rc<RefCell<dyn ControlObject>>,
+        root_view: &Rc<RefCell<dyn ControlObject>>,
         event: &InputEvent,
     ) {
         match event {
             InputEvent::KeyboardInput(key_event) => {
-                self.queue_event(
-                    self.get_focused_control(),
-                    ControlEvent::KeyboardInput(key_event.clone()),
-                );
+                if self.drag_operation.is_some()
+                    && key_event.state == KeyState::Pressed
+                    && key_event.keycode == Some(Keycode::Esc)
+                {
+                    self.finish_drag_operation(None);
+                    return;
+                }
+
+                // keyboard focus is trapped in the layer receiving input
+                // (controls on lower layers, like the ones below a modal dialog,
+                // don't get keyboard events)
+                let focused_control = self
+                    .get_focused_control()
+                    .filter(|control| Rc::ptr_eq(&find_root(control.clone()), root_view));
+
+                let is_return_accepted = (key_event.keycode == Some(Keycode::Enter)
+                    || key_event.keycode == Some(Keycode::NumpadEnter))
+                    && focused_control.as_ref().map_or(false, |control| {
+                        control
+                            .borrow()
+                            .get_context()
+                            .get_attached_values()
+                            .get::<AcceptsReturn>()
+                            .cloned()
+                            .unwrap_or(false)
+                    });
+
+                // the layer itself gets all the keyboard events too,
+                // so modal layers can handle Esc / Enter keys
+                if !focused_control
+                    .as_ref()
+                    .map_or(false, |control| Rc::ptr_eq(control, root_view))
+                {
+                    self.queue_event(
+                        focused_control,
+                        ControlEvent::KeyboardInput(key_event.clone()),
+                    );
+                }
+                if !is_return_accepted {
+                    self.queue_event(
+                        Some(root_view.clone()),
+                        ControlEvent::KeyboardInput(key_event.clone()),
+                    );
+                }
             }
 
             _ => (),
@@ -73,53 +501,331 @@ impl EventProcessor {
         root_view: &Rc<RefCell<dyn ControlObject>>,
         event: &InputEvent,
     ) {
-        self.gesture_detector
-            .handle_event(root_view, event)
-            .map(|ev| match ev {
-                Gesture::TapDown { position } => {
-                    let captured_control = self.get_captured_control();
-                    if let Some(captured_control) = captured_control {
+        if let Some(gesture) = self.gesture_detector.handle_event(root_view, event) {
+            self.handle_gesture(root_view, gesture);
+        }
+
+        let time = Instant::now();
+        let mut gestures = Vec::new();
+        for gesture_recognizer in self.gesture_recognizers.iter_mut() {
+            gestures.append(&mut gesture_recognizer.handle_event(event, time));
+        }
+        for gesture in gestures {
+            self.handle_gesture(root_view, gesture);
+        }
+    }
+
+    fn handle_gesture(&mut self, root_view: &Rc<RefCell<dyn ControlObject>>, gesture: Gesture) {
+        match gesture {
+            Gesture::TapDown { position } => {
+                let captured_control = self.get_captured_control();
+                if let Some(captured_control) = captured_control {
+                    self.gesture_control = Some(Rc::downgrade(&captured_control));
+                    self.queue_event(
+                        Some(captured_control),
+                        ControlEvent::TapDown { position: position },
+                    );
+                } else {
+                    let hit_test_result = root_view.borrow().hit_test(position);
+                    let hit_control = match hit_test_result {
+                        HitTestResult::Current => Some(root_view.clone()),
+                        HitTestResult::Child(control) => Some(control),
+                        HitTestResult::Nothing => None,
+                    };
+
+                    self.gesture_control = hit_control.as_ref().map(|c| Rc::downgrade(c));
+
+                    if let Some(ref hit_control) = hit_control {
+                        self.set_focused_control(Some(hit_control.clone()));
+
+                        self.set_captured_control(Some(hit_control.clone()));
+
                         self.queue_event(
-                            Some(captured_control),
+                            self.get_captured_control(),
                             ControlEvent::TapDown { position: position },
                         );
-                    } else {
-                        let hit_test_result = root_view.borrow().hit_test(position);
-                        let hit_control = match hit_test_result {
-                            HitTestResult::Current => Some(root_view.clone()),
-                            HitTestResult::Child(control) => Some(control),
-                            HitTestResult::Nothing => None,
-                        };
-
-                        if let Some(ref hit_control) = hit_control {
-                            self.set_focused_control(Some(hit_control.clone()));
-
-                            self.set_captured_control(Some(hit_control.clone()));
-
-                            self.queue_event(
-                                self.get_captured_control(),
-                                ControlEvent::TapDown { position: position },
-                            );
-                        }
                     }
                 }
+            }
 
-                Gesture::TapUp { position } => {
-                    let captured_control = self.get_captured_control();
-                    self.set_captured_control(None);
-                    self.queue_event(
-                        captured_control,
-                        ControlEvent::TapUp { position: position },
-                    );
-                }
+            Gesture::TapUp { position } => {
+                let captured_control = self.get_captured_control();
+                self.set_captured_control(None);
+                self.queue_event(
+                    captured_control,
+                    ControlEvent::TapUp { position: position },
+                );
+            }
 
-                Gesture::TapMove { position } => {
-                    self.queue_event(
-                        self.get_captured_control(),
-                        ControlEvent::TapMove { position: position },
-                    );
+            Gesture::TapMove { position } => {
+                self.queue_event(
+                    self.get_captured_control(),
+                    ControlEvent::TapMove { position: position },
+                );
+            }
+
+            // gestures recognized by gesture recognizers
+            // go to the control that received the last TapDown
+            Gesture::DragStart { position } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::DragStart { position },
+                );
+                self.start_drag_operation(position);
+            }
+
+            Gesture::DragMove { position, delta } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::DragMove { position, delta },
+                );
+                self.update_drag_operation(position);
+            }
+
+            Gesture::DragEnd { position } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::DragEnd { position },
+                );
+                self.finish_drag_operation(Some(position));
+            }
+
+            Gesture::LongPress { position } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::LongPress { position },
+                );
+            }
+
+            Gesture::DoubleTap { position } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::DoubleTap { position },
+                );
+            }
+
+            Gesture::Fling { position, velocity } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::Fling { position, velocity },
+                );
+            }
+
+            Gesture::Pinch { center, scale } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::Pinch { center, scale },
+                );
+            }
+
+            Gesture::Rotate { center, angle } => {
+                self.queue_event(
+                    self.get_gesture_control(),
+                    ControlEvent::Rotate { center, angle },
+                );
+            }
+        }
+    }
+
+    fn get_gesture_control(&self) -> Option<Rc<RefCell<dyn ControlObject>>> {
+        if let Some(ref control) = self.gesture_control {
+            control.upgrade()
+        } else {
+            None
+        }
+    }
+
+    fn start_drag_operation(&mut self, position: Point) {
+        if self.drag_operation.is_some() {
+            return;
+        }
+
+        let source = match self.get_gesture_control().and_then(|control| {
+            find_control(control, |c| {
+                c.get_context().get_attached_values().contains::<DragSource>()
+            })
+        }) {
+            Some(source) => source,
+            None => return,
+        };
+
+        let (data, visual) = {
+            let source = source.borrow();
+            match source.get_context().get_attached_values().get::<DragSource>() {
+                Some(handler) => (handler.get_data(), handler.create_visual()),
+                None => return,
+            }
+        };
+
+        let data = match data {
+            Some(data) => data,
+            None => return,
+        };
+
+        let root_view = find_root(source.clone());
+
+        let layer = match visual {
+            Some(visual) if self.init_layer_visibility(&source) => {
+                let layer = DragLayer::create(visual, position);
+                let services = source.borrow().get_context().get_services();
+                layer.borrow_mut().get_context_mut().set_services(services);
+
+                self.show_overlay_layer(layer.clone());
+                Some(layer)
+            }
+            _ => None,
+        };
+
+        self.drag_operation = Some(DragOperation {
+            source: Rc::downgrade(&source),
+            root_view: Rc::downgrade(&root_view),
+            data,
+            target: None,
+            effect: DropEffect::None,
+            layer,
+        });
+
+        self.update_drag_operation(position);
+    }
+
+    fn update_drag_operation(&mut self, position: Point) {
+        let mut drag_operation = match self.drag_operation.take() {
+            Some(drag_operation) => drag_operation,
+            None => return,
+        };
+
+        let target = drag_operation
+            .root_view
+            .upgrade()
+            .and_then(|root_view| {
+                let hit_test_result = root_view.borrow().hit_test(position);
+                match hit_test_result {
+                    HitTestResult::Current => Some(root_view.clone()),
+                    HitTestResult::Child(control) => Some(control),
+                    HitTestResult::Nothing => None,
                 }
+            })
+            .and_then(|control| {
+                find_control(control, |c| {
+                    c.get_context().get_attached_values().contains::<DropTarget>()
+                })
             });
+
+        let old_target = drag_operation
+            .target
+            .as_ref()
+            .and_then(|target| target.upgrade());
+        let is_target_changed = match (&old_target, &target) {
+            (Some(old_target), Some(target)) => !Rc::ptr_eq(old_target, target),
+            (None, None) => false,
+            _ => true,
+        };
+
+        if is_target_changed {
+            self.queue_event(old_target, ControlEvent::DragLeave);
+            self.queue_event(
+                target.clone(),
+                ControlEvent::DragEnter {
+                    data: drag_operation.data.clone(),
+                    position,
+                },
+            );
+            drag_operation.target = target.as_ref().map(|target| Rc::downgrade(target));
+
+            // the effect is taken from the handler only when entering the target,
+            // later it can be changed by the target with set_drop_effect()
+            drag_operation.effect = target
+                .as_ref()
+                .and_then(|target| {
+                    target
+                        .borrow()
+                        .get_context()
+                        .get_attached_values()
+                        .get::<DropTarget>()
+                        .map(|handler| handler.get_effect(&drag_operation.data))
+                })
+                .unwrap_or(DropEffect::None);
+        }
+
+        self.queue_event(
+            target,
+            ControlEvent::DragOver {
+                data: drag_operation.data.clone(),
+                position,
+            },
+        );
+
+        if let Some(ref layer) = drag_operation.layer {
+            let mut layer = layer.borrow_mut();
+            layer.data.position = position;
+            layer.get_context_mut().set_is_dirty(true);
+        }
+
+        self.drag_operation = Some(drag_operation);
+    }
+
+    ///
+    /// Drops the data at the position or cancels
+    /// the drag and drop operation if position is None.
+    ///
+    fn finish_drag_operation(&mut self, position: Option<Point>) {
+        let drag_operation = match self.drag_operation.take() {
+            Some(drag_operation) => drag_operation,
+            None => return,
+        };
+
+        let target = drag_operation
+            .target
+            .as_ref()
+            .and_then(|target| target.upgrade());
+
+        // the target could have been removed from the drop targets in the meantime
+        let drop = target.as_ref().and_then(|target| {
+            let target = target.borrow();
+            let handler = target.get_context().get_attached_values().get::<DropTarget>();
+            handler.map(|handler| handler.get_drop().clone())
+        });
+
+        let effect = match (position, target, drop) {
+            (Some(position), Some(target), Some(drop))
+                if drag_operation.effect != DropEffect::None =>
+            {
+                drop.emit(DropEventArgs {
+                    data: drag_operation.data.clone(),
+                    position,
+                    effect: drag_operation.effect,
+                });
+                self.queue_event(
+                    Some(target),
+                    ControlEvent::Drop {
+                        data: drag_operation.data.clone(),
+                        position,
+                        effect: drag_operation.effect,
+                    },
+                );
+                drag_operation.effect
+            }
+
+            (_, target, _) => {
+                self.queue_event(target, ControlEvent::DragLeave);
+                DropEffect::None
+            }
+        };
+
+        if let Some(source) = drag_operation.source.upgrade() {
+            if let Some(handler) = source
+                .borrow()
+                .get_context()
+                .get_attached_values()
+                .get::<DragSource>()
+            {
+                handler.get_completed().emit(effect);
+            }
+        }
+
+        if let Some(layer) = drag_operation.layer {
+            self.hide_overlay_layer(layer);
+        }
     }
 
     fn disable_hover(&mut self) {
@@ -132,6 +838,24 @@ impl EventProcessor {
         self.queue_event(self.get_hovered_control(), ControlEvent::HoverEnter);
     }
 
+    ///
+    /// Sends the mouse wheel to the control under the pointer.
+    ///
+    fn handle_mouse_wheel_event(&mut self, event: &InputEvent) {
+        if let InputEvent::MouseWheel { delta } = event {
+            let control = self
+                .get_captured_control()
+                .or_else(|| self.get_hovered_control());
+            self.queue_event(
+                control,
+                ControlEvent::MouseWheel {
+                    position: self.pointer_position,
+                    delta: *delta,
+                },
+            );
+        }
+    }
+
     fn handle_hover_event(
         &mut self,
         root_view: &Rc<RefCell<dyn ControlObject>>,
@@ -170,6 +894,9 @@ impl EventProcessor {
         event: ControlEvent,
     ) {
         if let Some(ref control) = control {
+            if filter_event(control, &event) {
+                return;
+            }
             control.borrow_mut().handle_event(drawing_context, self, event);
         };
     }
@@ -239,6 +966,16 @@ impl EventContext for EventProcessor {
         self.queue_event(control, ControlEvent::FocusEnter);
     }
 
+    fn set_drop_effect(&mut self, effect: DropEffect) {
+        if let Some(ref mut drag_operation) = self.drag_operation {
+            drag_operation.effect = effect;
+        }
+    }
+
+    fn get_key_modifiers(&self) -> KeyModifiers {
+        self.key_modifiers.clone()
+    }
+
     fn queue_event(&mut self, control: Option<Rc<RefCell<dyn ControlObject>>>, event: ControlEvent) {
         if let Some(control) = control {
             self.event_queue.push_back(QueuedEvent {
@@ -247,4 +984,733 @@ impl EventContext for EventProcessor {
             })
         }
     }
+
+    fn set_timer(&mut self, control: Rc<RefCell<dyn ControlObject>>, time: Instant) {
+        self.clear_timer(&control);
+        self.control_timers.push(ControlTimer {
+            control: Rc::downgrade(&control),
+            time,
+        });
+    }
+
+    fn clear_timer(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        let control = Rc::downgrade(control);
+        self.control_timers
+            .retain(|timer| !timer.control.ptr_eq(&control));
+    }
+}
+
+///
+/// Returns the first control (starting from the given one and going up
+/// through its parents) matching the predicate.
+///
+fn find_control<F: Fn(&dyn ControlObject) -> bool>(
+    control: Rc<RefCell<dyn ControlObject>>,
+    predicate: F,
+) -> Option<Rc<RefCell<dyn ControlObject>>> {
+    let mut control = Some(control);
+    while let Some(current) = control {
+        if predicate(&*current.borrow()) {
+            return Some(current);
+        }
+        let parent = current.borrow().get_context().get_parent();
+        control = parent;
+    }
+    None
+}
+
+///
+/// Calls the event filters of the control and its parents.
+/// Returns true when the event has been handled by one of them.
+///
+fn filter_event(control: &Rc<RefCell<dyn ControlObject>>, event: &ControlEvent) -> bool {
+    let mut control = Some(control.clone());
+    while let Some(current) = control {
+        let event_filter = current
+            .borrow()
+            .get_context()
+            .get_attached_values()
+            .get::<EventFilter>()
+            .cloned();
+        if let Some(event_filter) = event_filter {
+            if event_filter.filter(event) {
+                return true;
+            }
+        }
+        let parent = current.borrow().get_context().get_parent();
+        control = parent;
+    }
+    false
+}
+
+fn find_root(control: Rc<RefCell<dyn ControlObject>>) -> Rc<RefCell<dyn ControlObject>> {
+    let mut control = control;
+    loop {
+        let parent = control.borrow().get_context().get_parent();
+        match parent {
+            Some(parent) => control = parent,
+            None => return control,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+
+    use typemap::TypeMap;
+
+    use super::TOOL_TIP_DELAY;
+
+    use crate::common::*;
+    use crate::control::*;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use crate::observable::{Callback, CallbackExecutor};
+    use crate::services::*;
+
+    fn handle(
+        processor: &mut EventProcessor,
+        layers: &[Rc<RefCell<dyn ControlObject>>],
+        event: InputEvent,
+    ) {
+        processor.handle_event(layers, &mut TestDrawingContext, &event);
+        CallbackExecutor::execute_all_in_queue();
+    }
+
+    //
+    // Drag and drop.
+    //
+
+    struct DragDropFixture {
+        layers: Vec<Rc<RefCell<dyn ControlObject>>>,
+        target_a: Rc<RefCell<StyledControl<TestControl>>>,
+        target_b: Rc<RefCell<StyledControl<TestControl>>>,
+        completed: Rc<RefCell<Vec<DropEffect>>>,
+        dropped: Rc<RefCell<Vec<DropEventArgs>>>,
+        _callbacks: (Callback<DropEffect>, Callback<DropEventArgs>),
+    }
+
+    ///
+    /// Source at (0, 0), target A accepting "text" at (100, 0)
+    /// and target B accepting only "other" at (200, 0).
+    ///
+    fn create_drag_drop_fixture() -> DragDropFixture {
+        let completed = Rc::new(RefCell::new(Vec::new()));
+        let completed_clone = completed.clone();
+        let mut completed_callback = Callback::empty();
+        completed_callback.set(move |effect| completed_clone.borrow_mut().push(effect));
+
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        let dropped_clone = dropped.clone();
+        let mut drop_callback = Callback::empty();
+        drop_callback.set(move |args| dropped_clone.borrow_mut().push(args));
+
+        let mut source_values = TypeMap::new();
+        source_values.insert::<DragSource>(
+            DragSourceHandler::new(|| Some(DragData::new("text", Box::new(42))))
+                .with_completed(completed_callback.clone()),
+        );
+        let source =
+            TestControl::create(Rect::new(0.0, 0.0, 50.0, 50.0), source_values, Vec::new());
+
+        let mut target_a_values = TypeMap::new();
+        target_a_values.insert::<DropTarget>(
+            DropTargetHandler::new(&["text"])
+                .with_effect(DropEffect::Copy)
+                .with_drop(drop_callback.clone()),
+        );
+        let target_a = TestControl::create(
+            Rect::new(100.0, 0.0, 50.0, 50.0),
+            target_a_values,
+            Vec::new(),
+        );
+
+        let mut target_b_values = TypeMap::new();
+        target_b_values.insert::<DropTarget>(
+            DropTargetHandler::new(&["other"]).with_drop(drop_callback.clone()),
+        );
+        let target_b = TestControl::create(
+            Rect::new(200.0, 0.0, 50.0, 50.0),
+            target_b_values,
+            Vec::new(),
+        );
+
+        let root = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![
+                source as Rc<RefCell<dyn ControlObject>>,
+                target_a.clone(),
+                target_b.clone(),
+            ],
+        );
+
+        DragDropFixture {
+            layers: vec![root],
+            target_a,
+            target_b,
+            completed,
+            dropped,
+            _callbacks: (completed_callback, drop_callback),
+        }
+    }
+
+    fn start_drag(processor: &mut EventProcessor, fixture: &DragDropFixture) {
+        handle(processor, &fixture.layers, cursor_moved(10.0, 10.0));
+        handle(
+            processor,
+            &fixture.layers,
+            left_button(ElementState::Pressed),
+        );
+    }
+
+    fn drag_events(control: &Rc<RefCell<StyledControl<TestControl>>>) -> Vec<String> {
+        control
+            .borrow()
+            .data
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ControlEvent::DragEnter { .. } => Some("DragEnter".to_string()),
+                ControlEvent::DragOver { .. } => Some("DragOver".to_string()),
+                ControlEvent::DragLeave => Some("DragLeave".to_string()),
+                ControlEvent::Drop { effect, .. } => Some(format!("Drop({:?})", effect)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_drag_enter_over_drop() {
+        let fixture = create_drag_drop_fixture();
+        let mut processor = EventProcessor::new();
+
+        start_drag(&mut processor, &fixture);
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        handle(&mut processor, &fixture.layers, cursor_moved(120.0, 20.0));
+        assert_eq!(processor.get_cursor_icon(), CursorIcon::Default);
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+
+        assert_eq!(
+            drag_events(&fixture.target_a),
+            vec!["DragEnter", "DragOver", "DragOver", "Drop(Copy)"]
+        );
+        assert!(drag_events(&fixture.target_b).is_empty());
+
+        let dropped = fixture.dropped.borrow();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].data.get_payload::<i32>(), Some(&42));
+        assert_eq!(dropped[0].position, Point::new(120.0, 20.0));
+        assert_eq!(dropped[0].effect, DropEffect::Copy);
+        assert_eq!(*fixture.completed.borrow(), vec![DropEffect::Copy]);
+    }
+
+    #[test]
+    fn test_drag_leave_and_rejected_drop() {
+        let fixture = create_drag_drop_fixture();
+        let mut processor = EventProcessor::new();
+
+        start_drag(&mut processor, &fixture);
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        handle(&mut processor, &fixture.layers, cursor_moved(175.0, 10.0));
+        assert_eq!(
+            drag_events(&fixture.target_a),
+            vec!["DragEnter", "DragOver", "DragLeave"]
+        );
+
+        // target B doesn't accept the format
+        handle(&mut processor, &fixture.layers, cursor_moved(210.0, 10.0));
+        assert_eq!(processor.get_cursor_icon(), CursorIcon::NotAllowed);
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+
+        assert_eq!(
+            drag_events(&fixture.target_b),
+            vec!["DragEnter", "DragOver", "DragLeave"]
+        );
+        assert!(fixture.dropped.borrow().is_empty());
+        assert_eq!(*fixture.completed.borrow(), vec![DropEffect::None]);
+    }
+
+    #[test]
+    fn test_effect_set_by_target_is_kept() {
+        let fixture = create_drag_drop_fixture();
+        let mut processor = EventProcessor::new();
+
+        // target B sets the effect only when the data enters it
+        fixture.target_b.borrow_mut().data.drop_effect = Some(DropEffect::Move);
+
+        start_drag(&mut processor, &fixture);
+        handle(&mut processor, &fixture.layers, cursor_moved(210.0, 10.0));
+        handle(&mut processor, &fixture.layers, cursor_moved(220.0, 10.0));
+        handle(&mut processor, &fixture.layers, cursor_moved(230.0, 10.0));
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+
+        assert_eq!(
+            drag_events(&fixture.target_b),
+            vec![
+                "DragEnter",
+                "DragOver",
+                "DragOver",
+                "DragOver",
+                "Drop(Move)"
+            ]
+        );
+        assert_eq!(*fixture.completed.borrow(), vec![DropEffect::Move]);
+    }
+
+    #[test]
+    fn test_escape_cancels_drag() {
+        let fixture = create_drag_drop_fixture();
+        let mut processor = EventProcessor::new();
+
+        start_drag(&mut processor, &fixture);
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        handle(&mut processor, &fixture.layers, key_pressed(Keycode::Esc));
+        assert_eq!(
+            drag_events(&fixture.target_a),
+            vec!["DragEnter", "DragOver", "DragLeave"]
+        );
+        assert_eq!(*fixture.completed.borrow(), vec![DropEffect::None]);
+
+        handle(&mut processor, &fixture.layers, cursor_moved(120.0, 10.0));
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+        assert_eq!(
+            drag_events(&fixture.target_a),
+            vec!["DragEnter", "DragOver", "DragLeave"]
+        );
+        assert!(fixture.dropped.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_removed_drop_target_is_not_dropped_on() {
+        let fixture = create_drag_drop_fixture();
+        let mut processor = EventProcessor::new();
+
+        start_drag(&mut processor, &fixture);
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        fixture
+            .target_a
+            .borrow_mut()
+            .get_context_mut()
+            .get_attached_values_mut()
+            .remove::<DropTarget>();
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+
+        assert_eq!(
+            drag_events(&fixture.target_a),
+            vec!["DragEnter", "DragOver", "DragLeave"]
+        );
+        assert!(fixture.dropped.borrow().is_empty());
+        assert_eq!(*fixture.completed.borrow(), vec![DropEffect::None]);
+    }
+
+    //
+    // Tool tips.
+    //
+
+    struct ToolTipFixture {
+        layers: Vec<Rc<RefCell<dyn ControlObject>>>,
+        window_service: Rc<RefCell<TestWindowService>>,
+        control: Rc<RefCell<StyledControl<TestControl>>>,
+        _services: Rc<RefCell<Services>>,
+    }
+
+    ///
+    /// Control with the tool tip at (100, 0) on the main layer.
+    ///
+    fn create_tool_tip_fixture() -> ToolTipFixture {
+        let mut values = TypeMap::new();
+        values.insert::<ToolTip>(ToolTipContent::from("tool tip"));
+        let control = TestControl::create(Rect::new(100.0, 0.0, 50.0, 50.0), values, Vec::new());
+
+        let root: Rc<RefCell<dyn ControlObject>> = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![control.clone() as Rc<RefCell<dyn ControlObject>>],
+        );
+        let (window_service, services) = TestWindowService::create(root.clone());
+
+        ToolTipFixture {
+            layers: vec![root],
+            window_service,
+            control,
+            _services: services,
+        }
+    }
+
+    fn get_tool_tip_count(fixture: &ToolTipFixture) -> usize {
+        fixture.window_service.borrow().get_layers().len() - 1
+    }
+
+    fn handle_tool_tip_timer(
+        processor: &mut EventProcessor,
+        fixture: &ToolTipFixture,
+        time: Instant,
+    ) {
+        processor.handle_timers(&fixture.layers, &mut TestDrawingContext, time);
+        CallbackExecutor::execute_all_in_queue();
+    }
+
+    #[test]
+    fn test_tool_tip_is_shown_after_delay() {
+        let fixture = create_tool_tip_fixture();
+        let mut processor = EventProcessor::new();
+
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        let time = processor.get_next_timer().unwrap();
+        assert!(time >= Instant::now() + TOOL_TIP_DELAY - Duration::from_millis(100));
+
+        handle_tool_tip_timer(&mut processor, &fixture, time - Duration::from_millis(1));
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+
+        // moving the pointer restarts the delay
+        handle(&mut processor, &fixture.layers, cursor_moved(112.0, 10.0));
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+
+        let time = processor.get_next_timer().unwrap();
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 1);
+
+        let window_service = fixture.window_service.borrow();
+        let tool_tip_layer = &window_service.get_layers()[1];
+        assert_eq!(
+            window_service.get_layer_options(tool_tip_layer),
+            Some(LayerOptions::overlay())
+        );
+    }
+
+    #[test]
+    fn test_tool_tip_is_hidden_on_leave() {
+        let fixture = create_tool_tip_fixture();
+        let mut processor = EventProcessor::new();
+
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        let time = processor.get_next_timer().unwrap();
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 1);
+
+        handle(&mut processor, &fixture.layers, cursor_moved(10.0, 10.0));
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+        assert_eq!(processor.get_next_timer(), None);
+
+        // shown again when the pointer comes back
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        let time = processor.get_next_timer().unwrap();
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 1);
+
+        handle(&mut processor, &fixture.layers, InputEvent::CursorLeft {});
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+    }
+
+    #[test]
+    fn test_tool_tip_is_hidden_on_click() {
+        let fixture = create_tool_tip_fixture();
+        let mut processor = EventProcessor::new();
+
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        let time = processor.get_next_timer().unwrap();
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 1);
+
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Pressed),
+        );
+        handle(
+            &mut processor,
+            &fixture.layers,
+            left_button(ElementState::Released),
+        );
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+
+        // not shown again until the pointer leaves the control
+        handle(&mut processor, &fixture.layers, cursor_moved(111.0, 10.0));
+        handle_tool_tip_timer(&mut processor, &fixture, time + Duration::from_secs(10));
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+    }
+
+    #[test]
+    fn test_removed_tool_tip_is_not_shown() {
+        let fixture = create_tool_tip_fixture();
+        let mut processor = EventProcessor::new();
+
+        handle(&mut processor, &fixture.layers, cursor_moved(110.0, 10.0));
+        fixture
+            .control
+            .borrow_mut()
+            .get_context_mut()
+            .get_attached_values_mut()
+            .remove::<ToolTip>();
+
+        let time = processor.get_next_timer().unwrap();
+        handle_tool_tip_timer(&mut processor, &fixture, time);
+        assert_eq!(get_tool_tip_count(&fixture), 0);
+    }
+
+    //
+    // Keyboard routing.
+    //
+
+    fn get_keys(control: &Rc<RefCell<StyledControl<TestControl>>>) -> Vec<Keycode> {
+        control
+            .borrow()
+            .data
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ControlEvent::KeyboardInput(key_event) => key_event.keycode.clone(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    ///
+    /// Layer covering the whole window with a control at the given position.
+    ///
+    fn create_layer(
+        x: f32,
+    ) -> (
+        Rc<RefCell<StyledControl<TestControl>>>,
+        Rc<RefCell<StyledControl<TestControl>>>,
+    ) {
+        let control =
+            TestControl::create(Rect::new(x, 0.0, 50.0, 50.0), TypeMap::new(), Vec::new());
+        let layer = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![control.clone() as Rc<RefCell<dyn ControlObject>>],
+        );
+        (layer, control)
+    }
+
+    fn tap(processor: &mut EventProcessor, layers: &[Rc<RefCell<dyn ControlObject>>], x: f32) {
+        handle(processor, layers, cursor_moved(x + 10.0, 10.0));
+        handle(processor, layers, left_button(ElementState::Pressed));
+        handle(processor, layers, left_button(ElementState::Released));
+    }
+
+    #[test]
+    fn test_keys_go_to_focused_control_and_its_layer() {
+        let (main_layer, control) = create_layer(0.0);
+        let layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![main_layer.clone()];
+        let mut processor = EventProcessor::new();
+
+        // without focus only the layer gets the keys
+        handle(&mut processor, &layers, key_pressed(Keycode::A));
+        assert!(get_keys(&control).is_empty());
+        assert_eq!(get_keys(&main_layer), vec![Keycode::A]);
+
+        tap(&mut processor, &layers, 0.0);
+        handle(&mut processor, &layers, key_pressed(Keycode::B));
+        assert_eq!(get_keys(&control), vec![Keycode::B]);
+        assert_eq!(get_keys(&main_layer), vec![Keycode::A, Keycode::B]);
+    }
+
+    #[test]
+    fn test_enter_is_not_passed_to_layer_from_control_accepting_return() {
+        let mut values = TypeMap::new();
+        values.insert::<AcceptsReturn>(true);
+        let control = TestControl::create(Rect::new(0.0, 0.0, 50.0, 50.0), values, Vec::new());
+        let main_layer = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![control.clone() as Rc<RefCell<dyn ControlObject>>],
+        );
+        let layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![main_layer.clone()];
+        let mut processor = EventProcessor::new();
+
+        tap(&mut processor, &layers, 0.0);
+        handle(&mut processor, &layers, key_pressed(Keycode::Enter));
+        handle(&mut processor, &layers, key_pressed(Keycode::Esc));
+        assert_eq!(get_keys(&control), vec![Keycode::Enter, Keycode::Esc]);
+        assert_eq!(get_keys(&main_layer), vec![Keycode::Esc]);
+    }
+
+    #[test]
+    fn test_modal_layer_blocks_lower_layers() {
+        let (main_layer, main_control) = create_layer(0.0);
+        let (dialog_layer, dialog_control) = create_layer(100.0);
+        let mut processor = EventProcessor::new();
+
+        let main_layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![main_layer.clone()];
+        tap(&mut processor, &main_layers, 0.0);
+
+        // the modal layer is the only input layer
+        let dialog_layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![dialog_layer.clone()];
+
+        // keys don't go to the control focused on the lower layer
+        handle(&mut processor, &dialog_layers, key_pressed(Keycode::Esc));
+        assert!(get_keys(&main_control).is_empty());
+        assert!(get_keys(&main_layer).is_empty());
+        assert_eq!(get_keys(&dialog_layer), vec![Keycode::Esc]);
+
+        // neither does the pointer input
+        let get_tap_count = |control: &Rc<RefCell<StyledControl<TestControl>>>| {
+            control
+                .borrow()
+                .data
+                .events
+                .iter()
+                .filter(|event| matches!(event, ControlEvent::TapDown { .. }))
+                .count()
+        };
+        assert_eq!(get_tap_count(&main_control), 1);
+        tap(&mut processor, &dialog_layers, 0.0);
+        assert_eq!(get_tap_count(&main_control), 1);
+        assert_eq!(get_tap_count(&dialog_layer), 1);
+
+        // focus is trapped in the dialog
+        tap(&mut processor, &dialog_layers, 100.0);
+        handle(&mut processor, &dialog_layers, key_pressed(Keycode::Enter));
+        assert_eq!(get_keys(&dialog_control), vec![Keycode::Enter]);
+        assert_eq!(get_keys(&dialog_layer), vec![Keycode::Esc, Keycode::Enter]);
+        assert!(get_keys(&main_control).is_empty());
+    }
+
+    //
+    // Event filters and mouse wheel.
+    //
+
+    #[test]
+    fn test_event_filter_of_parent_handles_events_of_child() {
+        let is_consuming = Rc::new(Cell::new(true));
+        let filtered = Rc::new(RefCell::new(Vec::new()));
+        let is_consuming_clone = is_consuming.clone();
+        let filtered_clone = filtered.clone();
+        let mut values = TypeMap::new();
+        values.insert::<EventFilter>(EventFilterHandler::new(move |event| match event {
+            ControlEvent::KeyboardInput(key_event) => {
+                filtered_clone
+                    .borrow_mut()
+                    .push(key_event.keycode.clone().unwrap());
+                is_consuming_clone.get()
+            }
+            _ => false,
+        }));
+
+        let control =
+            TestControl::create(Rect::new(0.0, 0.0, 50.0, 50.0), TypeMap::new(), Vec::new());
+        let parent = TestControl::create(
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            values,
+            vec![control.clone() as Rc<RefCell<dyn ControlObject>>],
+        );
+        let main_layer = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![parent.clone() as Rc<RefCell<dyn ControlObject>>],
+        );
+        let layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![main_layer.clone()];
+        let mut processor = EventProcessor::new();
+
+        // events not handled by the filter are passed
+        tap(&mut processor, &layers, 0.0);
+        assert!(control
+            .borrow()
+            .data
+            .events
+            .iter()
+            .any(|event| matches!(event, ControlEvent::TapDown { .. })));
+
+        // the key sent to the child is handled by the filter of the parent
+        handle(&mut processor, &layers, key_pressed(Keycode::Up));
+        assert_eq!(*filtered.borrow(), vec![Keycode::Up]);
+        assert!(get_keys(&control).is_empty());
+        assert!(get_keys(&parent).is_empty());
+
+        // returning false lets the event through
+        is_consuming.set(false);
+        handle(&mut processor, &layers, key_pressed(Keycode::Down));
+        assert_eq!(*filtered.borrow(), vec![Keycode::Up, Keycode::Down]);
+        assert_eq!(get_keys(&control), vec![Keycode::Down]);
+    }
+
+    #[test]
+    fn test_mouse_wheel_goes_to_hovered_or_captured_control() {
+        let control_a =
+            TestControl::create(Rect::new(0.0, 0.0, 50.0, 50.0), TypeMap::new(), Vec::new());
+        let control_b = TestControl::create(
+            Rect::new(100.0, 0.0, 50.0, 50.0),
+            TypeMap::new(),
+            Vec::new(),
+        );
+        let main_layer = TestControl::create(
+            Rect::new(0.0, 0.0, 300.0, 100.0),
+            TypeMap::new(),
+            vec![
+                control_a.clone() as Rc<RefCell<dyn ControlObject>>,
+                control_b.clone() as Rc<RefCell<dyn ControlObject>>,
+            ],
+        );
+        let layers: Vec<Rc<RefCell<dyn ControlObject>>> = vec![main_layer.clone()];
+        let mut processor = EventProcessor::new();
+
+        let get_wheel_events = |control: &Rc<RefCell<StyledControl<TestControl>>>| {
+            control
+                .borrow()
+                .data
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    ControlEvent::MouseWheel { position, delta } => Some((*position, *delta)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let wheel = |delta_y| InputEvent::MouseWheel {
+            delta: Point::new(0.0, delta_y),
+        };
+
+        // hovered control
+        handle(&mut processor, &layers, cursor_moved(110.0, 10.0));
+        handle(&mut processor, &layers, wheel(1.0));
+        assert!(get_wheel_events(&control_a).is_empty());
+        assert_eq!(
+            get_wheel_events(&control_b),
+            vec![(Point::new(110.0, 10.0), Point::new(0.0, 1.0))]
+        );
+
+        // captured control gets the wheel even when the pointer is over another control
+        handle(&mut processor, &layers, cursor_moved(10.0, 10.0));
+        handle(&mut processor, &layers, left_button(ElementState::Pressed));
+        handle(&mut processor, &layers, cursor_moved(110.0, 10.0));
+        handle(&mut processor, &layers, wheel(-1.0));
+        assert_eq!(
+            get_wheel_events(&control_a),
+            vec![(Point::new(110.0, 10.0), Point::new(0.0, -1.0))]
+        );
+        assert_eq!(get_wheel_events(&control_b).len(), 1);
+
+        // after the release the hovered control gets it again
+        handle(&mut processor, &layers, left_button(ElementState::Released));
+        handle(&mut processor, &layers, wheel(2.0));
+        assert_eq!(get_wheel_events(&control_a).len(), 1);
+        assert_eq!(get_wheel_events(&control_b).len(), 2);
+    }
 }
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::common::Point;
+use crate::events::*;
+
+const DEFAULT_FLING_MIN_VELOCITY: f32 = 300.0f32;
+const VELOCITY_SAMPLE_DURATION: Duration = Duration::from_millis(100);
+
+///
+/// Recognizes releasing the left mouse button while the pointer moves fast.
+///
+/// The velocity is calculated from the pointer positions
+/// in the last 100 milliseconds before the release.
+///
+pub struct FlingRecognizer {
+    min_velocity: f32,
+    is_pressed: bool,
+    samples: VecDeque<(Instant, Point)>,
+}
+
+impl FlingRecognizer {
+    pub fn new() -> Self {
+        FlingRecognizer::with_min_velocity(DEFAULT_FLING_MIN_VELOCITY)
+    }
+
+    pub fn with_min_velocity(min_velocity: f32) -> Self {
+        FlingRecognizer {
+            min_velocity,
+            is_pressed: false,
+            samples: VecDeque::new(),
+        }
+    }
+
+    fn add_sample(&mut self, time: Instant, position: Point) {
+        self.samples.push_back((time, position));
+        self.remove_old_samples(time);
+    }
+
+    fn remove_old_samples(&mut self, time: Instant) {
+        // keep one sample older than the window, so the velocity
+        // can be calculated from the whole sample duration
+        while self.samples.len() > 1 {
+            if time.duration_since(self.samples[1].0) > VELOCITY_SAMPLE_DURATION {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn get_velocity(&self) -> Option<Point> {
+        if let (Some((first_time, first_pos)), Some((last_time, last_pos))) =
+            (self.samples.front(), self.samples.back())
+        {
+            let seconds = last_time.duration_since(*first_time).as_secs_f32();
+            if seconds > 0.0f32 {
+                return Some(Point::new(
+                    (last_pos.x - first_pos.x) / seconds,
+                    (last_pos.y - first_pos.y) / seconds,
+                ));
+            }
+        }
+        None
+    }
+}
+
+impl Default for FlingRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for FlingRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, time: Instant) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match event {
+            InputEvent::CursorMoved { position } => {
+                if self.is_pressed {
+                    self.add_sample(time, *position);
+                } else {
+                    self.samples.clear();
+                    self.samples.push_back((time, *position));
+                }
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+            } => {
+                self.is_pressed = true;
+                let last_sample = self.samples.pop_back();
+                self.samples.clear();
+                if let Some((_, position)) = last_sample {
+                    self.samples.push_back((time, position));
+                }
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+            } => {
+                self.is_pressed = false;
+
+                // the pointer stopped before the release
+                // if there are no samples in the velocity window
+                if let Some((_, position)) = self.samples.back().cloned() {
+                    self.add_sample(time, position);
+                }
+
+                if let (Some(velocity), Some((_, position))) =
+                    (self.get_velocity(), self.samples.back())
+                {
+                    let speed = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+                    if speed >= self.min_velocity {
+                        gestures.push(Gesture::Fling {
+                            position: *position,
+                            velocity,
+                        });
+                    }
+                }
+            }
+
+            _ => (),
+        }
+
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Point;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use std::time::{Duration, Instant};
+
+    fn ms(t0: Instant, millis: u64) -> Instant {
+        t0 + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_fling() {
+        let mut recognizer = FlingRecognizer::with_min_velocity(300.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&cursor_moved(0.0, 0.0), t0);
+        recognizer.handle_event(&left_button(ElementState::Pressed), ms(t0, 10));
+        recognizer.handle_event(&cursor_moved(0.0, 20.0), ms(t0, 30));
+        recognizer.handle_event(&cursor_moved(0.0, 40.0), ms(t0, 50));
+        recognizer.handle_event(&cursor_moved(0.0, 60.0), ms(t0, 70));
+
+        let gestures = recognizer.handle_event(&left_button(ElementState::Released), ms(t0, 70));
+        assert_eq!(gestures.len(), 1);
+        if let Gesture::Fling { position, velocity } = gestures[0] {
+            assert_eq!(position, Point::new(0.0, 60.0));
+            assert!(velocity.x.abs() < 0.001f32);
+            assert!((velocity.y - 1000.0f32).abs() < 0.1f32);
+        } else {
+            panic!("Expected Gesture::Fling");
+        }
+    }
+
+    #[test]
+    fn test_slow_move_is_not_fling() {
+        let mut recognizer = FlingRecognizer::with_min_velocity(300.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        recognizer.handle_event(&cursor_moved(0.0, 5.0), ms(t0, 50));
+        recognizer.handle_event(&cursor_moved(0.0, 10.0), ms(t0, 100));
+        assert!(recognizer
+            .handle_event(&left_button(ElementState::Released), ms(t0, 100))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_stop_before_release_is_not_fling() {
+        let mut recognizer = FlingRecognizer::with_min_velocity(300.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        recognizer.handle_event(&cursor_moved(0.0, 50.0), ms(t0, 20));
+        recognizer.handle_event(&cursor_moved(0.0, 100.0), ms(t0, 40));
+        assert!(recognizer
+            .handle_event(&left_button(ElementState::Released), ms(t0, 400))
+            .is_empty());
+    }
+}
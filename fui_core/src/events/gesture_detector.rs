@@ -5,10 +5,28 @@ use crate::common::Point;
 use crate::control::ControlObject;
 use crate::events::*;
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Gesture {
     TapDown { position: Point },
     TapUp { position: Point },
     TapMove { position: Point },
+
+    DragStart { position: Point },
+    DragMove { position: Point, delta: Point },
+    DragEnd { position: Point },
+
+    LongPress { position: Point },
+
+    DoubleTap { position: Point },
+
+    /// Velocity is in pixels per second.
+    Fling { position: Point, velocity: Point },
+
+    /// Scale is relative to the previous Pinch event.
+    Pinch { center: Point, scale: f32 },
+
+    /// Angle (in radians) is relative to the previous Rotate event.
+    Rotate { center: Point, angle: f32 },
 }
 
 pub struct GestureDetector {
@@ -0,0 +1,38 @@
+use std::time::Instant;
+
+use crate::common::Point;
+use crate::events::*;
+
+///
+/// Gesture recognizer is a state machine fed with raw input events.
+///
+/// The time of the event is passed explicitly, so recognizers can be tested
+/// by feeding them with input event sequences with synthetic timestamps.
+///
+/// Recognizers are registered in the EventProcessor which routes recognized
+/// gestures to the controls as ControlEvents.
+///
+pub trait GestureRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, time: Instant) -> Vec<Gesture>;
+
+    ///
+    /// Called periodically for time based gestures (like long press)
+    /// that can be recognized without new input events.
+    ///
+    fn handle_timers(&mut self, _time: Instant) -> Vec<Gesture> {
+        Vec::new()
+    }
+
+    ///
+    /// Returns the time when handle_timers() should be called next.
+    ///
+    fn get_next_timer(&self) -> Option<Instant> {
+        None
+    }
+}
+
+pub(crate) fn distance(p1: &Point, p2: &Point) -> f32 {
+    let dx = p2.x - p1.x;
+    let dy = p2.y - p1.y;
+    (dx * dx + dy * dy).sqrt()
+}
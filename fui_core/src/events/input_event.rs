@@ -1,6 +1,7 @@
 use crate::common::Point;
-use crate::events::key_event::KeyEvent;
+use crate::events::key_event::{KeyEvent, KeyModifiers};
 
+#[derive(Debug, PartialEq)]
 pub enum MouseButton {
     Left,
     Right,
@@ -8,11 +9,21 @@ pub enum MouseButton {
     Other(u8),
 }
 
+#[derive(Debug, PartialEq)]
 pub enum ElementState {
     Pressed,
     Released,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Debug, PartialEq)]
 pub enum InputEvent {
     CursorEntered {},
 
@@ -27,5 +38,20 @@ pub enum InputEvent {
         button: MouseButton,
     },
 
+    /// Touch point of a single finger.
+    /// The id is unique for the finger as long as it touches the screen.
+    Touch {
+        phase: TouchPhase,
+        id: u64,
+        position: Point,
+    },
+
+    /// Delta is in lines (positive y when the wheel is rotated away from the user).
+    MouseWheel {
+        delta: Point,
+    },
+
     KeyboardInput(KeyEvent),
+
+    ModifiersChanged(KeyModifiers),
 }
@@ -56,12 +56,52 @@ pub enum Keycode {
     Copy,
     Paste,
     Cut,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct KeyModifiers {
     pub shift: bool,
     pub ctrl: bool,
     pub alt: bool,
     pub win: bool,
 }
+
+//
+// Attached values.
+//
+
+///
+/// Marks controls that use Enter key themselves (like multi-line text editors).
+/// Enter pressed in such control is not passed to its layer
+/// (so it doesn't accept the modal dialog).
+///
+pub struct AcceptsReturn;
+impl typemap::Key for AcceptsReturn {
+    type Value = bool;
+}
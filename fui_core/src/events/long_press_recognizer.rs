@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+use crate::common::Point;
+use crate::events::gesture_recognizer::distance;
+use crate::events::*;
+
+const DEFAULT_LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+const DEFAULT_LONG_PRESS_TOLERANCE: f32 = 4.0f32;
+
+///
+/// Recognizes holding the left mouse button pressed without moving
+/// the pointer further than the tolerance for the given duration.
+///
+pub struct LongPressRecognizer {
+    duration: Duration,
+    tolerance: f32,
+    position: Point,
+    pressed: Option<(Instant, Point)>,
+}
+
+impl LongPressRecognizer {
+    pub fn new() -> Self {
+        LongPressRecognizer::with_params(DEFAULT_LONG_PRESS_DURATION, DEFAULT_LONG_PRESS_TOLERANCE)
+    }
+
+    pub fn with_params(duration: Duration, tolerance: f32) -> Self {
+        LongPressRecognizer {
+            duration,
+            tolerance,
+            position: Point::new(0f32, 0f32),
+            pressed: None,
+        }
+    }
+}
+
+impl Default for LongPressRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for LongPressRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, time: Instant) -> Vec<Gesture> {
+        // events may come late, so check the timer first
+        let gestures = self.handle_timers(time);
+
+        match event {
+            InputEvent::CursorMoved { position } => {
+                self.position = *position;
+                if let Some((_, press_position)) = self.pressed {
+                    if distance(&press_position, &self.position) > self.tolerance {
+                        self.pressed = None;
+                    }
+                }
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+            } => {
+                self.pressed = Some((time, self.position));
+            }
+
+            InputEvent::MouseInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+            } => {
+                self.pressed = None;
+            }
+
+            _ => (),
+        }
+
+        gestures
+    }
+
+    fn handle_timers(&mut self, time: Instant) -> Vec<Gesture> {
+        if let Some((press_time, press_position)) = self.pressed {
+            if time >= press_time + self.duration {
+                self.pressed = None;
+                return vec![Gesture::LongPress {
+                    position: press_position,
+                }];
+            }
+        }
+        Vec::new()
+    }
+
+    fn get_next_timer(&self) -> Option<Instant> {
+        self.pressed
+            .map(|(press_time, _)| press_time + self.duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Point;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_long_press_fires_on_timer() {
+        let mut recognizer = LongPressRecognizer::with_params(Duration::from_millis(500), 4.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&cursor_moved(10.0, 20.0), t0);
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        assert_eq!(
+            recognizer.get_next_timer(),
+            Some(t0 + Duration::from_millis(500))
+        );
+
+        assert!(recognizer
+            .handle_timers(t0 + Duration::from_millis(499))
+            .is_empty());
+        assert_eq!(
+            recognizer.handle_timers(t0 + Duration::from_millis(500)),
+            vec![Gesture::LongPress {
+                position: Point::new(10.0, 20.0)
+            }]
+        );
+
+        // fires only once
+        assert!(recognizer
+            .handle_timers(t0 + Duration::from_millis(1000))
+            .is_empty());
+        assert_eq!(recognizer.get_next_timer(), None);
+    }
+
+    #[test]
+    fn test_long_press_fires_on_late_event() {
+        let mut recognizer = LongPressRecognizer::with_params(Duration::from_millis(500), 4.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        assert_eq!(
+            recognizer.handle_event(&cursor_moved(1.0, 1.0), t0 + Duration::from_millis(600)),
+            vec![Gesture::LongPress {
+                position: Point::new(0.0, 0.0)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_release_cancels_long_press() {
+        let mut recognizer = LongPressRecognizer::with_params(Duration::from_millis(500), 4.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        recognizer.handle_event(
+            &left_button(ElementState::Released),
+            t0 + Duration::from_millis(200),
+        );
+        assert!(recognizer
+            .handle_timers(t0 + Duration::from_millis(600))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_move_cancels_long_press() {
+        let mut recognizer = LongPressRecognizer::with_params(Duration::from_millis(500), 4.0f32);
+        let t0 = Instant::now();
+
+        recognizer.handle_event(&left_button(ElementState::Pressed), t0);
+        recognizer.handle_event(&cursor_moved(10.0, 0.0), t0 + Duration::from_millis(100));
+        assert!(recognizer
+            .handle_timers(t0 + Duration::from_millis(600))
+            .is_empty());
+    }
+}
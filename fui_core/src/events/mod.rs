@@ -1,17 +1,56 @@
 mod control_event;
 pub use self::control_event::*;
 
+mod double_tap_recognizer;
+pub use self::double_tap_recognizer::*;
+
+mod drag_drop;
+pub use self::drag_drop::*;
+
+mod drag_layer;
+pub use self::drag_layer::*;
+
+mod drag_recognizer;
+pub use self::drag_recognizer::*;
+
 mod event_context;
 pub use self::event_context::*;
 
+mod event_filter;
+pub use self::event_filter::*;
+
 mod event_processor;
 pub use self::event_processor::*;
 
+mod fling_recognizer;
+pub use self::fling_recognizer::*;
+
 mod gesture_detector;
 pub use self::gesture_detector::*;
 
+mod gesture_recognizer;
+pub use self::gesture_recognizer::*;
+
 mod input_event;
 pub use self::input_event::*;
 
 mod key_event;
 pub use self::key_event::*;
+
+mod long_press_recognizer;
+pub use self::long_press_recognizer::*;
+
+mod pinch_rotate_recognizer;
+pub use self::pinch_rotate_recognizer::*;
+
+#[cfg(test)]
+pub(crate) mod test_helpers;
+
+mod tool_tip;
+pub use self::tool_tip::*;
+
+mod tool_tip_layer;
+pub use self::tool_tip_layer::*;
+
+mod touch_emulator;
+pub use self::touch_emulator::*;
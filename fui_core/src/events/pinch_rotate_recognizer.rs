@@ -0,0 +1,206 @@
+use std::f32::consts::PI;
+use std::time::Instant;
+
+use crate::common::Point;
+use crate::events::gesture_recognizer::distance;
+use crate::events::*;
+
+///
+/// Recognizes two-finger pinch (zoom) and rotate gestures.
+///
+/// Only the first two fingers touching the screen are tracked.
+/// Both gestures are reported incrementally, relative to the previous
+/// position of the fingers, so they can be applied directly
+/// to the current zoom factor or angle.
+///
+pub struct PinchRotateRecognizer {
+    touches: Vec<(u64, Point)>,
+}
+
+impl PinchRotateRecognizer {
+    pub fn new() -> Self {
+        PinchRotateRecognizer {
+            touches: Vec::new(),
+        }
+    }
+
+    fn get_center(&self) -> Point {
+        let p1 = self.touches[0].1;
+        let p2 = self.touches[1].1;
+        Point::new((p1.x + p2.x) / 2.0f32, (p1.y + p2.y) / 2.0f32)
+    }
+
+    fn get_distance(&self) -> f32 {
+        distance(&self.touches[0].1, &self.touches[1].1)
+    }
+
+    fn get_angle(&self) -> f32 {
+        let p1 = self.touches[0].1;
+        let p2 = self.touches[1].1;
+        (p2.y - p1.y).atan2(p2.x - p1.x)
+    }
+}
+
+impl Default for PinchRotateRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for PinchRotateRecognizer {
+    fn handle_event(&mut self, event: &InputEvent, _time: Instant) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        match event {
+            InputEvent::Touch {
+                phase: TouchPhase::Started,
+                id,
+                position,
+            } if self.touches.len() < 2 => {
+                self.touches.push((*id, *position));
+            }
+
+            InputEvent::Touch {
+                phase: TouchPhase::Moved,
+                id,
+                position,
+            } => {
+                if let Some(index) = self.touches.iter().position(|(i, _)| i == id) {
+                    if self.touches.len() < 2 {
+                        self.touches[index].1 = *position;
+                        return gestures;
+                    }
+
+                    let old_distance = self.get_distance();
+                    let old_angle = self.get_angle();
+                    self.touches[index].1 = *position;
+                    let new_distance = self.get_distance();
+                    let new_angle = self.get_angle();
+
+                    let center = self.get_center();
+
+                    if old_distance > 0.0f32 && new_distance != old_distance {
+                        gestures.push(Gesture::Pinch {
+                            center,
+                            scale: new_distance / old_distance,
+                        });
+                    }
+
+                    let mut angle = new_angle - old_angle;
+                    if angle > PI {
+                        angle -= 2.0f32 * PI;
+                    } else if angle < -PI {
+                        angle += 2.0f32 * PI;
+                    }
+                    if angle != 0.0f32 {
+                        gestures.push(Gesture::Rotate { center, angle });
+                    }
+                }
+            }
+
+            InputEvent::Touch {
+                phase: TouchPhase::Ended,
+                id,
+                ..
+            }
+            | InputEvent::Touch {
+                phase: TouchPhase::Cancelled,
+                id,
+                ..
+            } => {
+                self.touches.retain(|(i, _)| i != id);
+            }
+
+            _ => (),
+        }
+
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Point;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_pinch() {
+        let mut recognizer = PinchRotateRecognizer::new();
+        let time = Instant::now();
+
+        recognizer.handle_event(&touch(TouchPhase::Started, 1, 0.0, 0.0), time);
+        recognizer.handle_event(&touch(TouchPhase::Started, 2, 10.0, 0.0), time);
+
+        assert_eq!(
+            recognizer.handle_event(&touch(TouchPhase::Moved, 2, 20.0, 0.0), time),
+            vec![Gesture::Pinch {
+                center: Point::new(10.0, 0.0),
+                scale: 2.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut recognizer = PinchRotateRecognizer::new();
+        let time = Instant::now();
+
+        recognizer.handle_event(&touch(TouchPhase::Started, 1, 0.0, 0.0), time);
+        recognizer.handle_event(&touch(TouchPhase::Started, 2, 10.0, 0.0), time);
+
+        let gestures = recognizer.handle_event(&touch(TouchPhase::Moved, 2, 0.0, 10.0), time);
+        assert_eq!(gestures.len(), 1);
+        if let Gesture::Rotate { center, angle } = gestures[0] {
+            assert_eq!(center, Point::new(0.0, 5.0));
+            assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 0.001f32);
+        } else {
+            panic!("Expected Gesture::Rotate");
+        }
+    }
+
+    #[test]
+    fn test_single_finger_is_not_pinch() {
+        let mut recognizer = PinchRotateRecognizer::new();
+        let time = Instant::now();
+
+        recognizer.handle_event(&touch(TouchPhase::Started, 1, 0.0, 0.0), time);
+        recognizer.handle_event(&touch(TouchPhase::Started, 2, 10.0, 0.0), time);
+        recognizer.handle_event(&touch(TouchPhase::Ended, 2, 10.0, 0.0), time);
+
+        assert!(recognizer
+            .handle_event(&touch(TouchPhase::Moved, 1, 50.0, 50.0), time)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_pinch_and_rotate_together() {
+        let mut recognizer = PinchRotateRecognizer::new();
+        let time = Instant::now();
+
+        let events = vec![
+            touch(TouchPhase::Started, 1, 0.0, 0.0),
+            touch(TouchPhase::Started, 2, 10.0, 0.0),
+            touch(TouchPhase::Moved, 2, 0.0, 20.0),
+        ];
+
+        let mut gestures = Vec::new();
+        for event in &events {
+            gestures.append(&mut recognizer.handle_event(event, time));
+        }
+
+        assert_eq!(gestures.len(), 2);
+        if let Gesture::Pinch { center, scale } = gestures[0] {
+            assert_eq!(center, Point::new(0.0, 10.0));
+            assert!((scale - 2.0f32).abs() < 0.001f32);
+        } else {
+            panic!("Expected Gesture::Pinch");
+        }
+        if let Gesture::Rotate { angle, .. } = gestures[1] {
+            assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 0.001f32);
+        } else {
+            panic!("Expected Gesture::Rotate");
+        }
+    }
+}
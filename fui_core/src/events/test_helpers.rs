@@ -0,0 +1,252 @@
+// Fixtures shared by the input handling tests.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use typemap::TypeMap;
+
+use crate::common::*;
+use crate::control::*;
+use crate::events::*;
+use crate::observable::ObservableCollection;
+use crate::services::*;
+use crate::style::*;
+use crate::view::ViewContext;
+use crate::{DrawingContext, Resources};
+
+pub fn cursor_moved(x: f32, y: f32) -> InputEvent {
+    InputEvent::CursorMoved {
+        position: Point::new(x, y),
+    }
+}
+
+pub fn left_button(state: ElementState) -> InputEvent {
+    InputEvent::MouseInput {
+        state,
+        button: MouseButton::Left,
+    }
+}
+
+pub fn touch(phase: TouchPhase, id: u64, x: f32, y: f32) -> InputEvent {
+    InputEvent::Touch {
+        phase,
+        id,
+        position: Point::new(x, y),
+    }
+}
+
+pub fn key_pressed(keycode: Keycode) -> InputEvent {
+    InputEvent::KeyboardInput(KeyEvent {
+        state: KeyState::Pressed,
+        keycode: Some(keycode),
+        text: None,
+        modifiers: KeyModifiers::default(),
+    })
+}
+
+//
+// TestDrawingContext.
+//
+
+pub struct TestDrawingContext;
+
+impl DrawingContext for TestDrawingContext {
+    fn get_drawing_area_size(&self) -> (u16, u16) {
+        (800, 600)
+    }
+
+    fn get_resources(&mut self) -> &mut dyn Resources {
+        unimplemented!("test controls don't use resources")
+    }
+}
+
+//
+// TestWindowService.
+//
+
+pub struct TestWindowService {
+    pub layers: WindowLayers,
+}
+
+impl TestWindowService {
+    ///
+    /// Returns the window service with the given main layer
+    /// and the services (with the window service registered)
+    /// already set on the layer.
+    ///
+    pub fn create(
+        main_layer: Rc<RefCell<dyn ControlObject>>,
+    ) -> (Rc<RefCell<TestWindowService>>, Rc<RefCell<Services>>) {
+        let window_service = Rc::new(RefCell::new(TestWindowService {
+            layers: WindowLayers::new(),
+        }));
+        window_service.borrow_mut().add_layer(main_layer.clone());
+
+        let services = Rc::new(RefCell::new(Services::new()));
+        services
+            .borrow_mut()
+            .register::<dyn WindowService>(window_service.clone());
+        main_layer
+            .borrow_mut()
+            .get_context_mut()
+            .set_services(Some(Rc::downgrade(&services)));
+
+        (window_service, services)
+    }
+}
+
+impl WindowService for TestWindowService {
+    fn add_layer(&mut self, control: Rc<RefCell<dyn ControlObject>>) {
+        self.layers.add(control, LayerOptions::modal());
+    }
+
+    fn add_layer_with_options(
+        &mut self,
+        control: Rc<RefCell<dyn ControlObject>>,
+        options: LayerOptions,
+    ) {
+        self.layers.add(control, options);
+    }
+
+    fn remove_layer(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        self.layers.remove(control);
+    }
+
+    fn get_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>> {
+        self.layers.get_layers()
+    }
+
+    fn get_layer_options(&self, control: &Rc<RefCell<dyn ControlObject>>) -> Option<LayerOptions> {
+        self.layers.get_options(control)
+    }
+
+    fn set_layer_options(
+        &mut self,
+        control: &Rc<RefCell<dyn ControlObject>>,
+        options: LayerOptions,
+    ) {
+        self.layers.set_options(control, options);
+    }
+
+    fn bring_layer_to_front(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        self.layers.bring_to_front(control);
+    }
+}
+
+//
+// TestControl.
+//
+
+///
+/// Control placed at a fixed rect which records all the events it gets.
+///
+pub struct TestControl {
+    pub events: Vec<ControlEvent>,
+
+    /// Effect set when the dragged data enters the control.
+    pub drop_effect: Option<DropEffect>,
+}
+
+impl TestControl {
+    pub fn create(
+        rect: Rect,
+        attached_values: TypeMap,
+        children: Vec<Rc<RefCell<dyn ControlObject>>>,
+    ) -> Rc<RefCell<StyledControl<TestControl>>> {
+        TestControl::create_with_children_collection(rect, attached_values, Box::new(children))
+    }
+
+    pub fn create_with_children_collection(
+        rect: Rect,
+        attached_values: TypeMap,
+        children: Box<dyn ObservableCollection<Rc<RefCell<dyn ControlObject>>>>,
+    ) -> Rc<RefCell<StyledControl<TestControl>>> {
+        StyledControl::new(
+            TestControl {
+                events: Vec::new(),
+                drop_effect: None,
+            },
+            Box::new(TestControlStyle { rect }),
+            ViewContext {
+                attached_values,
+                children,
+            },
+        )
+    }
+}
+
+struct TestControlStyle {
+    rect: Rect,
+}
+
+impl Style<TestControl> for TestControlStyle {
+    fn setup(&mut self, _data: &mut TestControl, _control_context: &mut ControlContext) {}
+
+    fn handle_event(
+        &mut self,
+        data: &mut TestControl,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        if let (ControlEvent::DragEnter { .. }, Some(effect)) = (&event, data.drop_effect) {
+            event_context.set_drop_effect(effect);
+        }
+        data.events.push(event);
+    }
+
+    fn measure(
+        &mut self,
+        _data: &mut TestControl,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _size: Size,
+    ) {
+    }
+
+    fn set_rect(
+        &mut self,
+        _data: &mut TestControl,
+        _control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &TestControl,
+        control_context: &ControlContext,
+        point: Point,
+    ) -> HitTestResult {
+        if !point.is_inside(&self.rect) {
+            return HitTestResult::Nothing;
+        }
+
+        let children = control_context.get_children();
+        for child in children.into_iter() {
+            let child_hit_test = child.borrow().hit_test(point);
+            match child_hit_test {
+                HitTestResult::Current => return HitTestResult::Child(child.clone()),
+                HitTestResult::Child(..) => return child_hit_test,
+                HitTestResult::Nothing => (),
+            }
+        }
+        HitTestResult::Current
+    }
+
+    fn to_primitives(
+        &self,
+        _data: &TestControl,
+        _control_context: &ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        (Vec::new(), Vec::new())
+    }
+}
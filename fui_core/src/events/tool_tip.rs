@@ -0,0 +1,59 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::control::{ControlObject, StyledControl};
+use crate::view::{ViewModel, ViewModelObject};
+
+pub enum ToolTipContent {
+    Text(String),
+    Control(Rc<RefCell<dyn ControlObject>>),
+    ViewModel(Box<dyn ViewModelObject>),
+}
+
+impl From<&str> for ToolTipContent {
+    fn from(text: &str) -> Self {
+        ToolTipContent::Text(text.to_string())
+    }
+}
+
+impl From<String> for ToolTipContent {
+    fn from(text: String) -> Self {
+        ToolTipContent::Text(text)
+    }
+}
+
+impl From<Rc<RefCell<dyn ControlObject>>> for ToolTipContent {
+    fn from(control: Rc<RefCell<dyn ControlObject>>) -> Self {
+        ToolTipContent::Control(control)
+    }
+}
+
+impl<D: 'static> From<Rc<RefCell<StyledControl<D>>>> for ToolTipContent {
+    fn from(control: Rc<RefCell<StyledControl<D>>>) -> Self {
+        ToolTipContent::Control(control)
+    }
+}
+
+impl From<Box<dyn ViewModelObject>> for ToolTipContent {
+    fn from(view_model: Box<dyn ViewModelObject>) -> Self {
+        ToolTipContent::ViewModel(view_model)
+    }
+}
+
+impl<T: ViewModel + 'static> From<&Rc<RefCell<T>>> for ToolTipContent {
+    fn from(view_model: &Rc<RefCell<T>>) -> Self {
+        ToolTipContent::ViewModel(Box::new(view_model.clone()))
+    }
+}
+
+//
+// Attached values.
+//
+
+///
+/// Tool tip shown after the pointer rests over the control for a while.
+///
+pub struct ToolTip;
+impl typemap::Key for ToolTip {
+    type Value = ToolTipContent;
+}
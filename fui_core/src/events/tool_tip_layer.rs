@@ -0,0 +1,246 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use drawing::primitive::Primitive;
+use drawing::units::{PixelPoint, PixelRect, PixelSize};
+use euclid::Length;
+use typemap::TypeMap;
+
+use crate::common::*;
+use crate::control::*;
+use crate::events::*;
+use crate::style::*;
+use crate::view::ViewContext;
+use crate::DrawingContext;
+
+const TOOL_TIP_PADDING: f32 = 4.0f32;
+const TOOL_TIP_POINTER_OFFSET: f32 = 20.0f32;
+
+///
+/// Window layer showing the tool tip near the pointer.
+///
+/// The tool tip is moved to stay inside the window
+/// and the layer is transparent for hit testing.
+///
+pub struct ToolTipLayer {
+    pub position: Point,
+    text: Option<String>,
+}
+
+impl ToolTipLayer {
+    pub fn create(
+        content: &ToolTipContent,
+        position: Point,
+    ) -> Rc<RefCell<StyledControl<ToolTipLayer>>> {
+        let (text, children): (Option<String>, Vec<Rc<RefCell<dyn ControlObject>>>) =
+            match content {
+                ToolTipContent::Text(text) => (Some(text.clone()), Vec::new()),
+                ToolTipContent::Control(control) => (None, vec![control.clone()]),
+                ToolTipContent::ViewModel(view_model) => (None, vec![view_model.create_view()]),
+            };
+
+        StyledControl::new(
+            ToolTipLayer { position, text },
+            Box::new(ToolTipLayerStyle {
+                rect: Rect::new(0f32, 0f32, 0f32, 0f32),
+                tool_tip_rect: Rect::new(0f32, 0f32, 0f32, 0f32),
+                font_name: "OpenSans-Regular.ttf".to_string(),
+                font_size: 16u8,
+            }),
+            ViewContext {
+                attached_values: TypeMap::new(),
+                children: Box::new(children),
+            },
+        )
+    }
+}
+
+struct ToolTipLayerStyle {
+    rect: Rect,
+    tool_tip_rect: Rect,
+    font_name: String,
+    font_size: u8,
+}
+
+impl Style<ToolTipLayer> for ToolTipLayerStyle {
+    fn setup(&mut self, _data: &mut ToolTipLayer, _control_context: &mut ControlContext) {}
+
+    fn handle_event(
+        &mut self,
+        _data: &mut ToolTipLayer,
+        _control_context: &mut ControlContext,
+        _drawing_context: &mut dyn DrawingContext,
+        _event_context: &mut dyn EventContext,
+        _event: ControlEvent,
+    ) {
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut ToolTipLayer,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        let theme = control_context.get_theme();
+        self.font_name = theme.get_font("ToolTip");
+        self.font_size = theme.get_size("ToolTipFontSize") as u8;
+
+        let content_size = if let Some(ref text) = data.text {
+            let (text_width, text_height) = drawing_context
+                .get_resources()
+                .get_font_dimensions(&self.font_name, self.font_size, text)
+                .unwrap_or((0, 0));
+            Size::new(text_width as f32, text_height as f32)
+        } else {
+            let children = control_context.get_children();
+            if let Some(ref content) = children.into_iter().next() {
+                content.borrow_mut().measure(drawing_context, size);
+                let rect = content.borrow().get_rect();
+                Size::new(rect.width, rect.height)
+            } else {
+                Size::new(0f32, 0f32)
+            }
+        };
+
+        let width = content_size.width + TOOL_TIP_PADDING * 2.0f32;
+        let height = content_size.height + TOOL_TIP_PADDING * 2.0f32;
+
+        // keep the tool tip inside the window,
+        // show it above the pointer if there is no place below
+        let mut x = data.position.x;
+        if x + width > size.width {
+            x = (size.width - width).max(0f32);
+        }
+        let mut y = data.position.y + TOOL_TIP_POINTER_OFFSET;
+        if y + height > size.height {
+            y = (data.position.y - height).max(0f32);
+        }
+
+        self.tool_tip_rect = Rect::new(x, y, width, height);
+        self.rect = Rect::new(0f32, 0f32, size.width, size.height);
+    }
+
+    fn set_rect(
+        &mut self,
+        _data: &mut ToolTipLayer,
+        control_context: &mut ControlContext,
+        rect: Rect,
+    ) {
+        self.rect = rect;
+
+        let children = control_context.get_children();
+        if let Some(ref content) = children.into_iter().next() {
+            content.borrow_mut().set_rect(Rect::new(
+                self.tool_tip_rect.x + TOOL_TIP_PADDING,
+                self.tool_tip_rect.y + TOOL_TIP_PADDING,
+                self.tool_tip_rect.width - TOOL_TIP_PADDING * 2.0f32,
+                self.tool_tip_rect.height - TOOL_TIP_PADDING * 2.0f32,
+            ));
+        }
+    }
+
+    fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+        self.rect
+    }
+
+    fn hit_test(
+        &self,
+        _data: &ToolTipLayer,
+        _control_context: &ControlContext,
+        _point: Point,
+    ) -> HitTestResult {
+        HitTestResult::Nothing
+    }
+
+    fn to_primitives(
+        &self,
+        data: &ToolTipLayer,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        let mut vec = Vec::new();
+        let mut overlay = Vec::new();
+
+        let x = self.tool_tip_rect.x;
+        let y = self.tool_tip_rect.y;
+        let width = self.tool_tip_rect.width;
+        let height = self.tool_tip_rect.height;
+
+        let theme = control_context.get_theme();
+
+        vec.push(Primitive::Rectangle {
+            color: theme.get_color("ToolTipBackground"),
+            rect: PixelRect::new(PixelPoint::new(x, y), PixelSize::new(width, height)),
+        });
+
+        if let Some(ref text) = data.text {
+            vec.push(Primitive::Text {
+                resource_key: self.font_name.clone(),
+                color: theme.get_color("ToolTipForeground"),
+                position: PixelPoint::new(x + TOOL_TIP_PADDING, y + TOOL_TIP_PADDING),
+                clipping_rect: PixelRect::new(
+                    PixelPoint::new(x, y),
+                    PixelSize::new(width, height),
+                ),
+                size: Length::new(self.font_size as f32),
+                text: text.clone(),
+            });
+        } else {
+            let children = control_context.get_children();
+            if let Some(child) = children.into_iter().next() {
+                let (mut child_vec, mut child_overlay) =
+                    child.borrow().to_primitives(drawing_context);
+                vec.append(&mut child_vec);
+                overlay.append(&mut child_overlay);
+            }
+        }
+
+        (vec, overlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typemap::TypeMap;
+
+    use crate::common::*;
+    use crate::control::*;
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+
+    fn get_content_rect(position: Point) -> Rect {
+        let content =
+            TestControl::create(Rect::new(0.0, 0.0, 100.0, 20.0), TypeMap::new(), Vec::new());
+        let layer = ToolTipLayer::create(&ToolTipContent::from(content.clone()), position);
+
+        let mut drawing_context = TestDrawingContext;
+        layer
+            .borrow_mut()
+            .measure(&mut drawing_context, Size::new(800.0, 600.0));
+        layer
+            .borrow_mut()
+            .set_rect(Rect::new(0.0, 0.0, 800.0, 600.0));
+
+        let rect = content.borrow().get_rect();
+        rect
+    }
+
+    #[test]
+    fn test_tool_tip_is_shown_below_pointer() {
+        assert_eq!(
+            get_content_rect(Point::new(10.0, 10.0)),
+            Rect::new(14.0, 34.0, 100.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn test_tool_tip_is_kept_inside_window() {
+        // moved to the left from the right edge
+        // and shown above the pointer at the bottom edge
+        assert_eq!(
+            get_content_rect(Point::new(790.0, 590.0)),
+            Rect::new(696.0, 566.0, 100.0, 20.0)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::events::*;
+
+///
+/// Maps touch points of the primary finger (the first one touching the screen)
+/// to pointer events, so the controls handling only mouse input work on touchscreens.
+///
+/// Other fingers are ignored here, they are handled by gesture recognizers.
+///
+pub struct TouchEmulator {
+    primary_id: Option<u64>,
+}
+
+impl TouchEmulator {
+    pub fn new() -> Self {
+        TouchEmulator { primary_id: None }
+    }
+
+    pub fn handle_event(&mut self, event: &InputEvent) -> Vec<InputEvent> {
+        match event {
+            InputEvent::Touch {
+                phase: TouchPhase::Started,
+                id,
+                position,
+            } if self.primary_id.is_none() => {
+                self.primary_id = Some(*id);
+                vec![
+                    InputEvent::CursorMoved {
+                        position: *position,
+                    },
+                    InputEvent::MouseInput {
+                        state: ElementState::Pressed,
+                        button: MouseButton::Left,
+                    },
+                ]
+            }
+
+            InputEvent::Touch {
+                phase: TouchPhase::Moved,
+                id,
+                position,
+            } if self.primary_id == Some(*id) => vec![InputEvent::CursorMoved {
+                position: *position,
+            }],
+
+            InputEvent::Touch {
+                phase: TouchPhase::Ended,
+                id,
+                position,
+            }
+            | InputEvent::Touch {
+                phase: TouchPhase::Cancelled,
+                id,
+                position,
+            } if self.primary_id == Some(*id) => {
+                self.primary_id = None;
+                vec![
+                    InputEvent::CursorMoved {
+                        position: *position,
+                    },
+                    InputEvent::MouseInput {
+                        state: ElementState::Released,
+                        button: MouseButton::Left,
+                    },
+                ]
+            }
+
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Default for TouchEmulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::events::test_helpers::*;
+    use crate::events::*;
+
+    #[test]
+    fn test_primary_touch_is_mapped_to_pointer() {
+        let mut emulator = TouchEmulator::new();
+
+        assert_eq!(
+            emulator.handle_event(&touch(TouchPhase::Started, 1, 10.0, 20.0)),
+            vec![cursor_moved(10.0, 20.0), left_button(ElementState::Pressed)]
+        );
+        assert_eq!(
+            emulator.handle_event(&touch(TouchPhase::Moved, 1, 15.0, 25.0)),
+            vec![cursor_moved(15.0, 25.0)]
+        );
+        assert_eq!(
+            emulator.handle_event(&touch(TouchPhase::Ended, 1, 16.0, 26.0)),
+            vec![
+                cursor_moved(16.0, 26.0),
+                left_button(ElementState::Released)
+            ]
+        );
+
+        // the next touch becomes the primary one
+        assert_eq!(
+            emulator.handle_event(&touch(TouchPhase::Started, 2, 1.0, 2.0)),
+            vec![cursor_moved(1.0, 2.0), left_button(ElementState::Pressed)]
+        );
+    }
+
+    #[test]
+    fn test_secondary_touches_are_ignored() {
+        let mut emulator = TouchEmulator::new();
+
+        emulator.handle_event(&touch(TouchPhase::Started, 1, 0.0, 0.0));
+        assert!(emulator
+            .handle_event(&touch(TouchPhase::Started, 2, 50.0, 50.0))
+            .is_empty());
+        assert!(emulator
+            .handle_event(&touch(TouchPhase::Moved, 2, 60.0, 60.0))
+            .is_empty());
+        assert!(emulator
+            .handle_event(&touch(TouchPhase::Ended, 2, 60.0, 60.0))
+            .is_empty());
+
+        assert_eq!(
+            emulator.handle_event(&touch(TouchPhase::Cancelled, 1, 5.0, 5.0)),
+            vec![cursor_moved(5.0, 5.0), left_button(ElementState::Released)]
+        );
+    }
+
+    #[test]
+    fn test_mouse_events_are_not_mapped() {
+        let mut emulator = TouchEmulator::new();
+
+        assert!(emulator.handle_event(&cursor_moved(1.0, 1.0)).is_empty());
+        assert!(emulator
+            .handle_event(&left_button(ElementState::Pressed))
+            .is_empty());
+    }
+}
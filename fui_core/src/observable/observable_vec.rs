@@ -39,6 +39,23 @@ impl<T: 'static + Clone> ObservableVec<T> {
         self.changed_event.borrow().emit(event_args);
     }
 
+    pub fn insert(&mut self, index: usize, value: T) {
+        let event_args = ObservableChangedEventArgs::Insert {
+            index,
+            value: value.clone(),
+        };
+        self.items.insert(index, value);
+        self.changed_event.borrow().emit(event_args);
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.items.remove(index);
+        self.changed_event
+            .borrow()
+            .emit(ObservableChangedEventArgs::Remove { index });
+        value
+    }
+
     pub fn remove_filter<F>(&mut self, mut filter: F)
     where
         F: FnMut(&mut T) -> bool,
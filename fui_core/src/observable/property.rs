@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::common::Color;
 use crate::Event;
 use crate::EventSubscription;
 
@@ -180,6 +181,7 @@ impl IntoProperty for u128 {}
 impl IntoProperty for usize {}
 impl IntoProperty for f32 {}
 impl IntoProperty for f64 {}
+impl IntoProperty for Color {}
 
 ///
 /// Allows to convert types attributed with IntoProperty to Property<T>.
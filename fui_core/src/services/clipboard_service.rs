@@ -0,0 +1,142 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+///
+/// Clipboard holds the content in one or more formats at the same time
+/// (for example plain text and an application-defined rich format).
+///
+/// Setting the content in one format removes the content in all other formats.
+/// Use set_content() to put the content in many formats at once.
+///
+pub trait ClipboardService {
+    fn get_text(&self) -> Option<String>;
+    fn set_text(&mut self, text: &str);
+
+    fn get_data(&self, format: &str) -> Option<Rc<dyn Any>>;
+    fn set_data(&mut self, format: &str, data: Rc<dyn Any>);
+
+    ///
+    /// Replaces the content with the (optional) text
+    /// and the data in application-defined formats.
+    ///
+    fn set_content(&mut self, text: Option<&str>, data: Vec<(&str, Rc<dyn Any>)>);
+
+    fn has_format(&self, format: &str) -> bool;
+
+    fn clear(&mut self);
+}
+
+///
+/// In-memory clipboard, shared only inside the application.
+///
+pub struct MemoryClipboardService {
+    text: Option<String>,
+    data: HashMap<String, Rc<dyn Any>>,
+}
+
+impl MemoryClipboardService {
+    pub fn new() -> Self {
+        MemoryClipboardService {
+            text: None,
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl Default for MemoryClipboardService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardService for MemoryClipboardService {
+    fn get_text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.set_content(Some(text), Vec::new());
+    }
+
+    fn get_data(&self, format: &str) -> Option<Rc<dyn Any>> {
+        self.data.get(format).cloned()
+    }
+
+    fn set_data(&mut self, format: &str, data: Rc<dyn Any>) {
+        self.set_content(None, vec![(format, data)]);
+    }
+
+    fn set_content(&mut self, text: Option<&str>, data: Vec<(&str, Rc<dyn Any>)>) {
+        self.text = text.map(|text| text.to_string());
+        self.data = data
+            .into_iter()
+            .map(|(format, data)| (format.to_string(), data))
+            .collect();
+    }
+
+    fn has_format(&self, format: &str) -> bool {
+        self.data.contains_key(format)
+    }
+
+    fn clear(&mut self) {
+        self.set_content(None, Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        name: String,
+    }
+
+    #[test]
+    fn test_text() {
+        let mut clipboard = MemoryClipboardService::new();
+        assert_eq!(clipboard.get_text(), None);
+
+        clipboard.set_text("abc");
+        assert_eq!(clipboard.get_text(), Some("abc".to_string()));
+    }
+
+    #[test]
+    fn test_typed_data() {
+        let mut clipboard = MemoryClipboardService::new();
+        clipboard.set_data(
+            "person",
+            Rc::new(Person {
+                name: "John".to_string(),
+            }),
+        );
+
+        assert!(clipboard.has_format("person"));
+        let data = clipboard.get_data("person").unwrap();
+        assert_eq!(
+            data.downcast_ref::<Person>(),
+            Some(&Person {
+                name: "John".to_string()
+            })
+        );
+        assert!(clipboard.get_data("other").is_none());
+    }
+
+    #[test]
+    fn test_set_replaces_content() {
+        let mut clipboard = MemoryClipboardService::new();
+        clipboard.set_content(Some("John"), vec![("person", Rc::new(1i32))]);
+        assert_eq!(clipboard.get_text(), Some("John".to_string()));
+        assert!(clipboard.has_format("person"));
+
+        clipboard.set_text("abc");
+        assert!(!clipboard.has_format("person"));
+
+        clipboard.set_data("person", Rc::new(2i32));
+        assert_eq!(clipboard.get_text(), None);
+
+        clipboard.clear();
+        assert!(!clipboard.has_format("person"));
+    }
+}
@@ -0,0 +1,45 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CursorIcon {
+    Default,
+    Arrow,
+    Hand,
+    Text,
+    Wait,
+    Progress,
+    Crosshair,
+    Move,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+}
+
+//
+// Attached values.
+//
+
+///
+/// Cursor shown when the pointer is over the control (or its children).
+///
+pub struct Cursor;
+impl typemap::Key for Cursor {
+    type Value = CursorIcon;
+}
+
+pub trait CursorService {
+    ///
+    /// Sets the cursor resolved from the control under the pointer.
+    ///
+    fn set_cursor_icon(&mut self, cursor_icon: CursorIcon);
+
+    ///
+    /// Overrides the cursor for the whole window until it is reset with None
+    /// (for example to show busy cursor during long operation).
+    ///
+    fn set_override_cursor_icon(&mut self, cursor_icon: Option<CursorIcon>);
+}
@@ -1,5 +1,17 @@
+mod clipboard_service;
+pub use self::clipboard_service::*;
+
+mod cursor_service;
+pub use self::cursor_service::*;
+
 mod services;
 pub use self::services::*;
 
+mod theme_service;
+pub use self::theme_service::*;
+
+mod window_layers;
+pub use self::window_layers::*;
+
 mod window_service;
 pub use self::window_service::*;
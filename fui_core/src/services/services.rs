@@ -1,18 +1,196 @@
-use std::{cell::RefCell, rc::Rc, rc::Weak};
-use crate::WindowService;
+use crate::{ClipboardService, CursorService, StyleRegistry, ThemeService, WindowService};
+use anyhow::{anyhow, Result};
+use std::{cell::RefCell, marker::PhantomData, rc::Rc, rc::Weak};
+use typemap::TypeMap;
 
+///
+/// Type-keyed registry of services.
+///
+/// Services are registered by their (usually trait object) type
+/// and resolved with get::<dyn MyService>().
+///
+/// Every window has its own Services with the application's Services
+/// as a parent. Services not found in the window are looked up in the parent.
+///
 pub struct Services {
-    window_service: Weak<RefCell<dyn WindowService>>, 
+    services: TypeMap,
+    parent: Option<Rc<RefCell<Services>>>,
+}
+
+struct ServiceKey<T: ?Sized>(PhantomData<T>);
+impl<T: ?Sized + 'static> typemap::Key for ServiceKey<T> {
+    type Value = Rc<RefCell<T>>;
+}
+
+struct WeakServiceKey<T: ?Sized>(PhantomData<T>);
+impl<T: ?Sized + 'static> typemap::Key for WeakServiceKey<T> {
+    type Value = Weak<RefCell<T>>;
 }
 
 impl Services {
-    pub fn new(window_service: &Rc<RefCell<dyn WindowService>>) -> Self {
+    pub fn new() -> Self {
         Self {
-            window_service: Rc::downgrade(window_service)
+            services: TypeMap::new(),
+            parent: None,
+        }
+    }
+
+    pub fn with_parent(parent: &Rc<RefCell<Services>>) -> Self {
+        Self {
+            services: TypeMap::new(),
+            parent: Some(parent.clone()),
+        }
+    }
+
+    pub fn register<T: ?Sized + 'static>(&mut self, service: Rc<RefCell<T>>) {
+        self.services.insert::<ServiceKey<T>>(service);
+    }
+
+    ///
+    /// Registers the service without owning it.
+    ///
+    /// Used for services implemented by objects owning the services
+    /// (like windows), so they don't keep themselves alive.
+    /// The service is not found after it has been dropped.
+    ///
+    pub fn register_weak<T: ?Sized + 'static>(&mut self, service: &Rc<RefCell<T>>) {
+        self.services
+            .insert::<WeakServiceKey<T>>(Rc::downgrade(service));
+    }
+
+    pub fn unregister<T: ?Sized + 'static>(&mut self) {
+        self.services.remove::<ServiceKey<T>>();
+        self.services.remove::<WeakServiceKey<T>>();
+    }
+
+    pub fn get<T: ?Sized + 'static>(&self) -> Result<Rc<RefCell<T>>> {
+        if let Some(service) = self.services.get::<ServiceKey<T>>() {
+            return Ok(service.clone());
+        }
+
+        if let Some(service) = self
+            .services
+            .get::<WeakServiceKey<T>>()
+            .and_then(|service| service.upgrade())
+        {
+            return Ok(service);
+        }
+
+        match self.parent {
+            Some(ref parent) => parent.borrow().get::<T>(),
+            None => Err(anyhow!(
+                "Service {} is not registered.",
+                std::any::type_name::<T>()
+            )),
         }
     }
 
     pub fn get_window_service(&self) -> Option<Rc<RefCell<dyn WindowService>>> {
-        self.window_service.upgrade()
+        self.get::<dyn WindowService>().ok()
+    }
+
+    pub fn get_cursor_service(&self) -> Option<Rc<RefCell<dyn CursorService>>> {
+        self.get::<dyn CursorService>().ok()
+    }
+
+    pub fn get_clipboard_service(&self) -> Option<Rc<RefCell<dyn ClipboardService>>> {
+        self.get::<dyn ClipboardService>().ok()
+    }
+
+    pub fn get_style_registry(&self) -> Option<Rc<RefCell<StyleRegistry>>> {
+        self.get::<StyleRegistry>().ok()
+    }
+
+    pub fn get_theme_service(&self) -> Option<Rc<RefCell<ThemeService>>> {
+        self.get::<ThemeService>().ok()
+    }
+}
+
+impl Default for Services {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait Repository {
+        fn get_name(&self) -> &str;
+    }
+
+    struct NamedRepository(&'static str);
+    impl Repository for NamedRepository {
+        fn get_name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_missing_service_is_error() {
+        let services = Services::new();
+        assert!(services.get::<dyn Repository>().is_err());
+    }
+
+    #[test]
+    fn test_window_service_shadows_application_service() {
+        let application_services = Rc::new(RefCell::new(Services::new()));
+        application_services
+            .borrow_mut()
+            .register::<dyn Repository>(Rc::new(RefCell::new(NamedRepository("application"))));
+
+        let mut window_services = Services::with_parent(&application_services);
+        assert_eq!(
+            window_services
+                .get::<dyn Repository>()
+                .unwrap()
+                .borrow()
+                .get_name(),
+            "application"
+        );
+
+        window_services
+            .register::<dyn Repository>(Rc::new(RefCell::new(NamedRepository("window"))));
+        assert_eq!(
+            window_services
+                .get::<dyn Repository>()
+                .unwrap()
+                .borrow()
+                .get_name(),
+            "window"
+        );
+
+        window_services.unregister::<dyn Repository>();
+        assert_eq!(
+            window_services
+                .get::<dyn Repository>()
+                .unwrap()
+                .borrow()
+                .get_name(),
+            "application"
+        );
+    }
+
+    #[test]
+    fn test_weak_service_is_not_owned() {
+        let repository: Rc<RefCell<dyn Repository>> =
+            Rc::new(RefCell::new(NamedRepository("window")));
+
+        let mut services = Services::new();
+        services.register_weak::<dyn Repository>(&repository);
+        assert_eq!(
+            services
+                .get::<dyn Repository>()
+                .unwrap()
+                .borrow()
+                .get_name(),
+            "window"
+        );
+
+        let repository_weak = Rc::downgrade(&repository);
+        drop(repository);
+        assert!(repository_weak.upgrade().is_none());
+        assert!(services.get::<dyn Repository>().is_err());
     }
 }
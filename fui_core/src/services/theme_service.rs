@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{Event, EventSubscription, Theme, WindowService};
+
+///
+/// Holds the current theme.
+///
+/// Styles read the resources from the theme when drawing,
+/// so switching the theme restyles the controls
+/// without rebuilding the view models.
+///
+pub struct ThemeService {
+    theme: Rc<Theme>,
+    theme_changed: Event<Rc<Theme>>,
+}
+
+impl ThemeService {
+    pub fn new(theme: Theme) -> Self {
+        ThemeService {
+            theme: Rc::new(theme),
+            theme_changed: Event::new(),
+        }
+    }
+
+    pub fn get_theme(&self) -> Rc<Theme> {
+        self.theme.clone()
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Rc::new(theme);
+        self.theme_changed.emit(self.theme.clone());
+    }
+
+    pub fn on_theme_changed<F: 'static + Fn(Rc<Theme>)>(&mut self, f: F) -> EventSubscription {
+        self.theme_changed.subscribe(f)
+    }
+
+    ///
+    /// Marks all the window's layers dirty when the theme is switched,
+    /// so the window is laid out and redrawn with the new theme.
+    ///
+    pub fn dirty_watching(
+        &mut self,
+        window_service: &Rc<RefCell<dyn WindowService>>,
+    ) -> EventSubscription {
+        let window_service = Rc::downgrade(window_service);
+        self.on_theme_changed(move |_| {
+            if let Some(window_service) = window_service.upgrade() {
+                for layer in window_service.borrow().get_layers() {
+                    layer.borrow_mut().get_context_mut().set_is_dirty(true);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typemap::TypeMap;
+
+    use super::*;
+    use crate::common::Rect;
+    use crate::events::test_helpers::*;
+    use crate::observable::CallbackExecutor;
+    use crate::ControlObject;
+
+    #[test]
+    fn test_switching_theme_marks_window_dirty() {
+        let main_layer: Rc<RefCell<dyn ControlObject>> = TestControl::create(
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            TypeMap::new(),
+            Vec::new(),
+        );
+        let (window_service, _services) = TestWindowService::create(main_layer.clone());
+        let window_service: Rc<RefCell<dyn WindowService>> = window_service;
+
+        let mut theme_service = ThemeService::new(Theme::dark());
+        let subscription = theme_service.dirty_watching(&window_service);
+
+        main_layer
+            .borrow_mut()
+            .get_context_mut()
+            .set_is_dirty(false);
+        theme_service.set_theme(Theme::light());
+        CallbackExecutor::execute_all_in_queue();
+        assert!(main_layer.borrow().get_context().is_dirty());
+        assert_eq!(theme_service.get_theme().get_name(), "Light");
+
+        drop(subscription);
+        main_layer
+            .borrow_mut()
+            .get_context_mut()
+            .set_is_dirty(false);
+        theme_service.set_theme(Theme::high_contrast());
+        CallbackExecutor::execute_all_in_queue();
+        assert!(!main_layer.borrow().get_context().is_dirty());
+    }
+}
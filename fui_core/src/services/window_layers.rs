@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{ControlObject, LayerOptions};
+
+///
+/// Window's layers ordered by z-index.
+///
+/// It can be used by the windows to implement layer part of WindowService.
+///
+pub struct WindowLayers {
+    layers: Vec<(Rc<RefCell<dyn ControlObject>>, LayerOptions)>,
+}
+
+impl WindowLayers {
+    pub fn new() -> Self {
+        WindowLayers { layers: Vec::new() }
+    }
+
+    pub fn add(&mut self, control: Rc<RefCell<dyn ControlObject>>, options: LayerOptions) {
+        let index = self
+            .layers
+            .iter()
+            .position(|(_, layer_options)| layer_options.z_index > options.z_index)
+            .unwrap_or(self.layers.len());
+        self.layers.insert(index, (control, options));
+    }
+
+    pub fn remove(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        self.layers.retain(|(layer, _)| !Rc::ptr_eq(layer, control));
+    }
+
+    ///
+    /// Returns layers in the drawing order (from the bottom one).
+    ///
+    pub fn get_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>> {
+        self.layers.iter().map(|(layer, _)| layer.clone()).collect()
+    }
+
+    pub fn get_options(&self, control: &Rc<RefCell<dyn ControlObject>>) -> Option<LayerOptions> {
+        self.layers
+            .iter()
+            .find(|(layer, _)| Rc::ptr_eq(layer, control))
+            .map(|(_, options)| *options)
+    }
+
+    pub fn set_options(&mut self, control: &Rc<RefCell<dyn ControlObject>>, options: LayerOptions) {
+        if let Some(old_options) = self.get_options(control) {
+            if old_options.z_index == options.z_index {
+                for (layer, layer_options) in self.layers.iter_mut() {
+                    if Rc::ptr_eq(layer, control) {
+                        *layer_options = options;
+                    }
+                }
+            } else {
+                self.remove(control);
+                self.add(control.clone(), options);
+            }
+        }
+    }
+
+    pub fn bring_to_front(&mut self, control: &Rc<RefCell<dyn ControlObject>>) {
+        if let Some(options) = self.get_options(control) {
+            self.remove(control);
+            self.add(control.clone(), options);
+        }
+    }
+
+    ///
+    /// Returns layers which can get the input, from the top one
+    /// down to the first modal layer.
+    ///
+    pub fn get_input_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>> {
+        let mut input_layers = Vec::new();
+        for (layer, options) in self.layers.iter().rev() {
+            input_layers.push(layer.clone());
+            if options.is_modal {
+                break;
+            }
+        }
+        input_layers
+    }
+}
+
+impl Default for WindowLayers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Point;
+    use crate::events::{ToolTipContent, ToolTipLayer};
+
+    fn create_layer(text: &str) -> Rc<RefCell<dyn ControlObject>> {
+        ToolTipLayer::create(&ToolTipContent::from(text), Point::new(0f32, 0f32))
+    }
+
+    fn is_same(
+        layers: &[Rc<RefCell<dyn ControlObject>>],
+        expected: &[&Rc<RefCell<dyn ControlObject>>],
+    ) -> bool {
+        layers.len() == expected.len()
+            && layers
+                .iter()
+                .zip(expected.iter())
+                .all(|(layer, expected)| Rc::ptr_eq(layer, expected))
+    }
+
+    #[test]
+    fn test_layers_are_ordered_by_z_index() {
+        let main = create_layer("main");
+        let overlay = create_layer("overlay");
+        let toolbar = create_layer("toolbar");
+        let popup = create_layer("popup");
+
+        let mut layers = WindowLayers::new();
+        layers.add(main.clone(), LayerOptions::modal());
+        layers.add(overlay.clone(), LayerOptions::overlay());
+        layers.add(toolbar.clone(), LayerOptions::non_modal().with_z_index(1));
+        layers.add(popup.clone(), LayerOptions::modal());
+        assert!(is_same(
+            &layers.get_layers(),
+            &[&main, &popup, &toolbar, &overlay]
+        ));
+
+        layers.bring_to_front(&main);
+        assert!(is_same(
+            &layers.get_layers(),
+            &[&popup, &main, &toolbar, &overlay]
+        ));
+
+        layers.set_options(&popup, LayerOptions::modal().with_z_index(2));
+        assert!(is_same(
+            &layers.get_layers(),
+            &[&main, &toolbar, &popup, &overlay]
+        ));
+
+        layers.remove(&toolbar);
+        assert!(is_same(&layers.get_layers(), &[&main, &popup, &overlay]));
+    }
+
+    #[test]
+    fn test_input_layers_end_at_first_modal_layer() {
+        let main = create_layer("main");
+        let dialog = create_layer("dialog");
+        let toolbar = create_layer("toolbar");
+        let overlay = create_layer("overlay");
+
+        let mut layers = WindowLayers::new();
+        layers.add(main.clone(), LayerOptions::modal());
+        layers.add(toolbar.clone(), LayerOptions::non_modal());
+        layers.add(overlay.clone(), LayerOptions::overlay());
+        assert!(is_same(
+            &layers.get_input_layers(),
+            &[&overlay, &toolbar, &main]
+        ));
+
+        layers.add(dialog.clone(), LayerOptions::modal());
+        assert!(is_same(&layers.get_input_layers(), &[&overlay, &dialog]));
+    }
+}
@@ -1,7 +1,84 @@
 use std::{cell::RefCell, rc::Rc};
 use crate::ControlObject;
 
+///
+/// Options of the window's layer.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerOptions {
+    ///
+    /// Modal layer blocks the input to the layers below.
+    /// Hits not handled by non-modal layers (like floating toolbars,
+    /// toasts or tool tips) go through to the layers below.
+    ///
+    pub is_modal: bool,
+
+    ///
+    /// Layers with higher z-index are placed above.
+    /// Layers with the same z-index are placed in the order they were added.
+    ///
+    pub z_index: i32,
+}
+
+impl LayerOptions {
+    pub fn modal() -> Self {
+        LayerOptions {
+            is_modal: true,
+            z_index: 0,
+        }
+    }
+
+    pub fn non_modal() -> Self {
+        LayerOptions {
+            is_modal: false,
+            z_index: 0,
+        }
+    }
+
+    ///
+    /// Non-modal layer placed above all the other layers
+    /// (used for tool tips and drag visuals).
+    ///
+    pub fn overlay() -> Self {
+        LayerOptions {
+            is_modal: false,
+            z_index: i32::MAX,
+        }
+    }
+
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+impl Default for LayerOptions {
+    fn default() -> Self {
+        LayerOptions::modal()
+    }
+}
+
 pub trait WindowService {
+    ///
+    /// Adds modal layer on top of the layers with zero z-index.
+    ///
     fn add_layer(&mut self, control: Rc<RefCell<dyn ControlObject>>);
+    fn add_layer_with_options(
+        &mut self,
+        control: Rc<RefCell<dyn ControlObject>>,
+        options: LayerOptions,
+    );
     fn remove_layer(&mut self, control: &Rc<RefCell<dyn ControlObject>>);
+
+    ///
+    /// Returns layers in the drawing order (from the bottom one).
+    ///
+    fn get_layers(&self) -> Vec<Rc<RefCell<dyn ControlObject>>>;
+    fn get_layer_options(&self, control: &Rc<RefCell<dyn ControlObject>>) -> Option<LayerOptions>;
+    fn set_layer_options(&mut self, control: &Rc<RefCell<dyn ControlObject>>, options: LayerOptions);
+
+    ///
+    /// Moves the layer above the other layers with the same z-index.
+    ///
+    fn bring_layer_to_front(&mut self, control: &Rc<RefCell<dyn ControlObject>>);
 }
@@ -1,2 +1,11 @@
+mod registered_style;
+pub use self::registered_style::*;
+
 mod style;
 pub use self::style::*;
+
+mod style_registry;
+pub use self::style_registry::*;
+
+mod theme;
+pub use self::theme::*;
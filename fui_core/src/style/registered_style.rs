@@ -0,0 +1,124 @@
+use drawing::primitive::Primitive;
+
+use crate::common::*;
+use crate::control::*;
+use crate::events::ControlEvent;
+use crate::services::CursorIcon;
+use crate::style::*;
+use crate::{DrawingContext, EventContext};
+
+///
+/// Style looked up in the style registries.
+///
+/// Controls use it to wrap their default style. When the control
+/// is measured the first time (it is added to the window then),
+/// the style selected by StyleName attached value (or the implicit style
+/// of the control type) replaces the default one.
+///
+/// The default style is kept when there is no such style registered.
+///
+pub struct RegisteredStyle<D> {
+    style: Box<dyn Style<D>>,
+    is_resolved: bool,
+}
+
+impl<D: 'static> RegisteredStyle<D> {
+    pub fn with_default<S: 'static + Style<D>>(default_style: S) -> Box<dyn Style<D>> {
+        Box::new(RegisteredStyle {
+            style: Box::new(default_style),
+            is_resolved: false,
+        })
+    }
+
+    fn resolve(&mut self, data: &mut D, control_context: &mut ControlContext) {
+        if self.is_resolved {
+            return;
+        }
+
+        let services = match control_context
+            .get_services()
+            .and_then(|services| services.upgrade())
+        {
+            Some(services) => services,
+            None => return,
+        };
+        self.is_resolved = true;
+
+        let mut registries = control_context.get_style_registries();
+        if let Some(registry) = services.borrow().get_style_registry() {
+            registries.push(registry);
+        }
+
+        let name = control_context
+            .get_attached_values()
+            .get::<StyleName>()
+            .cloned();
+
+        if let Some(mut style) = StyleRegistry::resolve::<D>(&registries, name.as_deref()) {
+            style.setup(data, control_context);
+            self.style = style;
+        }
+    }
+}
+
+impl<D: 'static> Style<D> for RegisteredStyle<D> {
+    fn setup(&mut self, data: &mut D, control_context: &mut ControlContext) {
+        self.style.setup(data, control_context);
+    }
+
+    fn handle_event(
+        &mut self,
+        data: &mut D,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        event_context: &mut dyn EventContext,
+        event: ControlEvent,
+    ) {
+        self.resolve(data, control_context);
+        self.style
+            .handle_event(data, control_context, drawing_context, event_context, event);
+    }
+
+    fn measure(
+        &mut self,
+        data: &mut D,
+        control_context: &mut ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+        size: Size,
+    ) {
+        self.resolve(data, control_context);
+        self.style
+            .measure(data, control_context, drawing_context, size);
+    }
+
+    fn set_rect(&mut self, data: &mut D, control_context: &mut ControlContext, rect: Rect) {
+        self.style.set_rect(data, control_context, rect);
+    }
+
+    fn get_rect(&self, control_context: &ControlContext) -> Rect {
+        self.style.get_rect(control_context)
+    }
+
+    fn hit_test(&self, data: &D, control_context: &ControlContext, point: Point) -> HitTestResult {
+        self.style.hit_test(data, control_context, point)
+    }
+
+    fn get_cursor_icon(
+        &self,
+        data: &D,
+        control_context: &ControlContext,
+        point: Point,
+    ) -> Option<CursorIcon> {
+        self.style.get_cursor_icon(data, control_context, point)
+    }
+
+    fn to_primitives(
+        &self,
+        data: &D,
+        control_context: &ControlContext,
+        drawing_context: &mut dyn DrawingContext,
+    ) -> (Vec<Primitive>, Vec<Primitive>) {
+        self.style
+            .to_primitives(data, control_context, drawing_context)
+    }
+}
@@ -5,6 +5,7 @@ use drawing::primitive::Primitive;
 use crate::common::*;
 use crate::control::*;
 use crate::events::ControlEvent;
+use crate::services::CursorIcon;
 use crate::{DrawingContext, EventContext};
 
 pub trait Style<D> {
@@ -31,6 +32,20 @@ pub trait Style<D> {
 
     fn hit_test(&self, data: &D, control_context: &ControlContext, point: Point) -> HitTestResult;
 
+    ///
+    /// Returns the cursor shown when the pointer is at the point over the control
+    /// (like a hand over the link). When None, the Cursor attached value
+    /// of the control or its parents is used.
+    ///
+    fn get_cursor_icon(
+        &self,
+        _data: &D,
+        _control_context: &ControlContext,
+        _point: Point,
+    ) -> Option<CursorIcon> {
+        None
+    }
+
     fn to_primitives(
         &self,
         data: &D,
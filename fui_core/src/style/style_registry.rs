@@ -0,0 +1,338 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+
+use crate::Style;
+
+///
+/// Attached value selecting the named style from the style registry.
+///
+/// Set by the `Style: "Name"` syntax of the ui! macro.
+///
+pub struct StyleName;
+impl typemap::Key for StyleName {
+    type Value = String;
+}
+
+///
+/// Attached value with the style registry for the subtree.
+///
+/// Styles not found in it are looked up in the registries of the parents
+/// and then in the StyleRegistry service.
+///
+pub struct Styles;
+impl typemap::Key for Styles {
+    type Value = Rc<RefCell<StyleRegistry>>;
+}
+
+struct StyleEntry<D> {
+    create: Rc<dyn Fn() -> Box<dyn Style<D>>>,
+    params: Option<Rc<dyn Any>>,
+}
+
+struct StyleParamsEntry<D, P> {
+    params: P,
+    create: Rc<dyn Fn(P) -> Box<dyn Style<D>>>,
+}
+
+///
+/// Registry of named styles.
+///
+/// Styles are registered per control type (the data type of the control),
+/// so the same name (like "Primary") can be used for different controls.
+/// One of the styles can be marked as an implicit one - it is used
+/// by all the controls of that type without style set.
+///
+pub struct StyleRegistry {
+    styles: HashMap<(TypeId, String), Box<dyn Any>>,
+    implicit_styles: HashMap<TypeId, String>,
+}
+
+impl StyleRegistry {
+    pub fn new() -> Self {
+        StyleRegistry {
+            styles: HashMap::new(),
+            implicit_styles: HashMap::new(),
+        }
+    }
+
+    pub fn register<D: 'static, F: 'static + Fn() -> Box<dyn Style<D>>>(
+        &mut self,
+        name: &str,
+        create: F,
+    ) {
+        self.insert(
+            name,
+            StyleEntry::<D> {
+                create: Rc::new(create),
+                params: None,
+            },
+        );
+    }
+
+    ///
+    /// Registers style created from params.
+    /// Styles registered this way can be used as a base of other styles.
+    ///
+    pub fn register_with_params<
+        D: 'static,
+        P: 'static + Clone,
+        F: 'static + Fn(P) -> Box<dyn Style<D>>,
+    >(
+        &mut self,
+        name: &str,
+        params: P,
+        create: F,
+    ) {
+        self.insert_params(
+            name,
+            StyleParamsEntry {
+                params,
+                create: Rc::new(create),
+            },
+        );
+    }
+
+    ///
+    /// Registers style which params are the params of the base style
+    /// modified by the `derive` function.
+    ///
+    /// The base style has to be registered (in this registry) with params of type P.
+    ///
+    pub fn register_based_on<D: 'static, P: 'static + Clone, F: FnOnce(P) -> P>(
+        &mut self,
+        name: &str,
+        base_name: &str,
+        derive: F,
+    ) -> Result<()> {
+        let base_params = self
+            .styles
+            .get(&(TypeId::of::<D>(), base_name.to_string()))
+            .and_then(|entry| entry.downcast_ref::<StyleEntry<D>>())
+            .and_then(|entry| entry.params.clone())
+            .ok_or_else(|| anyhow!("Style {} is not registered with params.", base_name))?;
+        let base_params = base_params
+            .downcast_ref::<StyleParamsEntry<D, P>>()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Style {} params are not of type {}.",
+                    base_name,
+                    std::any::type_name::<P>()
+                )
+            })?;
+
+        let params_entry = StyleParamsEntry {
+            params: derive(base_params.params.clone()),
+            create: base_params.create.clone(),
+        };
+        self.insert_params(name, params_entry);
+        Ok(())
+    }
+
+    ///
+    /// Sets the named style as a default one for the controls of type D.
+    ///
+    pub fn set_implicit<D: 'static>(&mut self, name: &str) {
+        self.implicit_styles
+            .insert(TypeId::of::<D>(), name.to_string());
+    }
+
+    pub fn create_style<D: 'static>(&self, name: &str) -> Option<Box<dyn Style<D>>> {
+        self.styles
+            .get(&(TypeId::of::<D>(), name.to_string()))
+            .and_then(|entry| entry.downcast_ref::<StyleEntry<D>>())
+            .map(|entry| (entry.create)())
+    }
+
+    ///
+    /// Creates the style from the first registry (starting from the innermost one)
+    /// that has it. When the name is not provided, the implicit style is used.
+    ///
+    pub fn resolve<D: 'static>(
+        registries: &[Rc<RefCell<StyleRegistry>>],
+        name: Option<&str>,
+    ) -> Option<Box<dyn Style<D>>> {
+        let (registries, name) = match name {
+            Some(name) => (registries, name.to_string()),
+            None => {
+                let implicit = registries.iter().enumerate().find_map(|(index, registry)| {
+                    registry
+                        .borrow()
+                        .implicit_styles
+                        .get(&TypeId::of::<D>())
+                        .map(|name| (index, name.clone()))
+                });
+                match implicit {
+                    Some((index, name)) => (&registries[index..], name),
+                    None => return None,
+                }
+            }
+        };
+
+        registries
+            .iter()
+            .find_map(|registry| registry.borrow().create_style::<D>(&name))
+    }
+
+    fn insert<D: 'static>(&mut self, name: &str, entry: StyleEntry<D>) {
+        self.styles
+            .insert((TypeId::of::<D>(), name.to_string()), Box::new(entry));
+    }
+
+    fn insert_params<D: 'static, P: 'static + Clone>(
+        &mut self,
+        name: &str,
+        params_entry: StyleParamsEntry<D, P>,
+    ) {
+        let params_entry = Rc::new(params_entry);
+        let params_entry_clone = params_entry.clone();
+        self.insert(
+            name,
+            StyleEntry::<D> {
+                create: Rc::new(move || {
+                    (params_entry_clone.create)(params_entry_clone.params.clone())
+                }),
+                params: Some(params_entry),
+            },
+        );
+    }
+}
+
+impl Default for StyleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::control::{ControlContext, HitTestResult};
+    use crate::events::ControlEvent;
+    use crate::view::ViewContext;
+    use crate::{DrawingContext, EventContext};
+    use drawing::primitive::Primitive;
+
+    struct Label;
+
+    #[derive(Clone)]
+    struct WidthStyleParams {
+        width: f32,
+    }
+
+    struct WidthStyle {
+        params: WidthStyleParams,
+    }
+
+    impl Style<Label> for WidthStyle {
+        fn setup(&mut self, _data: &mut Label, _control_context: &mut ControlContext) {}
+
+        fn handle_event(
+            &mut self,
+            _data: &mut Label,
+            _control_context: &mut ControlContext,
+            _drawing_context: &mut dyn DrawingContext,
+            _event_context: &mut dyn EventContext,
+            _event: ControlEvent,
+        ) {
+        }
+
+        fn measure(
+            &mut self,
+            _data: &mut Label,
+            _control_context: &mut ControlContext,
+            _drawing_context: &mut dyn DrawingContext,
+            _size: Size,
+        ) {
+        }
+
+        fn set_rect(&mut self, _data: &mut Label, _control_context: &mut ControlContext, _rect: Rect) {}
+
+        fn get_rect(&self, _control_context: &ControlContext) -> Rect {
+            Rect::new(0f32, 0f32, self.params.width, 0f32)
+        }
+
+        fn hit_test(&self, _data: &Label, _control_context: &ControlContext, _point: Point) -> HitTestResult {
+            HitTestResult::Nothing
+        }
+
+        fn to_primitives(
+            &self,
+            _data: &Label,
+            _control_context: &ControlContext,
+            _drawing_context: &mut dyn DrawingContext,
+        ) -> (Vec<Primitive>, Vec<Primitive>) {
+            (Vec::new(), Vec::new())
+        }
+    }
+
+    fn create_registry() -> Rc<RefCell<StyleRegistry>> {
+        Rc::new(RefCell::new(StyleRegistry::new()))
+    }
+
+    fn get_width(style: Option<Box<dyn Style<Label>>>) -> Option<f32> {
+        let control_context = ControlContext::new(ViewContext::empty());
+        style.map(|style| style.get_rect(&control_context).width)
+    }
+
+    #[test]
+    fn test_style_based_on_params_of_base_style() {
+        let mut registry = StyleRegistry::new();
+        registry.register_with_params(
+            "Base",
+            WidthStyleParams { width: 10f32 },
+            |params| Box::new(WidthStyle { params }) as Box<dyn Style<Label>>,
+        );
+        registry
+            .register_based_on::<Label, WidthStyleParams, _>("Wide", "Base", |params| {
+                WidthStyleParams {
+                    width: params.width * 2f32,
+                }
+            })
+            .unwrap();
+
+        assert_eq!(get_width(registry.create_style::<Label>("Base")), Some(10f32));
+        assert_eq!(get_width(registry.create_style::<Label>("Wide")), Some(20f32));
+        assert_eq!(get_width(registry.create_style::<Label>("Missing")), None);
+        assert!(registry
+            .register_based_on::<Label, WidthStyleParams, _>("Other", "Missing", |params| params)
+            .is_err());
+    }
+
+    #[test]
+    fn test_inner_registry_overrides_outer_one() {
+        let application = create_registry();
+        application.borrow_mut().register("Normal", || {
+            Box::new(WidthStyle {
+                params: WidthStyleParams { width: 1f32 },
+            }) as Box<dyn Style<Label>>
+        });
+        application.borrow_mut().register("Large", || {
+            Box::new(WidthStyle {
+                params: WidthStyleParams { width: 2f32 },
+            }) as Box<dyn Style<Label>>
+        });
+        application.borrow_mut().set_implicit::<Label>("Normal");
+
+        let subtree = create_registry();
+        subtree.borrow_mut().set_implicit::<Label>("Large");
+
+        let registries = vec![application.clone()];
+        assert_eq!(get_width(StyleRegistry::resolve(&registries, None)), Some(1f32));
+
+        let registries = vec![subtree.clone(), application.clone()];
+        assert_eq!(get_width(StyleRegistry::resolve(&registries, None)), Some(2f32));
+        assert_eq!(
+            get_width(StyleRegistry::resolve(&registries, Some("Normal"))),
+            Some(1f32)
+        );
+        assert_eq!(
+            get_width(StyleRegistry::resolve(&registries, Some("Missing"))),
+            None
+        );
+    }
+}
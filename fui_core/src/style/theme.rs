@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::common::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThemeBrush {
+    Color(Color),
+    LinearGradient { start_color: Color, end_color: Color },
+}
+
+///
+/// Resource dictionary of named colors, brushes, fonts, sizes
+/// and corner radii used by the styles.
+///
+/// Missing resources are taken from the fallback theme (dark one),
+/// so custom themes can override only some of them.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    name: String,
+    colors: HashMap<String, Color>,
+    brushes: HashMap<String, ThemeBrush>,
+    fonts: HashMap<String, String>,
+    sizes: HashMap<String, f32>,
+    corner_radii: HashMap<String, f32>,
+}
+
+thread_local! {
+    static DEFAULT_THEME: Rc<Theme> = Rc::new(Theme::dark());
+}
+
+impl Theme {
+    pub fn new(name: &str) -> Self {
+        Theme {
+            name: name.to_string(),
+            colors: HashMap::new(),
+            brushes: HashMap::new(),
+            fonts: HashMap::new(),
+            sizes: HashMap::new(),
+            corner_radii: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Returns shared instance of the default (dark) theme.
+    ///
+    pub fn get_default() -> Rc<Theme> {
+        DEFAULT_THEME.with(|theme| theme.clone())
+    }
+
+    pub fn dark() -> Self {
+        Theme::new("Dark")
+            .with_color("WindowBackground", [0.3, 0.4, 0.3, 1.0])
+            .with_color("Foreground", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderLight1", [0.65, 0.65, 0.65, 1.0])
+            .with_color("BorderLight2", [0.35, 0.35, 0.35, 1.0])
+            .with_color("BorderMedium1", [0.15, 0.15, 0.15, 1.0])
+            .with_color("BorderMedium2", [0.12, 0.12, 0.12, 1.0])
+            .with_color("BorderDark", [0.0, 0.0, 0.0, 1.0])
+            .with_color("EditBorder", [0.4, 0.4, 0.4, 1.0])
+            .with_color("EditForeground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("EditPlaceholder", [0.45, 0.45, 0.45, 1.0])
+            .with_color("Caret", [1.0, 1.0, 0.0, 1.0])
+            .with_color("Link", [0.55, 0.75, 1.0, 1.0])
+            .with_color("SelectionBackground", [0.2, 0.45, 0.8, 1.0])
+            .with_color("SelectionForeground", [1.0, 1.0, 1.0, 1.0])
+            .with_color("CheckMark", [1.0, 1.0, 1.0, 0.8])
+            .with_color("Shadow", [0.0, 0.0, 0.0, 0.35])
+            .with_color("TrackBackground", [0.1, 0.5, 0.0, 0.2])
+            .with_color("ProgressForeground", [1.0, 1.0, 0.0, 0.7])
+            .with_color("ModalDim", [0.0, 0.0, 0.0, 0.5])
+            .with_color("DialogBackground", [0.2, 0.2, 0.2, 1.0])
+            .with_color("ToolTipBackground", [0.0, 0.0, 0.0, 0.8])
+            .with_color("ToolTipForeground", [1.0, 1.0, 1.0, 1.0])
+            .with_brush(
+                "ButtonBackground",
+                ThemeBrush::LinearGradient {
+                    start_color: [0.35, 0.35, 0.35, 1.0],
+                    end_color: [0.28, 0.28, 0.28, 1.0],
+                },
+            )
+            .with_brush(
+                "EditBackground",
+                ThemeBrush::LinearGradient {
+                    start_color: [1.0, 1.0, 1.0, 0.6],
+                    end_color: [0.9, 0.9, 0.9, 0.6],
+                },
+            )
+            .with_brush(
+                "EditBackgroundFocused",
+                ThemeBrush::LinearGradient {
+                    start_color: [1.0, 1.0, 1.0, 0.75],
+                    end_color: [0.9, 0.9, 0.9, 0.75],
+                },
+            )
+            .with_font("Default", "OpenSans-Regular.ttf")
+            .with_font("ToolTip", "OpenSans-Regular.ttf")
+            .with_size("FontSize", 20.0)
+            .with_size("ToolTipFontSize", 16.0)
+            .with_corner_radius("Button", 3.0)
+    }
+
+    pub fn light() -> Self {
+        Theme::new("Light")
+            .with_color("WindowBackground", [0.92, 0.92, 0.92, 1.0])
+            .with_color("Foreground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("BorderLight1", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderLight2", [0.9, 0.9, 0.9, 1.0])
+            .with_color("BorderMedium1", [0.6, 0.6, 0.6, 1.0])
+            .with_color("BorderMedium2", [0.5, 0.5, 0.5, 1.0])
+            .with_color("BorderDark", [0.35, 0.35, 0.35, 1.0])
+            .with_color("EditBorder", [0.7, 0.7, 0.7, 1.0])
+            .with_color("EditForeground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("EditPlaceholder", [0.55, 0.55, 0.55, 1.0])
+            .with_color("Caret", [0.0, 0.0, 0.0, 1.0])
+            .with_color("Link", [0.0, 0.35, 0.8, 1.0])
+            .with_color("SelectionBackground", [0.0, 0.47, 0.84, 1.0])
+            .with_color("SelectionForeground", [1.0, 1.0, 1.0, 1.0])
+            .with_color("CheckMark", [0.0, 0.0, 0.0, 0.8])
+            .with_color("Shadow", [0.0, 0.0, 0.0, 0.2])
+            .with_color("TrackBackground", [0.0, 0.3, 0.6, 0.15])
+            .with_color("ProgressForeground", [0.0, 0.45, 0.85, 0.8])
+            .with_color("ModalDim", [0.0, 0.0, 0.0, 0.3])
+            .with_color("DialogBackground", [0.97, 0.97, 0.97, 1.0])
+            .with_color("ToolTipBackground", [1.0, 1.0, 0.88, 0.95])
+            .with_color("ToolTipForeground", [0.0, 0.0, 0.0, 1.0])
+            .with_brush(
+                "ButtonBackground",
+                ThemeBrush::LinearGradient {
+                    start_color: [0.98, 0.98, 0.98, 1.0],
+                    end_color: [0.86, 0.86, 0.86, 1.0],
+                },
+            )
+            .with_brush(
+                "EditBackground",
+                ThemeBrush::Color([1.0, 1.0, 1.0, 1.0]),
+            )
+            .with_brush(
+                "EditBackgroundFocused",
+                ThemeBrush::Color([1.0, 1.0, 1.0, 1.0]),
+            )
+            .with_font("Default", "OpenSans-Regular.ttf")
+            .with_font("ToolTip", "OpenSans-Regular.ttf")
+            .with_size("FontSize", 20.0)
+            .with_size("ToolTipFontSize", 16.0)
+            .with_corner_radius("Button", 3.0)
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme::new("HighContrast")
+            .with_color("WindowBackground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("Foreground", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderLight1", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderLight2", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderMedium1", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderMedium2", [1.0, 1.0, 1.0, 1.0])
+            .with_color("BorderDark", [1.0, 1.0, 1.0, 1.0])
+            .with_color("EditBorder", [1.0, 1.0, 1.0, 1.0])
+            .with_color("EditForeground", [1.0, 1.0, 1.0, 1.0])
+            .with_color("EditPlaceholder", [0.75, 0.75, 0.75, 1.0])
+            .with_color("Caret", [1.0, 1.0, 0.0, 1.0])
+            .with_color("Link", [0.0, 1.0, 1.0, 1.0])
+            .with_color("SelectionBackground", [1.0, 1.0, 0.0, 1.0])
+            .with_color("SelectionForeground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("CheckMark", [1.0, 1.0, 0.0, 1.0])
+            .with_color("Shadow", [0.0, 0.0, 0.0, 0.0])
+            .with_color("TrackBackground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("ProgressForeground", [1.0, 1.0, 0.0, 1.0])
+            .with_color("ModalDim", [0.0, 0.0, 0.0, 0.7])
+            .with_color("DialogBackground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("ToolTipBackground", [0.0, 0.0, 0.0, 1.0])
+            .with_color("ToolTipForeground", [1.0, 1.0, 0.0, 1.0])
+            .with_brush(
+                "ButtonBackground",
+                ThemeBrush::Color([0.0, 0.0, 0.0, 1.0]),
+            )
+            .with_brush("EditBackground", ThemeBrush::Color([0.0, 0.0, 0.0, 1.0]))
+            .with_brush(
+                "EditBackgroundFocused",
+                ThemeBrush::Color([0.0, 0.0, 0.3, 1.0]),
+            )
+            .with_font("Default", "OpenSans-Regular.ttf")
+            .with_font("ToolTip", "OpenSans-Regular.ttf")
+            .with_size("FontSize", 22.0)
+            .with_size("ToolTipFontSize", 18.0)
+            .with_corner_radius("Button", 0.0)
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn with_color(mut self, key: &str, color: Color) -> Self {
+        self.colors.insert(key.to_string(), color);
+        self
+    }
+
+    pub fn with_brush(mut self, key: &str, brush: ThemeBrush) -> Self {
+        self.brushes.insert(key.to_string(), brush);
+        self
+    }
+
+    pub fn with_font(mut self, key: &str, font_name: &str) -> Self {
+        self.fonts.insert(key.to_string(), font_name.to_string());
+        self
+    }
+
+    pub fn with_size(mut self, key: &str, size: f32) -> Self {
+        self.sizes.insert(key.to_string(), size);
+        self
+    }
+
+    pub fn with_corner_radius(mut self, key: &str, radius: f32) -> Self {
+        self.corner_radii.insert(key.to_string(), radius);
+        self
+    }
+
+    pub fn get_color(&self, key: &str) -> Color {
+        match self.colors.get(key) {
+            Some(color) => *color,
+            None => self.fallback(|theme| theme.colors.get(key).cloned(), [1.0, 0.0, 1.0, 1.0]),
+        }
+    }
+
+    pub fn get_brush(&self, key: &str) -> ThemeBrush {
+        match self.brushes.get(key) {
+            Some(brush) => *brush,
+            None => self.fallback(
+                |theme| theme.brushes.get(key).cloned(),
+                ThemeBrush::Color([1.0, 0.0, 1.0, 1.0]),
+            ),
+        }
+    }
+
+    pub fn get_font(&self, key: &str) -> String {
+        match self.fonts.get(key) {
+            Some(font_name) => font_name.clone(),
+            None => self.fallback(
+                |theme| theme.fonts.get(key).cloned(),
+                "OpenSans-Regular.ttf".to_string(),
+            ),
+        }
+    }
+
+    pub fn get_size(&self, key: &str) -> f32 {
+        match self.sizes.get(key) {
+            Some(size) => *size,
+            None => self.fallback(|theme| theme.sizes.get(key).cloned(), 0.0f32),
+        }
+    }
+
+    pub fn get_corner_radius(&self, key: &str) -> f32 {
+        match self.corner_radii.get(key) {
+            Some(radius) => *radius,
+            None => self.fallback(|theme| theme.corner_radii.get(key).cloned(), 0.0f32),
+        }
+    }
+
+    fn fallback<T, F: Fn(&Theme) -> Option<T>>(&self, get: F, missing: T) -> T {
+        DEFAULT_THEME
+            .with(|theme| get(theme))
+            .unwrap_or(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_resources_come_from_default_theme() {
+        let theme = Theme::new("Custom").with_color("Foreground", [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(theme.get_color("Foreground"), [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(
+            theme.get_color("BorderDark"),
+            Theme::dark().get_color("BorderDark")
+        );
+        assert_eq!(theme.get_font("Default"), "OpenSans-Regular.ttf");
+        assert_eq!(theme.get_size("FontSize"), 20.0f32);
+    }
+
+    #[test]
+    fn test_built_in_themes_define_all_resources() {
+        let dark = Theme::dark();
+        for theme in &[Theme::light(), Theme::high_contrast()] {
+            for key in dark.colors.keys() {
+                assert!(theme.colors.contains_key(key), "{}: {}", theme.name, key);
+            }
+            for key in dark.brushes.keys() {
+                assert!(theme.brushes.contains_key(key), "{}: {}", theme.name, key);
+            }
+            for key in dark.fonts.keys() {
+                assert!(theme.fonts.contains_key(key), "{}: {}", theme.name, key);
+            }
+            for key in dark.sizes.keys() {
+                assert!(theme.sizes.contains_key(key), "{}: {}", theme.name, key);
+            }
+            for key in dark.corner_radii.keys() {
+                assert!(
+                    theme.corner_radii.contains_key(key),
+                    "{}: {}",
+                    theme.name,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fonts_can_be_set_at_runtime() {
+        let font_name = format!("{}.ttf", "Custom");
+        let theme = Theme::new("Custom").with_font("Default", &font_name);
+        assert_eq!(theme.get_font("Default"), "Custom.ttf");
+    }
+}
@@ -92,6 +92,20 @@ use crate::parser::CtrlProperty;
 //     }
 // )
 //
+// ui!(
+//     Button {
+//         Style: "PrimaryButton",
+//         Text { text: "OK" },
+//     }
+// )
+//
+// translates to:
+//
+// <Button>::builder().build().to_view(None, ViewContext {
+//     attached_values: { let mut map = TypeMap::new(); map.insert::<StyleName>("PrimaryButton".into()); map },
+//     children: ...
+// })
+//
 #[proc_macro]
 pub fn ui(input: TokenStream) -> TokenStream {
     let ctrl = parse_macro_input!(input as Ctrl);
@@ -221,6 +235,13 @@ fn decouple_params(
 
         if let CtrlParam::Style(s) = el {
             style = Some(s);
+        } else if let CtrlParam::StyleName(name) = el {
+            // named style is looked up in the style registries
+            // with the StyleName attached value
+            attached_values.push(CtrlProperty {
+                name: Ident::new("StyleName", name.span()),
+                expr: syn::parse_quote!(#name),
+            });
         } else if let CtrlParam::Property(property) = el {
             if let Some(first_char) = property.name.to_string().chars().next() {
                 if first_char.is_uppercase() {
@@ -1,7 +1,7 @@
 use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
-use syn::{braced, Error, Expr, ExprReference, Ident, Token};
+use syn::{braced, Error, Expr, ExprReference, Ident, LitStr, Token};
 
 /// Syntax of ui! macro.
 ///
@@ -56,6 +56,9 @@ pub enum CtrlParam {
     // Style: Default { property: "Value", },
     Style(Ctrl),
 
+    // Style: "PrimaryButton",
+    StyleName(LitStr),
+
     // property: "value",
     Property(CtrlProperty),
 
@@ -74,7 +77,11 @@ impl Parse for CtrlParam {
         if input.peek(keyword::Style) && input.peek2(Token![:]) {
             input.parse::<keyword::Style>()?;
             input.parse::<Token![:]>()?;
-            input.parse().map(CtrlParam::Style)
+            if input.peek(LitStr) {
+                input.parse().map(CtrlParam::StyleName)
+            } else {
+                input.parse().map(CtrlParam::Style)
+            }
         } else if input.peek(Ident) && input.peek2(Token![:]) {
             input.parse().map(CtrlParam::Property)
         } else if input.peek(Token![@]) {
@@ -263,4 +270,20 @@ mod tests {
             panic!("Expected CtrlParam::Style");
         }
     }
+
+    #[test]
+    fn test_control_with_style_name() {
+        let ctrl: Ctrl = parse_quote!(Button {
+            Style: "PrimaryButton",
+            Text { text: "OK" },
+        });
+        assert_eq!(ctrl.params.len(), 2);
+
+        let param = ctrl.params.into_iter().next().unwrap();
+        if let CtrlParam::StyleName(name) = param {
+            assert_eq!(name.value(), "PrimaryButton");
+        } else {
+            panic!("Expected CtrlParam::StyleName");
+        }
+    }
 }